@@ -1,4 +1,5 @@
 // Warning: this file is automatically generated. Don't edit by hand!
+// elm-duet 0.1.0, schema 72ad229d824f0fe1, content c73f191473a690a0
 
 declare module Elm {
   namespace Main {
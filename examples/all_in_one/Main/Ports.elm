@@ -1,6 +1,7 @@
 port module Main.Ports exposing (..)
 
 {-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema 72ad229d824f0fe1, content 4c41403993a43976
 -}
 
 import Dict exposing (Dict)
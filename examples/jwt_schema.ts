@@ -1,14 +1,21 @@
 // Warning: this file is automatically generated. Don't edit by hand!
+// elm-duet 0.1.0, schema 13c46321ad3cd9b4, content fe6f0dd16fbc755f
 
 declare module Elm {
+  type CurrentJwt = string;
+
+  type Jwt = string;
+
+  type NewJwt = string;
+
   namespace Main {
     type Flags = {
-      currentJwt: string | null;
+      currentJwt: Elm.CurrentJwt | null;
     };
 
     type Ports = {
       newJwt?: {
-        subscribe: (callback: (value: string) => void) => void;
+        subscribe: (callback: (value: Elm.NewJwt) => void) => void;
       };
     };
 
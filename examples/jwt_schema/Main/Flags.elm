@@ -1,6 +1,7 @@
 module Main.Flags exposing (..)
 
 {-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema 13c46321ad3cd9b4, content 719d865d1b0c78a6
 -}
 
 import Dict exposing (Dict)
@@ -1,6 +1,7 @@
 port module Main.Ports exposing (..)
 
 {-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema 13c46321ad3cd9b4, content 297e1b5b53bcdb56
 -}
 
 import Dict exposing (Dict)
@@ -1,11 +1,20 @@
 use crate::inflected_string::InflectedString;
+use crate::metadata;
 use eyre::{bail, eyre, Result, WrapErr};
 use jtd::Schema;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
+use syntax::{Expr, Pattern};
+
+mod syntax;
+
+const GENERATED_WARNING: &str =
+    "Warning: this file is automatically generated. Don't edit by hand!";
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum Type {
-    Int,
+    // JTD integers have a range depending on their width. Elm doesn't care, but we use it to
+    // generate values that will survive the trip through JSON.
+    Int { min: i64, max: i64 },
     Float,
     Bool,
     String,
@@ -23,11 +32,42 @@ pub enum RecordPresence {
     Optional,
 }
 
+/// How we generate Elm types for JTD `properties`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, clap::ValueEnum)]
+pub enum RecordStyle {
+    // `type alias Foo = { ... }`
+    #[default]
+    Alias,
+
+    // `type Foo = Foo { ... }`, with the constructor hidden behind accessor and constructor
+    // functions.
+    Opaque,
+}
+
+impl RecordStyle {
+    /// Read the style from `metadata.elm.record`, if it's set.
+    fn from_metadata(metadata: &jtd::Metadata) -> Result<Option<Self>> {
+        match metadata
+            .get("elm")
+            .and_then(|elm| elm.get("record"))
+            .map(|record| record.as_str())
+        {
+            None => Ok(None),
+            Some(Some("alias")) => Ok(Some(Self::Alias)),
+            Some(Some("opaque")) => Ok(Some(Self::Opaque)),
+            Some(other) => bail!(
+                "`metadata.elm.record` should be either \"alias\" or \"opaque\", but I got {other:?}"
+            ),
+        }
+    }
+}
+
 impl Type {
     pub fn from_schema(
         schema: Schema,
         name_suggestion: Option<String>,
         globals: &BTreeMap<String, Schema>,
+        record_style: RecordStyle,
         discriminator: Option<(String, String)>,
     ) -> Result<(Self, Vec<Decl>)> {
         let mut is_nullable = false;
@@ -48,6 +88,7 @@ impl Type {
                         schema.clone(),
                         name_suggestion.or_else(|| Some(ref_.to_string())),
                         globals,
+                        record_style,
                         discriminator.clone(),
                     )
                     .wrap_err_with(|| format!("could not convert the value of ref `{ref_}`"))?;
@@ -64,12 +105,30 @@ impl Type {
 
                 match type_ {
                     jtd::Type::Boolean => Self::Bool,
-                    jtd::Type::Int8
-                    | jtd::Type::Uint8
-                    | jtd::Type::Int16
-                    | jtd::Type::Uint16
-                    | jtd::Type::Int32
-                    | jtd::Type::Uint32 => Self::Int,
+                    jtd::Type::Int8 => Self::Int {
+                        min: i8::MIN.into(),
+                        max: i8::MAX.into(),
+                    },
+                    jtd::Type::Uint8 => Self::Int {
+                        min: u8::MIN.into(),
+                        max: u8::MAX.into(),
+                    },
+                    jtd::Type::Int16 => Self::Int {
+                        min: i16::MIN.into(),
+                        max: i16::MAX.into(),
+                    },
+                    jtd::Type::Uint16 => Self::Int {
+                        min: u16::MIN.into(),
+                        max: u16::MAX.into(),
+                    },
+                    jtd::Type::Int32 => Self::Int {
+                        min: i32::MIN.into(),
+                        max: i32::MAX.into(),
+                    },
+                    jtd::Type::Uint32 => Self::Int {
+                        min: u32::MIN.into(),
+                        max: u32::MAX.into(),
+                    },
                     jtd::Type::Float32 | jtd::Type::Float64 => Self::Float,
                     jtd::Type::String | jtd::Type::Timestamp => Self::String,
                 }
@@ -88,11 +147,28 @@ impl Type {
                     is_nullable = nullable;
 
                     let mut cases = BTreeMap::new();
-                    for value in enum_ {
-                        cases.insert(value.into(), None);
+                    for value in &enum_ {
+                        cases.insert(value.as_str().into(), None);
+                    }
+
+                    let values = metadata::enum_values(&metadata)
+                        .wrap_err_with(|| format!("could not read the values of `{name}`"))?;
+
+                    let mut constructor_names = BTreeMap::new();
+                    for (value, value_metadata) in &values {
+                        if !enum_.contains(value) {
+                            bail!("`metadata.values` has `{value}`, but that's not one of the values of `{name}`")
+                        }
+
+                        if let Some(constructor) =
+                            value_metadata.get("name").and_then(|n| n.as_str())
+                        {
+                            constructor_names
+                                .insert(value.as_str().into(), constructor.to_string());
+                        }
                     }
 
-                    decls.push(Decl::CustomTypeEnum {
+                    let mut decl = Decl::CustomTypeEnum {
                         name: name.into(),
                         discriminator: None,
                         constructor_prefix: metadata
@@ -101,7 +177,33 @@ impl Type {
                             .unwrap_or("")
                             .into(),
                         cases,
-                    });
+                        constructor_names,
+                        open: metadata::is_open(&metadata),
+                        docs: Docs::from_metadata(&metadata),
+                    };
+
+                    // we document the values by the names people will see in Elm, so we can't
+                    // do this until we know the constructor names.
+                    for (value, value_metadata) in &values {
+                        let constructor = decl.case_constructor_name(&value.as_str().into())?;
+
+                        if let Some(description) =
+                            value_metadata.get("description").and_then(|d| d.as_str())
+                        {
+                            decl.docs_mut()
+                                .member_descriptions
+                                .insert(constructor.clone(), description.to_string());
+                        }
+
+                        if let Some(reason) = metadata::deprecation(value_metadata) {
+                            decl.docs_mut()
+                                .deprecated_members
+                                .insert(constructor, reason);
+                        }
+                    }
+
+                    decl.check_constructor_names()?;
+                    decls.push(decl);
 
                     Self::Ref(name.into())
                 }
@@ -116,6 +218,7 @@ impl Type {
                     *elements,
                     name_suggestion.map(|n| format!("{n}Elements")),
                     globals,
+                    record_style,
                     discriminator.clone(),
                 )
                 .wrap_err("could not convert elements of a list")?;
@@ -138,12 +241,19 @@ impl Type {
                 Some(name) => {
                     is_nullable = nullable;
 
+                    let mut docs = Docs::from_metadata(&metadata);
+
                     let mut fields = BTreeMap::new();
                     for (field_name, field_schema) in properties {
+                        if let Some(reason) = metadata::deprecation(field_schema.metadata()) {
+                            docs.deprecated_members.insert(field_name.clone(), reason);
+                        }
+
                         let (field_type, field_decls) = Self::from_schema(
                             field_schema,
                             Some(field_name.clone()),
                             globals,
+                            record_style,
                             None, // We'll actually use this in the unified handler below!
                         )
                         .wrap_err_with(|| {
@@ -155,10 +265,15 @@ impl Type {
                     }
 
                     for (field_name, field_schema) in optional_properties {
+                        if let Some(reason) = metadata::deprecation(field_schema.metadata()) {
+                            docs.deprecated_members.insert(field_name.clone(), reason);
+                        }
+
                         let (field_type, field_decls) = Self::from_schema(
                             field_schema,
                             Some(field_name.clone()),
                             globals,
+                            record_style,
                             None, // We'll actually use this in the unified handler below!
                         )
                         .wrap_err_with(|| {
@@ -173,10 +288,23 @@ impl Type {
                         );
                     }
 
-                    decls.push(Decl::TypeAlias {
-                        name: name.into(),
-                        discriminator: None,
-                        type_: Self::Record(fields),
+                    let style = RecordStyle::from_metadata(&metadata)
+                        .wrap_err_with(|| format!("could not read the record style for `{name}`"))?
+                        .unwrap_or(record_style);
+
+                    decls.push(match style {
+                        RecordStyle::Alias => Decl::TypeAlias {
+                            name: name.into(),
+                            discriminator: None,
+                            type_: Self::Record(fields),
+                            docs,
+                        },
+                        RecordStyle::Opaque => Decl::OpaqueRecord {
+                            name: name.into(),
+                            discriminator: None,
+                            fields,
+                            docs,
+                        },
                     });
 
                     Self::Ref(name.into())
@@ -192,6 +320,7 @@ impl Type {
                     *values,
                     name_suggestion.map(|n| format!("{n}Values")),
                     globals,
+                    record_style,
                     discriminator.clone(),
                 )
                 .wrap_err("could not convert elements of a list")?;
@@ -220,6 +349,7 @@ impl Type {
                             tag_schema,
                             Some(tag.to_string()),
                             globals,
+                            record_style,
                             Some((discriminator_field.clone(), tag.to_string())),
                         )
                         .wrap_err_with(|| format!("could not convert mapping for `{tag}`"))?;
@@ -227,7 +357,8 @@ impl Type {
                         decls.extend(value_decls);
                         cases.insert(tag.into(), Some(value_type));
                     }
-                    decls.push(Decl::CustomTypeEnum {
+
+                    let decl = Decl::CustomTypeEnum {
                         name: name.into(),
                         discriminator: Some(discriminator_field),
                         constructor_prefix: metadata
@@ -236,7 +367,13 @@ impl Type {
                             .unwrap_or("")
                             .into(),
                         cases,
-                    });
+                        constructor_names: BTreeMap::new(),
+                        open: metadata::is_open(&metadata),
+                        docs: Docs::from_metadata(&metadata),
+                    };
+
+                    decl.check_constructor_names()?;
+                    decls.push(decl);
 
                     Self::Ref(name.into())
                 }
@@ -255,6 +392,7 @@ impl Type {
                         name: name.clone(),
                         type_: Type::Record(BTreeMap::new()),
                         discriminator: Some((discriminator_tag, discriminator_value)),
+                        docs: Docs::default(),
                     });
 
                     base = Self::Ref(name);
@@ -269,7 +407,7 @@ impl Type {
                         }
                     }
                 }
-                Self::Int => bail!("I can't add a discriminator to an int"),
+                Self::Int { .. } => bail!("I can't add a discriminator to an int"),
                 Self::Float => bail!("I can't add a discriminator to an float"),
                 Self::Bool => bail!("I can't add a discriminator to a bool"),
                 Self::String => bail!("I can't add a discriminator to a string"),
@@ -290,250 +428,243 @@ impl Type {
         ))
     }
 
-    fn to_source(&self) -> Result<String> {
+    fn to_annotation(&self) -> Result<syntax::Type> {
         Ok(match self {
-            Type::Bool => String::from("Bool"),
-            Type::Int => String::from("Int"),
-            Type::Float => String::from("Float"),
-            Type::String => String::from("String"),
-            Type::Maybe(inner) => {
-                let mut out = String::from("Maybe ");
-
-                let inner_source = inner.to_source()?;
-
-                if inner_source.contains(' ') {
-                    out.push('(');
-                    out.push_str(&inner_source);
-                    out.push(')');
-                } else {
-                    out.push_str(&inner_source);
+            Type::Bool => syntax::Type::named("Bool"),
+            Type::Int { .. } => syntax::Type::named("Int"),
+            Type::Float => syntax::Type::named("Float"),
+            Type::String => syntax::Type::named("String"),
+            Type::Maybe(inner) => syntax::Type::apply("Maybe", Vec::from([inner.to_annotation()?])),
+            Type::Unit => syntax::Type::Unit,
+            Type::DictWithStringKeys(inner) => syntax::Type::apply(
+                "Dict",
+                Vec::from([syntax::Type::named("String"), inner.to_annotation()?]),
+            ),
+            Type::List(inner) => syntax::Type::apply("List", Vec::from([inner.to_annotation()?])),
+            Type::Ref(ref_) => syntax::Type::named(ref_.to_pascal_case()?),
+            Type::Record(fields) => {
+                let mut out = Vec::with_capacity(fields.len());
+                for (name, (value, _)) in fields {
+                    out.push((name.to_camel_case()?, value.to_annotation()?));
                 }
-
-                out
+                syntax::Type::Record(out)
             }
-            Type::Unit => "()".to_string(),
-            Type::DictWithStringKeys(inner) => {
-                let mut out = String::from("Dict String ");
-
-                let inner_source = inner.to_source()?;
-
-                if inner_source.contains(' ') {
-                    out.push('(');
-                    out.push_str(&inner_source);
-                    out.push(')');
-                } else {
-                    out.push_str(&inner_source);
-                }
+        })
+    }
 
-                out
+    fn to_decoder(&self, dest_type: &str) -> Result<Expr> {
+        Ok(match self {
+            Type::Int { .. } => Expr::var("Json.Decode.int"),
+            Type::Float => Expr::var("Json.Decode.float"),
+            Type::Bool => Expr::var("Json.Decode.bool"),
+            Type::String => Expr::var("Json.Decode.string"),
+            Type::Maybe(type_) => Expr::call(
+                "Json.Decode.nullable",
+                Vec::from([type_.to_decoder(dest_type)?]),
+            ),
+            Type::Unit => Expr::call("Json.Decode.null", Vec::from([Expr::Unit])),
+            Type::DictWithStringKeys(type_) => Expr::call(
+                "Json.Decode.dict",
+                Vec::from([type_.to_decoder(dest_type)?]),
+            ),
+            Type::List(type_) => Expr::call(
+                "Json.Decode.list",
+                Vec::from([type_.to_decoder(dest_type)?]),
+            ),
+            Type::Ref(name) => Expr::var(format!("{}Decoder", name.to_camel_case()?)),
+            Type::Record(fields) => {
+                Expr::call("Json.Decode.succeed", Vec::from([Expr::var(dest_type)]))
+                    .pipeline(Self::record_pipeline(fields, dest_type)?)
             }
-            Type::List(inner) => {
-                let mut out = String::from("List ");
-
-                let inner_source = inner.to_source()?;
+        })
+    }
 
-                if inner_source.contains(' ') {
-                    out.push('(');
-                    out.push_str(&inner_source);
-                    out.push(')');
-                } else {
-                    out.push_str(&inner_source);
+    /// The `Json.Decode.Pipeline.required ...` steps that decode each field of a record.
+    fn record_pipeline(
+        fields: &BTreeMap<InflectedString, (Type, RecordPresence)>,
+        dest_type: &str,
+    ) -> Result<Vec<Expr>> {
+        let mut out = Vec::with_capacity(fields.len());
+
+        for (name, (field_type, presence)) in fields {
+            let mut args =
+                Vec::from([Expr::string(name.orig()), field_type.to_decoder(dest_type)?]);
+
+            let function = match presence {
+                RecordPresence::Required => "Json.Decode.Pipeline.required",
+                RecordPresence::Optional => {
+                    args.push(Expr::var("Nothing"));
+                    "Json.Decode.Pipeline.optional"
                 }
+            };
 
-                out
-            }
-            Type::Ref(ref_) => ref_.to_pascal_case()?,
-            Type::Record(fields) => {
-                let mut out = String::new();
-
-                if fields.is_empty() {
-                    out.push_str("{}");
-                } else {
-                    for (i, (name, (value, _))) in fields.iter().enumerate() {
-                        if i == 0 {
-                            out.push_str("{ ");
-                        } else {
-                            out.push_str("\n, ");
-                        }
-
-                        out.push_str(&name.to_camel_case()?);
-                        out.push_str(" : ");
-                        out.push_str(&value.to_source()?.replace('\n', "\n    "));
-                    }
+            out.push(Expr::call(function, args));
+        }
 
-                    out.push_str("\n}");
-                }
-                out
-            }
-        })
+        Ok(out)
     }
 
-    fn to_decoder_source(&self, dest_type: &str) -> Result<String> {
-        let mut out = String::new();
-
+    /// Add the names of all the declarations this type refers to to `out`.
+    fn collect_refs(&self, out: &mut BTreeSet<InflectedString>) {
         match self {
-            Type::Int => out.push_str("Json.Decode.int"),
-            Type::Float => out.push_str("Json.Decode.float"),
-            Type::Bool => out.push_str("Json.Decode.bool"),
-            Type::String => out.push_str("Json.Decode.string"),
-            Type::Maybe(type_) => {
-                let sub_decoder = type_.to_decoder_source(dest_type)?;
-                out.push_str("Json.Decode.nullable ");
-
-                if sub_decoder.contains(' ') {
-                    out.push('(');
-                    out.push_str(&sub_decoder);
-                    out.push(')');
-                } else {
-                    out.push_str(&sub_decoder);
-                }
+            Type::Int { .. } | Type::Float | Type::Bool | Type::String | Type::Unit => (),
+            Type::Maybe(inner) | Type::DictWithStringKeys(inner) | Type::List(inner) => {
+                inner.collect_refs(out)
             }
-            Type::Unit => out.push_str("Json.Decode.null ()"),
-            Type::DictWithStringKeys(type_) => {
-                let sub_decoder = type_.to_decoder_source(dest_type)?;
-                out.push_str("Json.Decode.dict ");
-
-                if sub_decoder.contains(' ') {
-                    out.push('(');
-                    out.push_str(&sub_decoder);
-                    out.push(')');
-                } else {
-                    out.push_str(&sub_decoder);
-                }
+            Type::Ref(ref_) => {
+                out.insert(ref_.clone());
             }
-            Type::List(type_) => {
-                let sub_decoder = type_.to_decoder_source(dest_type)?;
-                out.push_str("Json.Decode.list ");
-
-                if sub_decoder.contains(' ') {
-                    out.push('(');
-                    out.push_str(&sub_decoder);
-                    out.push(')');
-                } else {
-                    out.push_str(&sub_decoder);
+            Type::Record(fields) => {
+                for (field_type, _) in fields.values() {
+                    field_type.collect_refs(out)
                 }
             }
-            Type::Ref(name) => {
-                out.push_str(&name.to_camel_case()?);
-                out.push_str("Decoder");
-            }
-            Type::Record(fields) => {
-                out.push_str("Json.Decode.succeed ");
-                out.push_str(dest_type);
+        }
+    }
 
-                for (name, (field_type, presence)) in fields {
-                    let sub_decoder = field_type.to_decoder_source(dest_type)?;
+    /// A fuzzer (from elm-explorations/test) that generates values of this type.
+    fn to_fuzzer(&self) -> Result<Expr> {
+        Ok(match self {
+            Type::Int { min, max } => Expr::call(
+                "Fuzz.intRange",
+                Vec::from([Expr::Int(*min), Expr::Int(*max)]),
+            ),
+            // JSON can't represent NaN or infinity, so we stick to the nice ones.
+            Type::Float => Expr::var("Fuzz.niceFloat"),
+            Type::Bool => Expr::var("Fuzz.bool"),
+            Type::String => Expr::var("Fuzz.string"),
+            Type::Maybe(inner) => Expr::call("Fuzz.maybe", Vec::from([inner.to_fuzzer()?])),
+            Type::Unit => Expr::call("Fuzz.constant", Vec::from([Expr::Unit])),
+            Type::DictWithStringKeys(inner) => Expr::call(
+                "Fuzz.map",
+                Vec::from([
+                    Expr::var("Dict.fromList"),
+                    Expr::call(
+                        "Fuzz.list",
+                        Vec::from([Expr::call(
+                            "Fuzz.pair",
+                            Vec::from([Expr::var("Fuzz.string"), inner.to_fuzzer()?]),
+                        )]),
+                    ),
+                ]),
+            ),
+            Type::List(inner) => Expr::call("Fuzz.list", Vec::from([inner.to_fuzzer()?])),
+            Type::Ref(ref_) => Expr::var(format!("{}Fuzzer", ref_.to_camel_case()?)),
+            Type::Record(fields) => Self::record_fuzzer(fields, None)?,
+        })
+    }
 
-                    out.push_str("\n    ");
-                    match presence {
-                        RecordPresence::Required => {
-                            out.push_str("|> Json.Decode.Pipeline.required \"")
-                        }
-                        RecordPresence::Optional => {
-                            out.push_str("|> Json.Decode.Pipeline.optional \"")
-                        }
-                    }
-                    out.push_str(name.orig());
-                    out.push_str("\" ");
+    /// Fuzz a record one field at a time. If we're making an opaque type, `constructor` is the
+    /// function that turns the record into the final value.
+    fn record_fuzzer(
+        fields: &BTreeMap<InflectedString, (Type, RecordPresence)>,
+        constructor: Option<&str>,
+    ) -> Result<Expr> {
+        let mut params = Vec::with_capacity(fields.len());
+        let mut record = Vec::with_capacity(fields.len());
+        for field in fields.keys() {
+            let field_name = field.to_camel_case()?;
+            params.push(Pattern::var(format!("{field_name}_")));
+            record.push((field_name.clone(), Expr::var(format!("{field_name}_"))));
+        }
 
-                    if sub_decoder.contains(' ') {
-                        out.push('(');
-                        out.push_str(&sub_decoder);
-                        out.push(')');
-                    } else {
-                        out.push_str(&sub_decoder);
-                    }
+        let mut value = Expr::record(record);
+        if let Some(constructor) = constructor {
+            value = Expr::call(constructor, Vec::from([value]));
+        }
 
-                    if *presence == RecordPresence::Optional {
-                        out.push_str(" Nothing");
-                    }
-                }
-            }
+        if fields.is_empty() {
+            return Ok(Expr::call("Fuzz.constant", Vec::from([value])));
         }
 
-        Ok(out)
+        // there's no constructor function for an anonymous record, so we build one with a lambda
+        // (same as in the decoders.)
+        let mut steps = Vec::with_capacity(fields.len());
+        for (field_type, _) in fields.values() {
+            steps.push(Expr::call(
+                "Fuzz.andMap",
+                Vec::from([field_type.to_fuzzer()?]),
+            ));
+        }
+
+        Ok(Expr::call("Fuzz.constant", Vec::from([Expr::lambda(params, value)])).pipeline(steps))
     }
 
-    fn to_encoder_source(
+    fn to_encoder(
         &self,
         source_var: &str,
         discriminator_field_opt: &Option<(String, String)>,
-    ) -> Result<String> {
-        let mut out = String::new();
+    ) -> Result<Expr> {
+        let source = Expr::var(source_var);
 
-        match self {
-            Type::Int => {
-                out.push_str("Json.Encode.int ");
-                out.push_str(source_var);
-            }
-            Type::Float => {
-                out.push_str("Json.Encode.float ");
-                out.push_str(source_var);
-            }
-            Type::Bool => {
-                out.push_str("Json.Encode.bool ");
-                out.push_str(source_var);
-            }
-            Type::String => {
-                out.push_str("Json.Encode.string ");
-                out.push_str(source_var);
-            }
-            Type::Maybe(type_) => {
-                out.push_str("case ");
-                out.push_str(source_var);
-                out.push_str(" of\n    Just value ->\n        ");
-                out.push_str(
-                    &type_
-                        .to_encoder_source("value", discriminator_field_opt)?
-                        .replace('\n', "\n       "),
-                );
-                out.push_str("\n\n    Nothing ->\n        Json.Encode.null");
-            }
-            Type::Unit => out.push_str("Json.Encode.null"),
-            Type::DictWithStringKeys(values) => {
-                out.push_str("Json.Encode.dict identity (\\value -> ");
-                out.push_str(&values.to_encoder_source("value", discriminator_field_opt)?);
-                out.push_str(") ");
-                out.push_str(source_var);
-            }
-            Type::List(values) => {
-                out.push_str("Json.Encode.list (\\value -> ");
-                out.push_str(&values.to_encoder_source("value", discriminator_field_opt)?);
-                out.push_str(") ");
-                out.push_str(source_var);
-            }
-            Type::Ref(ref_) => {
-                out.push_str("encode");
-                out.push_str(&ref_.to_pascal_case()?);
-                out.push(' ');
-                out.push_str(source_var);
-            }
+        Ok(match self {
+            Type::Int { .. } => Expr::call("Json.Encode.int", Vec::from([source])),
+            Type::Float => Expr::call("Json.Encode.float", Vec::from([source])),
+            Type::Bool => Expr::call("Json.Encode.bool", Vec::from([source])),
+            Type::String => Expr::call("Json.Encode.string", Vec::from([source])),
+            Type::Maybe(type_) => Expr::case(
+                source,
+                Vec::from([
+                    (
+                        Pattern::constructor("Just", Vec::from([Pattern::var("value")])),
+                        type_.to_encoder("value", discriminator_field_opt)?,
+                    ),
+                    (
+                        Pattern::constructor("Nothing", Vec::new()),
+                        Expr::var("Json.Encode.null"),
+                    ),
+                ]),
+            ),
+            Type::Unit => Expr::var("Json.Encode.null"),
+            Type::DictWithStringKeys(values) => Expr::call(
+                "Json.Encode.dict",
+                Vec::from([
+                    Expr::var("identity"),
+                    Expr::lambda(
+                        Vec::from([Pattern::var("value")]),
+                        values.to_encoder("value", discriminator_field_opt)?,
+                    ),
+                    source,
+                ]),
+            ),
+            Type::List(values) => Expr::call(
+                "Json.Encode.list",
+                Vec::from([
+                    Expr::lambda(
+                        Vec::from([Pattern::var("value")]),
+                        values.to_encoder("value", discriminator_field_opt)?,
+                    ),
+                    source,
+                ]),
+            ),
+            Type::Ref(ref_) => Expr::call(
+                format!("encode{}", ref_.to_pascal_case()?),
+                Vec::from([source]),
+            ),
             Type::Record(fields) => {
                 let mut field_encoders = Vec::with_capacity(fields.len() + 1);
                 let any_optional = fields
                     .values()
                     .any(|(_, presence)| *presence == RecordPresence::Optional);
 
+                // with optional fields, every field is a `Maybe` so `List.filterMap` can drop
+                // the missing ones.
+                let required = |pair: Expr| {
+                    if any_optional {
+                        Expr::call("Just", Vec::from([pair]))
+                    } else {
+                        pair
+                    }
+                };
+
                 for (name, (field_type, presence)) in fields {
                     let accessor = format!("{}.{}", source_var, name.to_camel_case()?);
 
-                    let mut encoder = String::new();
-
-                    match presence {
-                        RecordPresence::Required => {
-                            if any_optional {
-                                encoder.push_str("Just ");
-                            }
-                            encoder.push_str("( \"");
-                            encoder.push_str(name.orig());
-                            encoder.push_str("\", ");
-                            encoder.push_str(
-                                &field_type
-                                    .to_encoder_source(&accessor, discriminator_field_opt)?
-                                    .replace('\n', "\n    "),
-                            );
-                            encoder.push_str(" )");
-                        }
+                    field_encoders.push(match presence {
+                        RecordPresence::Required => required(Expr::Tuple(Vec::from([
+                            Expr::string(name.orig()),
+                            field_type.to_encoder(&accessor, discriminator_field_opt)?,
+                        ]))),
                         RecordPresence::Optional => {
                             let local_var = format!("{}_", name.to_camel_case()?);
 
@@ -544,55 +675,48 @@ impl Type {
                                 ),
                             };
 
-                            encoder.push_str("Maybe.map (\\");
-                            encoder.push_str(&local_var);
-                            encoder.push_str(" -> ( \"");
-                            encoder.push_str(name.orig());
-                            encoder.push_str("\", ");
-                            encoder.push_str(
-                                &maybe_inner
-                                    .to_encoder_source(&local_var, discriminator_field_opt)?
-                                    .replace('\n', "\n    "),
-                            );
-                            encoder.push_str(")) ");
-                            encoder.push_str(&accessor);
+                            Expr::call(
+                                "Maybe.map",
+                                Vec::from([
+                                    Expr::lambda(
+                                        Vec::from([Pattern::var(&local_var)]),
+                                        Expr::Tuple(Vec::from([
+                                            Expr::string(name.orig()),
+                                            maybe_inner
+                                                .to_encoder(&local_var, discriminator_field_opt)?,
+                                        ])),
+                                    ),
+                                    Expr::var(accessor),
+                                ]),
+                            )
                         }
-                    };
-
-                    field_encoders.push(encoder)
+                    });
                 }
 
                 if let Some((discriminator_name, discriminator_value)) = discriminator_field_opt {
-                    let mut encoder = String::new();
-                    if any_optional {
-                        encoder.push_str("Just ");
-                    }
-                    encoder.push_str("( \"");
-                    encoder.push_str(discriminator_name);
-                    encoder.push_str("\", Json.Encode.string \"");
-                    encoder.push_str(discriminator_value);
-                    encoder.push_str("\" )\n");
-
-                    field_encoders.push(encoder)
+                    field_encoders.push(required(Expr::Tuple(Vec::from([
+                        Expr::string(discriminator_name),
+                        Expr::call(
+                            "Json.Encode.string",
+                            Vec::from([Expr::string(discriminator_value)]),
+                        ),
+                    ]))));
                 }
 
                 if any_optional {
-                    out.push_str("List.filterMap identity\n    [ ")
+                    Expr::call(
+                        "List.filterMap",
+                        Vec::from([Expr::var("identity"), Expr::multiline_list(field_encoders)]),
+                    )
+                    .pipeline(Vec::from([Expr::var("Json.Encode.object")]))
                 } else {
-                    out.push_str("Json.Encode.object\n    [ ")
-                }
-
-                out.push_str(&field_encoders.join("\n    , "));
-
-                out.push_str("\n    ]");
-
-                if any_optional {
-                    out.push_str("\n    |> Json.Encode.object")
+                    Expr::call(
+                        "Json.Encode.object",
+                        Vec::from([Expr::multiline_list(field_encoders)]),
+                    )
                 }
             }
-        }
-
-        Ok(out)
+        })
     }
 }
 
@@ -603,6 +727,15 @@ pub enum Decl {
         discriminator: Option<String>,
         constructor_prefix: InflectedString,
         cases: BTreeMap<InflectedString, Option<Type>>,
+
+        // constructor names set by hand in `metadata.values`, for when we can't (or shouldn't)
+        // make one from the value itself.
+        constructor_names: BTreeMap<InflectedString, String>,
+
+        // open enums get an extra `Unknown` case to catch values we haven't heard of yet, so JS
+        // can start sending new ones before the Elm side is updated.
+        open: bool,
+        docs: Docs,
     },
     TypeAlias {
         name: InflectedString,
@@ -611,241 +744,793 @@ pub enum Decl {
         // a bit of a hack, but we need to add disciminators specifically to records in order to
         // make the decoders and encoders round-trip properly.
         discriminator: Option<(String, String)>,
+        docs: Docs,
+    },
+    OpaqueRecord {
+        name: InflectedString,
+        fields: BTreeMap<InflectedString, (Type, RecordPresence)>,
+
+        // same hack as in `TypeAlias`
+        discriminator: Option<(String, String)>,
+        docs: Docs,
     },
 }
 
+/// Notes from the schema metadata that we put in doc comments above declarations.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Docs {
+    pub deprecated: Option<String>,
+
+    // descriptions of the pieces of a declaration (like enum constructors), keyed by their name in
+    // Elm.
+    pub member_descriptions: BTreeMap<String, String>,
+
+    // record fields (or other pieces of a declaration) that are deprecated, keyed by the name they
+    // have in the schema.
+    pub deprecated_members: BTreeMap<String, String>,
+}
+
+impl Docs {
+    pub fn from_metadata(metadata: &jtd::Metadata) -> Self {
+        Self {
+            deprecated: metadata::deprecation(metadata),
+            member_descriptions: BTreeMap::new(),
+            deprecated_members: BTreeMap::new(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.deprecated.is_none()
+            && self.member_descriptions.is_empty()
+            && self.deprecated_members.is_empty()
+    }
+
+    /// The text of a doc comment, if there's anything to say.
+    fn to_text(&self) -> Option<String> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let mut paragraphs = Vec::new();
+
+        if let Some(reason) = &self.deprecated {
+            paragraphs.push(deprecation_note("**Deprecated**", reason));
+        }
+
+        for (member, description) in &self.member_descriptions {
+            paragraphs.push(format!("`{member}`: {description}"));
+        }
+
+        for (member, reason) in &self.deprecated_members {
+            paragraphs.push(deprecation_note(
+                &format!("`{member}` is deprecated"),
+                reason,
+            ));
+        }
+
+        Some(paragraphs.join("\n\n"))
+    }
+}
+
+fn deprecation_note(subject: &str, reason: &str) -> String {
+    if reason.is_empty() {
+        format!("{subject}.")
+    } else {
+        format!("{subject}: {reason}")
+    }
+}
+
 impl Decl {
-    fn to_source(&self) -> Result<String> {
-        let mut out = String::new();
+    fn to_declaration(&self) -> Result<syntax::Declaration> {
+        let docs = self.docs().to_text();
 
-        match self {
+        Ok(match self {
             Decl::CustomTypeEnum {
                 name,
-                constructor_prefix,
                 cases,
+                discriminator,
+                open,
                 ..
             } => {
-                out.push_str("type ");
-                out.push_str(&name.to_pascal_case()?);
-                out.push('\n');
-
-                for (i, (case_name, case_type_opt)) in cases.iter().enumerate() {
-                    if i == 0 {
-                        out.push_str("    = ");
-                    } else {
-                        out.push_str("    | ");
+                let mut constructors = Vec::with_capacity(cases.len() + 1);
+                for (case_name, case_type_opt) in cases {
+                    let mut args = Vec::new();
+                    if let Some(case_type) = case_type_opt {
+                        args.push(case_type.to_annotation()?);
                     }
 
-                    out.push_str(&constructor_prefix.to_pascal_case()?);
-                    out.push_str(&name.to_pascal_case()?);
-                    out.push_str(&case_name.to_pascal_case()?);
+                    constructors.push((self.case_constructor_name(case_name)?, args));
+                }
 
-                    if let Some(case_type) = case_type_opt {
-                        out.push(' ');
-                        out.push_str(&case_type.to_source()?.replace('\n', "\n    "));
+                if *open {
+                    let mut args = Vec::from([syntax::Type::named("String")]);
+                    if discriminator.is_some() {
+                        args.push(syntax::Type::named("Json.Decode.Value"));
                     }
 
-                    out.push('\n')
+                    constructors.push((self.unknown_constructor_name()?, args));
+                }
+
+                syntax::Declaration::CustomType {
+                    docs,
+                    name: name.to_pascal_case()?,
+                    constructors,
                 }
             }
-            Decl::TypeAlias { name, type_, .. } => {
-                out.push_str("type alias ");
-                out.push_str(&name.to_pascal_case()?);
-                out.push_str(" =\n    ");
-                out.push_str(&type_.to_source()?.replace('\n', "\n    "));
+            Decl::TypeAlias { name, type_, .. } => syntax::Declaration::TypeAlias {
+                docs,
+                name: name.to_pascal_case()?,
+                type_: type_.to_annotation()?,
+            },
+            Decl::OpaqueRecord { name, fields, .. } => {
+                let type_name = name.to_pascal_case()?;
+
+                syntax::Declaration::CustomType {
+                    docs,
+                    name: type_name.clone(),
+                    constructors: Vec::from([(
+                        type_name,
+                        Vec::from([Type::Record(fields.clone()).to_annotation()?]),
+                    )]),
+                }
             }
-        }
-
-        Ok(out)
+        })
     }
 
-    fn name(&self) -> &InflectedString {
+    pub fn name(&self) -> &InflectedString {
         match self {
             Decl::CustomTypeEnum { name, .. } => name,
             Decl::TypeAlias { name, .. } => name,
+            Decl::OpaqueRecord { name, .. } => name,
         }
     }
 
-    fn decoder_name(&self) -> Result<String> {
-        Ok(format!("{}Decoder", self.name().to_camel_case()?))
+    fn docs(&self) -> &Docs {
+        match self {
+            Decl::CustomTypeEnum { docs, .. } => docs,
+            Decl::TypeAlias { docs, .. } => docs,
+            Decl::OpaqueRecord { docs, .. } => docs,
+        }
     }
 
-    fn encoder_name(&self) -> Result<String> {
-        Ok(format!("encode{}", self.name().to_pascal_case()?))
+    fn docs_mut(&mut self) -> &mut Docs {
+        match self {
+            Decl::CustomTypeEnum { docs, .. } => docs,
+            Decl::TypeAlias { docs, .. } => docs,
+            Decl::OpaqueRecord { docs, .. } => docs,
+        }
     }
 
-    fn to_decoder_source(&self) -> Result<String> {
-        let mut out = String::new();
+    fn is_opaque(&self) -> bool {
+        matches!(self, Decl::OpaqueRecord { .. })
+    }
 
-        let name = self.name();
-        let decoder_name = self.decoder_name()?;
-        let type_name = name.to_pascal_case()?;
-        out.push_str(&decoder_name);
-        out.push_str(" : Json.Decode.Decoder ");
-        out.push_str(&type_name);
-        out.push('\n');
-        out.push_str(&decoder_name);
-        out.push_str(" =\n");
+    /// The names this declaration adds to a module, in the form we'd put them in an `exposing`
+    /// list.
+    fn exposing(&self) -> Result<Vec<String>> {
+        let mut out = Vec::new();
 
-        match &self {
+        match self {
             Decl::CustomTypeEnum {
-                constructor_prefix,
+                name,
                 discriminator,
-                cases,
                 ..
             } => {
-                out.push_str(
-                    "    Json.Decode.andThen\n        (\\tag ->\n            case tag of\n",
-                );
-
-                for (i, (case, case_type_opt)) in cases.iter().enumerate() {
-                    if i > 0 {
-                        out.push('\n');
-                    }
-
-                    out.push_str("                \"");
-                    out.push_str(case.orig());
-                    out.push_str("\" ->\n                    ");
-
-                    match case_type_opt {
-                        Some(type_) => {
-                            let sub_decoder = type_.to_decoder_source(&type_name)?;
-
-                            out.push_str("Json.Decode.map ");
-                            out.push_str(&constructor_prefix.to_pascal_case()?);
-                            out.push_str(&name.to_pascal_case()?);
-                            out.push_str(&case.to_pascal_case()?);
-                            if sub_decoder.contains('\n') {
-                                out.push_str("\n                        ");
-                                out.push_str(
-                                    &sub_decoder.replace('\n', "\n                        "),
-                                );
-                            } else {
-                                out.push(' ');
-                                out.push_str(&sub_decoder);
-                            }
-                        }
-                        None => {
-                            out.push_str("Json.Decode.succeed ");
-                            out.push_str(&constructor_prefix.to_pascal_case()?);
-                            out.push_str(&name.to_pascal_case()?);
-                            out.push_str(&case.to_pascal_case()?);
-                        }
-                    }
-                    out.push('\n');
-                }
+                out.push(format!("{}(..)", name.to_pascal_case()?));
 
-                out.push_str(
-                    "                unknown ->\n                    Json.Decode.fail (\"Unknown value `\" ++ unknown ++ \"`\")\n        )\n        ");
-                match discriminator {
-                    None => out.push_str("Json.Decode.string"),
-                    Some(name) => {
-                        out.push_str("(Json.Decode.field \"");
-                        out.push_str(name);
-                        out.push_str("\" Json.Decode.string)");
-                    }
+                if discriminator.is_none() {
+                    out.push(self.all_name()?);
+                    out.push(self.enum_to_string_name()?);
+                    out.push(self.enum_from_string_name()?);
                 }
             }
-            Decl::TypeAlias { type_, .. } => {
-                out.push_str("    ");
-                out.push_str(&type_.to_decoder_source(&type_name)?.replace('\n', "\n    "));
+            Decl::TypeAlias { name, .. } => out.push(name.to_pascal_case()?),
+            Decl::OpaqueRecord { name, fields, .. } => {
+                out.push(name.to_pascal_case()?);
+                out.push(self.constructor_name()?);
+                for field in fields.keys() {
+                    out.push(self.accessor_name(field)?);
+                }
             }
         }
 
+        out.push(self.decoder_name()?);
+        out.push(self.encoder_name()?);
+
         Ok(out)
     }
 
-    fn to_encoder_source(&self) -> Result<String> {
-        let mut out = String::new();
-
-        let name = self.name();
-        let decoder_name = self.encoder_name()?;
-        let type_name = name.to_pascal_case()?;
+    fn constructor_name(&self) -> Result<String> {
+        Ok(format!("make{}", self.name().to_pascal_case()?))
+    }
 
-        // We have an underscore after this because we commonly generate a port with the same name
-        // and that needs to take precedence.
-        let variable_name = format!("{}_", name.to_camel_case()?);
-
-        out.push_str(&decoder_name);
-        out.push_str(" : ");
-        out.push_str(&type_name);
-        out.push_str(" -> Json.Encode.Value\n");
-        out.push_str(&decoder_name);
-        out.push(' ');
-        out.push_str(&variable_name);
-        out.push_str(" =\n");
-
-        match &self {
+    /// The name of the Elm constructor for one case of a custom type. Unless the schema says
+    /// otherwise, this is the prefix, the type name, and the case name all smooshed together.
+    fn case_constructor_name(&self, case: &InflectedString) -> Result<String> {
+        match self {
             Decl::CustomTypeEnum {
+                name,
                 constructor_prefix,
-                cases,
+                constructor_names,
                 ..
-            } => {
-                out.push_str("    case ");
-                out.push_str(&variable_name);
-                out.push_str(" of\n");
-
-                for (i, (case, case_type_opt)) in cases.iter().enumerate() {
-                    if i > 0 {
-                        out.push_str("\n\n")
+            } => match constructor_names.get(case) {
+                // names from `metadata.values` go through the same inflection as the ones we
+                // make up, so `lower case` still gives us a valid constructor.
+                Some(constructor) => {
+                    let constructor =
+                        InflectedString::from(constructor.as_str()).to_pascal_case()?;
+                    if !constructor.starts_with(char::is_alphabetic) {
+                        bail!(
+                            "`{}` is not a valid name for the `{}` case of `{}`",
+                            constructor,
+                            case.orig(),
+                            name.orig()
+                        )
                     }
+                    Ok(constructor)
+                }
+                None => Ok(format!(
+                    "{}{}{}",
+                    constructor_prefix.to_pascal_case()?,
+                    name.to_pascal_case()?,
+                    case.to_pascal_case()?
+                )),
+            },
+            _ => bail!("only custom types have constructors for cases"),
+        }
+    }
 
-                    let case_name = InflectedString::from(format!(
-                        "{}{}{}",
-                        constructor_prefix.to_pascal_case()?,
-                        name.to_pascal_case()?,
-                        case.to_pascal_case()?
-                    ));
+    /// Every case needs its own constructor, and none of them can share a name with the type
+    /// itself or (for open enums) the case for unknown values.
+    fn check_constructor_names(&self) -> Result<()> {
+        if let Decl::CustomTypeEnum {
+            name, cases, open, ..
+        } = self
+        {
+            let type_name = name.to_pascal_case()?;
+            let unknown = if *open {
+                Some(self.unknown_constructor_name()?)
+            } else {
+                None
+            };
 
-                    out.push_str("        ");
-                    out.push_str(&case_name.to_pascal_case()?);
+            let mut seen: BTreeMap<String, &InflectedString> = BTreeMap::new();
+            for case in cases.keys() {
+                let constructor = self.case_constructor_name(case)?;
 
-                    if case_type_opt.is_some() {
-                        out.push(' ');
-                        out.push_str(&case_name.to_camel_case()?);
-                    }
+                if constructor == type_name {
+                    bail!("the `{}` case of `{}` would have a constructor named `{constructor}`, which is the name of the type itself. Give it a different `name` in `metadata.values`", case.orig(), name.orig())
+                }
 
-                    out.push_str(" ->\n            ");
+                if Some(&constructor) == unknown.as_ref() {
+                    bail!("`{}` is open, but already has a case named `{}`, which would conflict with the case for unknown values", name.orig(), case.orig())
+                }
 
-                    match case_type_opt {
-                        Some(case_type) => out.push_str(
-                            &case_type
-                                .to_encoder_source(&case_name.to_camel_case()?, &None)?
-                                .replace('\n', "\n            "),
-                        ),
-                        None => {
-                            out.push_str("Json.Encode.string \"");
-                            out.push_str(case.orig());
-                            out.push('"');
-                        }
-                    }
+                if let Some(other) = seen.insert(constructor.clone(), case) {
+                    bail!("the `{}` and `{}` cases of `{}` would both have a constructor named `{constructor}`. Give one of them a different `name` in `metadata.values`", other.orig(), case.orig(), name.orig())
                 }
             }
-            Decl::TypeAlias {
-                type_,
-                discriminator,
-                ..
-            } => {
-                out.push_str("    ");
-                out.push_str(
-                    &type_
-                        .to_encoder_source(&variable_name, discriminator)?
-                        .replace('\n', "\n    "),
-                );
-            }
         }
 
-        Ok(out)
+        Ok(())
     }
 
-    fn add_discriminator(&mut self, name: String, value: String) -> Result<()> {
-        match self {
-            Decl::CustomTypeEnum { .. } => bail!("cannot add a discriminator to a custom type"),
-            Decl::TypeAlias { discriminator, .. } => {
-                *discriminator = Some((name, value));
-                Ok(())
-            }
-        }
+    fn unknown_constructor_name(&self) -> Result<String> {
+        let prefix = match self {
+            Decl::CustomTypeEnum {
+                constructor_prefix, ..
+            } => constructor_prefix.to_pascal_case()?,
+            _ => String::new(),
+        };
+
+        Ok(format!("{prefix}{}Unknown", self.name().to_pascal_case()?))
+    }
+
+    fn all_name(&self) -> Result<String> {
+        Ok(format!("all{}", self.name().to_pascal_case()?))
+    }
+
+    fn enum_to_string_name(&self) -> Result<String> {
+        Ok(format!("{}ToString", self.name().to_camel_case()?))
+    }
+
+    fn enum_from_string_name(&self) -> Result<String> {
+        Ok(format!("{}FromString", self.name().to_camel_case()?))
+    }
+
+    fn accessor_name(&self, field: &InflectedString) -> Result<String> {
+        Ok(format!(
+            "{}{}",
+            self.name().to_camel_case()?,
+            field.to_pascal_case()?
+        ))
+    }
+
+    /// Functions that go along with the type (beyond decoders and encoders.) For opaque records,
+    /// this is how you construct the value and get at its fields. For enums, it's conversions
+    /// to and from the strings we use on the wire.
+    fn to_helpers(&self) -> Result<Vec<syntax::Declaration>> {
+        let mut out = Vec::new();
+
+        if let Decl::CustomTypeEnum {
+            name,
+            cases,
+            discriminator: None,
+            open,
+            ..
+        } = self
+        {
+            let type_name = name.to_pascal_case()?;
+            let variable_name = format!("{}_", name.to_camel_case()?);
+
+            let mut constructors = Vec::with_capacity(cases.len());
+            for case in cases.keys() {
+                constructors.push((case.orig(), self.case_constructor_name(case)?));
+            }
+
+            out.push(syntax::Declaration::Value {
+                docs: None,
+                name: self.all_name()?,
+                annotation: syntax::Type::apply(
+                    "List",
+                    Vec::from([syntax::Type::named(&type_name)]),
+                ),
+                params: Vec::new(),
+                body: Expr::multiline_list(
+                    constructors
+                        .iter()
+                        .map(|(_, constructor)| Expr::var(constructor))
+                        .collect(),
+                ),
+            });
+
+            let mut to_string_branches = Vec::with_capacity(constructors.len() + 1);
+            for (wire, constructor) in &constructors {
+                to_string_branches.push((
+                    Pattern::constructor(constructor, Vec::new()),
+                    Expr::string(*wire),
+                ));
+            }
+            if *open {
+                to_string_branches.push((
+                    Pattern::constructor(
+                        self.unknown_constructor_name()?,
+                        Vec::from([Pattern::var("unknown")]),
+                    ),
+                    Expr::var("unknown"),
+                ));
+            }
+            out.push(syntax::Declaration::Value {
+                docs: None,
+                name: self.enum_to_string_name()?,
+                annotation: syntax::Type::function(Vec::from([
+                    syntax::Type::named(&type_name),
+                    syntax::Type::named("String"),
+                ])),
+                params: Vec::from([Pattern::var(&variable_name)]),
+                body: Expr::case(Expr::var(&variable_name), to_string_branches),
+            });
+
+            // open enums can hold any string, so converting from one always works (and undoes
+            // `toString` for unknown values too.)
+            let mut from_string_branches = Vec::with_capacity(constructors.len() + 1);
+            for (wire, constructor) in &constructors {
+                let value = Expr::var(constructor);
+                from_string_branches.push((
+                    Pattern::Str((*wire).to_owned()),
+                    if *open {
+                        value
+                    } else {
+                        Expr::call("Just", Vec::from([value]))
+                    },
+                ));
+            }
+            let return_type = if *open {
+                from_string_branches.push((
+                    Pattern::var("unknown"),
+                    Expr::call(
+                        self.unknown_constructor_name()?,
+                        Vec::from([Expr::var("unknown")]),
+                    ),
+                ));
+                syntax::Type::named(&type_name)
+            } else {
+                from_string_branches.push((Pattern::Anything, Expr::var("Nothing")));
+                syntax::Type::apply("Maybe", Vec::from([syntax::Type::named(&type_name)]))
+            };
+            out.push(syntax::Declaration::Value {
+                docs: None,
+                name: self.enum_from_string_name()?,
+                annotation: syntax::Type::function(Vec::from([
+                    syntax::Type::named("String"),
+                    return_type,
+                ])),
+                params: Vec::from([Pattern::var("string_")]),
+                body: Expr::case(Expr::var("string_"), from_string_branches),
+            });
+        }
+
+        if let Decl::OpaqueRecord { name, fields, .. } = self {
+            let type_name = name.to_pascal_case()?;
+            let variable_name = format!("{}_", name.to_camel_case()?);
+
+            out.push(syntax::Declaration::Value {
+                docs: None,
+                name: self.constructor_name()?,
+                annotation: syntax::Type::function(Vec::from([
+                    Type::Record(fields.clone()).to_annotation()?,
+                    syntax::Type::named(&type_name),
+                ])),
+                params: Vec::new(),
+                body: Expr::var(&type_name),
+            });
+
+            for (field, (field_type, _)) in fields {
+                out.push(syntax::Declaration::Value {
+                    docs: None,
+                    name: self.accessor_name(field)?,
+                    annotation: syntax::Type::function(Vec::from([
+                        syntax::Type::named(&type_name),
+                        field_type.to_annotation()?,
+                    ])),
+                    params: Vec::from([Pattern::constructor(
+                        &type_name,
+                        Vec::from([Pattern::var(&variable_name)]),
+                    )]),
+                    body: Expr::var(format!("{variable_name}.{}", field.to_camel_case()?)),
+                });
+            }
+        }
+
+        Ok(out)
+    }
+
+    fn decoder_name(&self) -> Result<String> {
+        Ok(format!("{}Decoder", self.name().to_camel_case()?))
+    }
+
+    fn encoder_name(&self) -> Result<String> {
+        Ok(format!("encode{}", self.name().to_pascal_case()?))
+    }
+
+    fn fuzzer_name(&self) -> Result<String> {
+        Ok(format!("{}Fuzzer", self.name().to_camel_case()?))
+    }
+
+    /// The names of the other declarations this one refers to.
+    fn refs(&self) -> BTreeSet<InflectedString> {
+        let mut out = BTreeSet::new();
+
+        match self {
+            Decl::CustomTypeEnum { cases, .. } => {
+                for case_type in cases.values().flatten() {
+                    case_type.collect_refs(&mut out)
+                }
+            }
+            Decl::TypeAlias { type_, .. } => type_.collect_refs(&mut out),
+            Decl::OpaqueRecord { fields, .. } => {
+                for (field_type, _) in fields.values() {
+                    field_type.collect_refs(&mut out)
+                }
+            }
+        }
+
+        out
+    }
+
+    /// Does this declaration keep a raw `Json.Decode.Value` around? (Only the `Unknown` case of
+    /// an open discriminator does this, but we don't look through refs here.)
+    fn holds_raw_json(&self) -> bool {
+        matches!(
+            self,
+            Decl::CustomTypeEnum {
+                discriminator: Some(_),
+                open: true,
+                ..
+            }
+        )
+    }
+
+    fn to_fuzzer(&self) -> Result<syntax::Declaration> {
+        let body = match self {
+            Decl::CustomTypeEnum {
+                discriminator,
+                cases,
+                open,
+                ..
+            } => {
+                let mut options = Vec::with_capacity(cases.len() + 1);
+                for (case, case_type_opt) in cases {
+                    let constructor = Expr::var(self.case_constructor_name(case)?);
+
+                    options.push(match case_type_opt {
+                        Some(case_type) => {
+                            Expr::call("Fuzz.map", Vec::from([constructor, case_type.to_fuzzer()?]))
+                        }
+                        None => constructor,
+                    });
+                }
+
+                if *open {
+                    // pick a value we don't know about, or we'd get a known case back when
+                    // decoding what we generated.
+                    let mut unknown = String::from("unknown");
+                    while cases.keys().any(|case| case.orig() == unknown) {
+                        unknown.push('_');
+                    }
+
+                    let unknown_constructor = self.unknown_constructor_name()?;
+                    options.push(match discriminator {
+                        None => Expr::call(unknown_constructor, Vec::from([Expr::string(unknown)])),
+                        Some(tag) => Expr::call(
+                            "Fuzz.constant",
+                            Vec::from([Expr::call(
+                                unknown_constructor,
+                                Vec::from([
+                                    Expr::string(&unknown),
+                                    Expr::call(
+                                        "Json.Encode.object",
+                                        Vec::from([Expr::list(Vec::from([Expr::Tuple(
+                                            Vec::from([
+                                                Expr::string(tag),
+                                                Expr::call(
+                                                    "Json.Encode.string",
+                                                    Vec::from([Expr::string(&unknown)]),
+                                                ),
+                                            ]),
+                                        )]))]),
+                                    ),
+                                ]),
+                            )]),
+                        ),
+                    });
+                }
+
+                // enum cases are plain values, but the cases of a discriminator need their own
+                // fuzzers for what's inside.
+                Expr::call(
+                    if discriminator.is_some() {
+                        "Fuzz.oneOf"
+                    } else {
+                        "Fuzz.oneOfValues"
+                    },
+                    Vec::from([Expr::multiline_list(options)]),
+                )
+            }
+            Decl::TypeAlias { type_, .. } => type_.to_fuzzer()?,
+            Decl::OpaqueRecord { fields, .. } => {
+                Type::record_fuzzer(fields, Some(&self.constructor_name()?))?
+            }
+        };
+
+        Ok(syntax::Declaration::Value {
+            docs: None,
+            name: self.fuzzer_name()?,
+            annotation: syntax::Type::apply(
+                "Fuzzer",
+                Vec::from([syntax::Type::named(self.name().to_pascal_case()?)]),
+            ),
+            params: Vec::new(),
+            body,
+        })
+    }
+
+    fn to_decoder(&self) -> Result<syntax::Declaration> {
+        let type_name = self.name().to_pascal_case()?;
+
+        let body = match &self {
+            Decl::CustomTypeEnum {
+                discriminator,
+                cases,
+                open,
+                ..
+            } => {
+                let mut branches = Vec::with_capacity(cases.len() + 1);
+                for (case, case_type_opt) in cases {
+                    let constructor = Expr::var(self.case_constructor_name(case)?);
+
+                    branches.push((
+                        Pattern::Str(case.orig().to_owned()),
+                        match case_type_opt {
+                            Some(type_) => Expr::call(
+                                "Json.Decode.map",
+                                Vec::from([constructor, type_.to_decoder(&type_name)?]),
+                            ),
+                            None => Expr::call("Json.Decode.succeed", Vec::from([constructor])),
+                        },
+                    ));
+                }
+
+                let unknown = match (open, discriminator) {
+                    (false, _) => Expr::call(
+                        "Json.Decode.fail",
+                        Vec::from([Expr::Binops {
+                            first: Box::new(Expr::string("Unknown value `")),
+                            rest: Vec::from([
+                                (String::from("++"), Expr::var("unknown")),
+                                (String::from("++"), Expr::string("`")),
+                            ]),
+                            multiline: false,
+                        }]),
+                    ),
+                    (true, None) => Expr::call(
+                        "Json.Decode.succeed",
+                        Vec::from([Expr::call(
+                            self.unknown_constructor_name()?,
+                            Vec::from([Expr::var("unknown")]),
+                        )]),
+                    ),
+                    (true, Some(_)) => Expr::call(
+                        "Json.Decode.map",
+                        Vec::from([
+                            Expr::call(
+                                self.unknown_constructor_name()?,
+                                Vec::from([Expr::var("unknown")]),
+                            ),
+                            Expr::var("Json.Decode.value"),
+                        ]),
+                    ),
+                };
+                branches.push((Pattern::var("unknown"), unknown));
+
+                let tag_decoder = match discriminator {
+                    None => Expr::var("Json.Decode.string"),
+                    Some(name) => Expr::call(
+                        "Json.Decode.field",
+                        Vec::from([Expr::string(name), Expr::var("Json.Decode.string")]),
+                    ),
+                };
+
+                Expr::call(
+                    "Json.Decode.andThen",
+                    Vec::from([
+                        Expr::lambda(
+                            Vec::from([Pattern::var("tag")]),
+                            Expr::case(Expr::var("tag"), branches),
+                        ),
+                        tag_decoder,
+                    ]),
+                )
+            }
+            Decl::TypeAlias { type_, .. } => type_.to_decoder(&type_name)?,
+            Decl::OpaqueRecord { fields, .. } => {
+                let mut params = Vec::with_capacity(fields.len());
+                let mut record = Vec::with_capacity(fields.len());
+                for field in fields.keys() {
+                    let field_name = field.to_camel_case()?;
+                    params.push(Pattern::var(format!("{field_name}_")));
+                    record.push((field_name.clone(), Expr::var(format!("{field_name}_"))));
+                }
+
+                let value = Expr::call(&type_name, Vec::from([Expr::record(record)]));
+
+                // there's no constructor function for an anonymous record, so we have to build
+                // one up with a lambda.
+                let constructor = if fields.is_empty() {
+                    value
+                } else {
+                    Expr::lambda(params, value)
+                };
+
+                Expr::call("Json.Decode.succeed", Vec::from([constructor]))
+                    .pipeline(Type::record_pipeline(fields, &type_name)?)
+            }
+        };
+
+        Ok(syntax::Declaration::Value {
+            docs: None,
+            name: self.decoder_name()?,
+            annotation: syntax::Type::apply(
+                "Json.Decode.Decoder",
+                Vec::from([syntax::Type::named(&type_name)]),
+            ),
+            params: Vec::new(),
+            body,
+        })
+    }
+
+    fn to_encoder(&self) -> Result<syntax::Declaration> {
+        let type_name = self.name().to_pascal_case()?;
+
+        // We have an underscore after this because we commonly generate a port with the same name
+        // and that needs to take precedence.
+        let variable_name = format!("{}_", self.name().to_camel_case()?);
+
+        let param = if self.is_opaque() {
+            Pattern::constructor(&type_name, Vec::from([Pattern::var(&variable_name)]))
+        } else {
+            Pattern::var(&variable_name)
+        };
+
+        let body = match &self {
+            Decl::CustomTypeEnum {
+                cases,
+                discriminator,
+                open,
+                ..
+            } => {
+                let mut branches = Vec::with_capacity(cases.len() + 1);
+                for (case, case_type_opt) in cases {
+                    let constructor = self.case_constructor_name(case)?;
+
+                    branches.push(match case_type_opt {
+                        Some(case_type) => {
+                            let case_var =
+                                InflectedString::from(constructor.clone()).to_camel_case()?;
+                            (
+                                Pattern::constructor(
+                                    constructor,
+                                    Vec::from([Pattern::var(&case_var)]),
+                                ),
+                                case_type.to_encoder(&case_var, &None)?,
+                            )
+                        }
+                        None => (
+                            Pattern::constructor(constructor, Vec::new()),
+                            Expr::call(
+                                "Json.Encode.string",
+                                Vec::from([Expr::string(case.orig())]),
+                            ),
+                        ),
+                    });
+                }
+
+                if *open {
+                    // we send unknown values back out exactly as we got them
+                    let unknown_constructor = self.unknown_constructor_name()?;
+                    branches.push(if discriminator.is_some() {
+                        (
+                            Pattern::constructor(
+                                unknown_constructor,
+                                Vec::from([Pattern::Anything, Pattern::var("raw")]),
+                            ),
+                            Expr::var("raw"),
+                        )
+                    } else {
+                        (
+                            Pattern::constructor(
+                                unknown_constructor,
+                                Vec::from([Pattern::var("unknown")]),
+                            ),
+                            Expr::call("Json.Encode.string", Vec::from([Expr::var("unknown")])),
+                        )
+                    });
+                }
+
+                Expr::case(Expr::var(&variable_name), branches)
+            }
+            Decl::TypeAlias {
+                type_,
+                discriminator,
+                ..
+            } => type_.to_encoder(&variable_name, discriminator)?,
+            Decl::OpaqueRecord {
+                fields,
+                discriminator,
+                ..
+            } => Type::Record(fields.clone()).to_encoder(&variable_name, discriminator)?,
+        };
+
+        Ok(syntax::Declaration::Value {
+            docs: None,
+            name: self.encoder_name()?,
+            annotation: syntax::Type::function(Vec::from([
+                syntax::Type::named(type_name),
+                syntax::Type::named("Json.Encode.Value"),
+            ])),
+            params: Vec::from([param]),
+            body,
+        })
+    }
+
+    fn add_discriminator(&mut self, name: String, value: String) -> Result<()> {
+        match self {
+            Decl::CustomTypeEnum { .. } => bail!("cannot add a discriminator to a custom type"),
+            Decl::TypeAlias { discriminator, .. } | Decl::OpaqueRecord { discriminator, .. } => {
+                *discriminator = Some((name, value));
+                Ok(())
+            }
+        }
     }
 }
 
@@ -854,6 +1539,7 @@ pub struct Port {
     name: String,
     direction: PortDirection,
     type_: Decl,
+    deprecated: Option<String>,
 }
 
 #[derive(Debug)]
@@ -863,84 +1549,100 @@ pub enum PortDirection {
 }
 
 impl Port {
-    pub fn new(name: String, direction: PortDirection, type_: Decl) -> Self {
+    pub fn new(
+        name: String,
+        direction: PortDirection,
+        type_: Decl,
+        deprecated: Option<String>,
+    ) -> Self {
         Self {
             name,
             direction,
             type_,
+            deprecated,
         }
     }
 
-    fn to_source(&self) -> Result<String> {
-        let mut out = String::from("port ");
-        out.push_str(&self.name);
-        out.push_str(" : ");
-
-        match self.direction {
-            PortDirection::Send => out.push_str("Json.Decode.Value -> Cmd msg\n\n\n"),
-            PortDirection::Subscribe => out.push_str("(Json.Decode.Value -> msg) -> Sub msg\n\n\n"),
-        }
-
-        let type_ref = self.type_.name();
-
-        let type_safe_name = {
-            let mut out = String::new();
-
+    fn type_safe_name(&self) -> Result<String> {
+        Ok(format!(
+            "{}{}",
             match self.direction {
-                PortDirection::Send => out.push_str("send"),
-                PortDirection::Subscribe => out.push_str("subscribeTo"),
-            }
-            out.push_str(&InflectedString::from(self.name.clone()).to_pascal_case()?);
-
-            out
-        };
-
-        out.push_str(&type_safe_name);
-        out.push_str(" : ");
-
-        match self.direction {
-            PortDirection::Send => {
-                out.push_str(&type_ref.to_pascal_case()?);
-                out.push_str(" -> Cmd msg\n")
-            }
-            PortDirection::Subscribe => {
-                out.push_str("(Result Json.Decode.Error ");
-
-                let type_name = type_ref.to_pascal_case()?;
-                if type_name.contains(' ') {
-                    out.push('(');
-                    out.push_str(&type_name);
-                    out.push(')');
-                } else {
-                    out.push_str(&type_name);
-                }
+                PortDirection::Send => "send",
+                PortDirection::Subscribe => "subscribeTo",
+            },
+            InflectedString::from(self.name.clone()).to_pascal_case()?
+        ))
+    }
 
-                out.push_str(" -> msg) -> Sub msg\n")
-            }
-        }
+    fn exposing(&self) -> Result<Vec<String>> {
+        Ok(Vec::from([self.name.clone(), self.type_safe_name()?]))
+    }
 
-        out.push_str(&type_safe_name);
-        out.push(' ');
+    fn to_declarations(&self) -> Result<Vec<syntax::Declaration>> {
+        let type_name = syntax::Type::named(self.type_.name().to_pascal_case()?);
+        let msg = || syntax::Type::named("msg");
 
         // I don't normally like to write things point-free, but in this case it lets us
         // avoid assigning names that may cause a collisions with things from schemas
-        match self.direction {
-            PortDirection::Send => {
-                out.push_str(" =\n    ");
-                out.push_str(&self.type_.encoder_name()?);
-                out.push_str(" >> ");
-                out.push_str(&self.name);
-            }
-            PortDirection::Subscribe => {
-                out.push_str("toMsg =\n    ");
-                out.push_str(&self.name);
-                out.push_str(" (Json.Decode.decodeValue ");
-                out.push_str(&self.type_.decoder_name()?);
-                out.push_str(" >> toMsg)");
-            }
-        }
+        let (port_annotation, annotation, params, body) = match self.direction {
+            PortDirection::Send => (
+                syntax::Type::function(Vec::from([
+                    syntax::Type::named("Json.Decode.Value"),
+                    syntax::Type::apply("Cmd", Vec::from([msg()])),
+                ])),
+                syntax::Type::function(Vec::from([
+                    type_name,
+                    syntax::Type::apply("Cmd", Vec::from([msg()])),
+                ])),
+                Vec::new(),
+                Expr::var(self.type_.encoder_name()?).binop(">>", Expr::var(&self.name)),
+            ),
+            PortDirection::Subscribe => (
+                syntax::Type::function(Vec::from([
+                    syntax::Type::function(Vec::from([
+                        syntax::Type::named("Json.Decode.Value"),
+                        msg(),
+                    ])),
+                    syntax::Type::apply("Sub", Vec::from([msg()])),
+                ])),
+                syntax::Type::function(Vec::from([
+                    syntax::Type::function(Vec::from([
+                        syntax::Type::apply(
+                            "Result",
+                            Vec::from([syntax::Type::named("Json.Decode.Error"), type_name]),
+                        ),
+                        msg(),
+                    ])),
+                    syntax::Type::apply("Sub", Vec::from([msg()])),
+                ])),
+                Vec::from([Pattern::var("toMsg")]),
+                Expr::call(
+                    &self.name,
+                    Vec::from([Expr::call(
+                        "Json.Decode.decodeValue",
+                        Vec::from([Expr::var(self.type_.decoder_name()?)]),
+                    )
+                    .binop(">>", Expr::var("toMsg"))]),
+                ),
+            ),
+        };
 
-        Ok(out)
+        Ok(Vec::from([
+            syntax::Declaration::Port {
+                name: self.name.clone(),
+                annotation: port_annotation,
+            },
+            syntax::Declaration::Value {
+                docs: self
+                    .deprecated
+                    .as_ref()
+                    .map(|reason| deprecation_note("**Deprecated**", reason)),
+                name: self.type_safe_name()?,
+                annotation,
+                params,
+                body,
+            },
+        ]))
     }
 }
 
@@ -949,6 +1651,7 @@ pub struct Module {
     pub name: Vec<String>,
     decls: Vec<Decl>,
     ports: Vec<Port>,
+    imports: Vec<String>,
 }
 
 impl Module {
@@ -957,7 +1660,69 @@ impl Module {
             name,
             decls: Vec::new(),
             ports: Vec::new(),
+            imports: Vec::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.decls.is_empty() && self.ports.is_empty()
+    }
+
+    /// Move declarations that are defined in more than one of `others` (or that this module
+    /// already defines) into this module, and import this module where we took them from. That
+    /// way, splitting things across modules doesn't give us several copies of the same type.
+    pub fn hoist_shared_decls(&mut self, others: &mut [Module]) -> Result<()> {
+        let mut counts: BTreeMap<InflectedString, (Decl, usize)> = BTreeMap::new();
+        for decl in &self.decls {
+            // anything we already define is shared by definition, so give it a head start.
+            counts.insert(decl.name().clone(), (decl.clone(), 2));
+        }
+        for other in others.iter() {
+            for decl in &other.decls {
+                match counts.entry(decl.name().clone()) {
+                    std::collections::btree_map::Entry::Vacant(entry) => {
+                        entry.insert((decl.clone(), 1));
+                    }
+                    std::collections::btree_map::Entry::Occupied(mut entry) => {
+                        let (existing, count) = entry.get_mut();
+                        if existing != decl {
+                            bail!(
+                                "two different types are both called `{}` in the {} port modules, so we can't share them. Give one of them a different `metadata.name`.",
+                                decl.name().to_pascal_case()?,
+                                self.name.join("."),
+                            )
+                        }
+                        *count += 1;
+                    }
+                }
+            }
+        }
+
+        let own_name = self.name.join(".");
+
+        for other in others.iter_mut() {
+            let (shared, unique): (Vec<Decl>, Vec<Decl>) = std::mem::take(&mut other.decls)
+                .into_iter()
+                .partition(|decl| counts.get(decl.name()).is_some_and(|(_, count)| *count > 1));
+
+            other.decls = unique;
+
+            if shared.is_empty() {
+                continue;
+            }
+
+            for decl in shared {
+                if !self.decls.contains(&decl) {
+                    self.decls.push(decl)
+                }
+            }
+
+            if !other.imports.contains(&own_name) {
+                other.imports.push(own_name.clone())
+            }
         }
+
+        Ok(())
     }
 
     pub fn insert_from_schema(
@@ -965,10 +1730,19 @@ impl Module {
         schema: Schema,
         name_suggestion: Option<String>,
         globals: &BTreeMap<String, Schema>,
+        record_style: RecordStyle,
     ) -> Result<Decl> {
-        let (type_, decls) = Type::from_schema(schema, name_suggestion.clone(), globals, None)?;
+        let docs = Docs::from_metadata(schema.metadata());
 
-        self.decls.extend(decls);
+        let (type_, decls) =
+            Type::from_schema(schema, name_suggestion.clone(), globals, record_style, None)?;
+
+        for decl in decls {
+            // several ports may refer to the same definition, but we only need it once.
+            if !self.decls.contains(&decl) {
+                self.decls.push(decl)
+            }
+        }
 
         match &type_ {
             Type::Ref(name) => {
@@ -987,6 +1761,7 @@ impl Module {
                         ?.into(),
                     discriminator: None,
                     type_: otherwise.clone(),
+                    docs,
                 };
                 self.decls.push(top_decl.clone());
 
@@ -995,46 +1770,295 @@ impl Module {
         }
     }
 
+    /// Add `type alias {name} = {target}`, for when something needs a particular name (like
+    /// `Flags`) but is just a reference to a definition.
+    pub fn insert_alias(&mut self, name: &str, target: &Decl) -> Decl {
+        let alias = Decl::TypeAlias {
+            name: name.into(),
+            discriminator: None,
+            type_: Type::Ref(target.name().clone()),
+            docs: Docs::default(),
+        };
+        self.decls.push(alias.clone());
+        alias
+    }
+
     pub fn insert_port(&mut self, port: Port) {
         self.ports.push(port)
     }
 
     pub fn to_source(&self) -> Result<String> {
-        if self.decls.is_empty() {
+        if self.is_empty() {
             eyre::bail!(
                 "Module {} didn't contain any definitions",
                 self.name.join(".")
             )
         }
 
-        let mut out = String::new();
+        // exposing everything is simplest, but it would also expose the constructors of opaque
+        // types. If we have any of those, we need to be specific.
+        let exposing = if self.decls.iter().any(|decl| decl.is_opaque()) {
+            let mut exposing = Vec::new();
+            for decl in &self.decls {
+                exposing.extend(decl.exposing()?);
+            }
+            for port in &self.ports {
+                exposing.extend(port.exposing()?);
+            }
+            Some(exposing)
+        } else {
+            None
+        };
 
-        if !self.ports.is_empty() {
-            out.push_str("port ");
+        let mut imports = Vec::from([
+            String::from("Dict exposing (Dict)"),
+            String::from("Json.Decode"),
+            String::from("Json.Decode.Pipeline"),
+            String::from("Json.Encode"),
+        ]);
+        imports.extend(
+            self.imports
+                .iter()
+                .map(|import| format!("{import} exposing (..)")),
+        );
+
+        let mut declarations = Vec::new();
+        for decl in &self.decls {
+            declarations.push(decl.to_declaration()?);
+            declarations.push(decl.to_decoder()?);
+            declarations.push(decl.to_encoder()?);
+            declarations.extend(decl.to_helpers()?);
         }
+        for port in &self.ports {
+            declarations.extend(port.to_declarations()?);
+        }
+
+        Ok(syntax::Module {
+            name: self.name.join("."),
+            is_port_module: !self.ports.is_empty(),
+            exposing,
+            docs: String::from(GENERATED_WARNING),
+            imports,
+            declarations,
+        }
+        .to_source())
+    }
 
-        out.push_str("module ");
-        out.push_str(&self.name.join("."));
-        out.push_str(" exposing (..)\n\n{-| Warning: this file is automatically generated. Don't edit by hand!\n-}\n\nimport Dict exposing (Dict)\nimport Json.Decode\nimport Json.Decode.Pipeline\nimport Json.Encode\n");
+    /// The name of the module holding the fuzzers for this module's types.
+    pub fn fuzzers_name(&self) -> Vec<String> {
+        let mut name = self.name.clone();
+        name.push("Fuzzers".to_string());
+        name
+    }
+
+    /// A module with a fuzzer for every type in this module, for use with
+    /// elm-explorations/test. If this module doesn't define any types, there's nothing to fuzz
+    /// and we return `None`.
+    pub fn to_fuzzers_source(&self) -> Result<Option<String>> {
+        if self.decls.is_empty() {
+            return Ok(None);
+        }
 
+        let mut imports = Vec::from([
+            String::from("Dict"),
+            String::from("Fuzz exposing (Fuzzer)"),
+            String::from("Json.Encode"),
+            format!("{} exposing (..)", self.name.join(".")),
+        ]);
+        imports.extend(
+            self.imports
+                .iter()
+                .map(|import| format!("{import}.Fuzzers exposing (..)")),
+        );
+
+        let mut declarations = Vec::with_capacity(self.decls.len());
         for decl in &self.decls {
-            out.push_str("\n\n");
-            out.push_str(&decl.to_source()?);
-            out.push_str("\n\n\n");
-            out.push_str(&decl.to_decoder_source()?);
-            out.push_str("\n\n\n");
-            out.push_str(&decl.to_encoder_source()?);
-            out.push('\n');
+            declarations.push(decl.to_fuzzer()?);
         }
 
-        for port in &self.ports {
-            out.push_str("\n\n");
-            out.push_str(&port.to_source()?);
-            out.push('\n');
+        Ok(Some(
+            syntax::Module {
+                name: self.fuzzers_name().join("."),
+                is_port_module: false,
+                exposing: None,
+                docs: String::from(GENERATED_WARNING),
+                imports,
+                declarations,
+            }
+            .to_source(),
+        ))
+    }
+}
+
+/// A test module that checks that the decoder for every declaration in `modules` can read what the
+/// encoder writes, using the fuzzers from `Module::to_fuzzers_source`.
+pub fn round_trip_tests_source(modules: &[Module]) -> Result<String> {
+    // Elm crashes if you compare JSON values with `==`, so we have to know which declarations
+    // hold one, even if it's a few refs away.
+    let mut holds_raw_json: BTreeSet<InflectedString> = modules
+        .iter()
+        .flat_map(|module| &module.decls)
+        .filter(|decl| decl.holds_raw_json())
+        .map(|decl| decl.name().clone())
+        .collect();
+    loop {
+        let before = holds_raw_json.len();
+        for decl in modules.iter().flat_map(|module| &module.decls) {
+            if decl.refs().iter().any(|ref_| holds_raw_json.contains(ref_)) {
+                holds_raw_json.insert(decl.name().clone());
+            }
         }
+        if holds_raw_json.len() == before {
+            break;
+        }
+    }
 
-        Ok(out)
+    let mut imports = Vec::from([
+        String::from("Expect"),
+        String::from("Json.Decode"),
+        String::from("Json.Encode"),
+        String::from("Test exposing (Test, describe, fuzz)"),
+    ]);
+    for module in modules {
+        if !module.decls.is_empty() {
+            imports.push(module.name.join("."));
+            imports.push(module.fuzzers_name().join("."));
+        }
+    }
+
+    let mut module_tests = Vec::new();
+    for module in modules {
+        if module.decls.is_empty() {
+            continue;
+        }
+
+        let module_name = module.name.join(".");
+        let fuzzers_name = module.fuzzers_name().join(".");
+
+        let mut tests = Vec::with_capacity(module.decls.len());
+        for decl in &module.decls {
+            let encoder = format!("{module_name}.{}", decl.encoder_name()?);
+            let decoder = format!("{module_name}.{}", decl.decoder_name()?);
+
+            let mut steps = Vec::from([Expr::call(
+                "Json.Decode.decodeValue",
+                Vec::from([Expr::var(decoder)]),
+            )]);
+
+            if holds_raw_json.contains(decl.name()) {
+                steps.push(Expr::call(
+                    "Result.map",
+                    Vec::from([Expr::var(&encoder).binop(
+                        ">>",
+                        Expr::call("Json.Encode.encode", Vec::from([Expr::Int(0)])),
+                    )]),
+                ));
+                steps.push(Expr::call(
+                    "Expect.equal",
+                    Vec::from([Expr::call(
+                        "Ok",
+                        Vec::from([Expr::call(
+                            "Json.Encode.encode",
+                            Vec::from([
+                                Expr::Int(0),
+                                Expr::call(&encoder, Vec::from([Expr::var("value")])),
+                            ]),
+                        )]),
+                    )]),
+                ));
+            } else {
+                steps.push(Expr::call(
+                    "Expect.equal",
+                    Vec::from([Expr::call("Ok", Vec::from([Expr::var("value")]))]),
+                ));
+            }
+
+            let test = Expr::call(
+                "fuzz",
+                Vec::from([
+                    Expr::var(format!("{fuzzers_name}.{}", decl.fuzzer_name()?)),
+                    Expr::string(decl.name().to_pascal_case()?),
+                ]),
+            )
+            .binop(
+                "<|",
+                Expr::lambda(
+                    Vec::from([Pattern::var("value")]),
+                    Expr::call(&encoder, Vec::from([Expr::var("value")])).pipeline(steps),
+                ),
+            );
+
+            tests.push(test);
+
+            // enums also have string helpers, which had better undo each other too
+            if let Decl::CustomTypeEnum {
+                discriminator: None,
+                open,
+                ..
+            } = decl
+            {
+                let expected = if *open {
+                    Expr::var("value")
+                } else {
+                    Expr::call("Just", Vec::from([Expr::var("value")]))
+                };
+
+                tests.push(
+                    Expr::call(
+                        "fuzz",
+                        Vec::from([
+                            Expr::var(format!("{fuzzers_name}.{}", decl.fuzzer_name()?)),
+                            Expr::string(format!("{} strings", decl.name().to_pascal_case()?)),
+                        ]),
+                    )
+                    .binop(
+                        "<|",
+                        Expr::lambda(
+                            Vec::from([Pattern::var("value")]),
+                            Expr::call(
+                                format!("{module_name}.{}", decl.enum_to_string_name()?),
+                                Vec::from([Expr::var("value")]),
+                            )
+                            .pipeline(Vec::from([
+                                Expr::var(format!(
+                                    "{module_name}.{}",
+                                    decl.enum_from_string_name()?
+                                )),
+                                Expr::call("Expect.equal", Vec::from([expected])),
+                            ])),
+                        ),
+                    ),
+                );
+            }
+        }
+
+        module_tests.push(Expr::call(
+            "describe",
+            Vec::from([Expr::string(module_name), Expr::multiline_list(tests)]),
+        ));
     }
+
+    Ok(syntax::Module {
+        name: String::from("Generated.RoundTripTest"),
+        is_port_module: false,
+        exposing: Some(Vec::from([String::from("suite")])),
+        docs: String::from(GENERATED_WARNING),
+        imports,
+        declarations: Vec::from([syntax::Declaration::Value {
+            docs: None,
+            name: String::from("suite"),
+            annotation: syntax::Type::named("Test"),
+            params: Vec::new(),
+            body: Expr::call(
+                "describe",
+                Vec::from([
+                    Expr::string("round trips"),
+                    Expr::multiline_list(module_tests),
+                ]),
+            ),
+        }]),
+    }
+    .to_source())
 }
 
 #[cfg(test)]
@@ -1052,50 +2076,80 @@ mod tests {
         }
 
         fn from_schema(value: Value) -> (Type, Vec<Decl>) {
-            Type::from_schema(from_json(value), None, &BTreeMap::new(), None)
-                .expect("valid schema from JSON value")
+            Type::from_schema(
+                from_json(value),
+                None,
+                &BTreeMap::new(),
+                RecordStyle::Alias,
+                None,
+            )
+            .expect("valid schema from JSON value")
         }
 
         #[test]
         fn interprets_int8() {
             let (type_, _) = from_schema(json!({"type": "int8"}));
 
-            assert_eq!(type_, Type::Int);
+            assert_eq!(
+                type_,
+                Type::Int {
+                    min: -128,
+                    max: 127
+                }
+            );
         }
 
         #[test]
         fn interprets_int16() {
             let (type_, _) = from_schema(json!({"type": "int16"}));
 
-            assert_eq!(type_, Type::Int);
+            assert_eq!(
+                type_,
+                Type::Int {
+                    min: -32768,
+                    max: 32767
+                }
+            );
         }
 
         #[test]
         fn interprets_int32() {
             let (type_, _) = from_schema(json!({"type": "int32"}));
 
-            assert_eq!(type_, Type::Int);
+            assert_eq!(
+                type_,
+                Type::Int {
+                    min: -2147483648,
+                    max: 2147483647
+                }
+            );
         }
 
         #[test]
         fn interprets_uint8() {
             let (type_, _) = from_schema(json!({"type": "uint8"}));
 
-            assert_eq!(type_, Type::Int);
+            assert_eq!(type_, Type::Int { min: 0, max: 255 });
         }
 
         #[test]
         fn interprets_uint16() {
             let (type_, _) = from_schema(json!({"type": "uint16"}));
 
-            assert_eq!(type_, Type::Int);
+            assert_eq!(type_, Type::Int { min: 0, max: 65535 });
         }
 
         #[test]
         fn interprets_uint32() {
             let (type_, _) = from_schema(json!({"type": "uint32"}));
 
-            assert_eq!(type_, Type::Int);
+            assert_eq!(
+                type_,
+                Type::Int {
+                    min: 0,
+                    max: 4294967295
+                }
+            );
         }
 
         #[test]
@@ -1207,87 +2261,348 @@ mod tests {
                 })),
                 None,
                 &BTreeMap::new(),
+                RecordStyle::Alias,
+                None,
+            )
+            .unwrap_err();
+
+            assert_eq!(
+                err.to_string(),
+                "string names are required for enums".to_string()
+            );
+        }
+
+        #[test]
+        fn interprets_enum() {
+            let (type_, decls) = from_schema(json!({
+                "metadata": {
+                    "name": "Foo",
+                },
+                "enum": ["a", "b"],
+            }));
+
+            assert_eq!(type_, Type::Ref("Foo".into()));
+
+            assert_eq!(
+                decls,
+                Vec::from([Decl::CustomTypeEnum {
+                    name: "Foo".into(),
+                    discriminator: None,
+                    docs: Docs::default(),
+                    constructor_prefix: "".into(),
+                    constructor_names: BTreeMap::new(),
+                    open: false,
+                    cases: BTreeMap::from([("a".into(), None), ("b".into(), None)]),
+                }])
+            );
+        }
+
+        #[test]
+        fn interprets_properties_no_name() {
+            let err = Type::from_schema(
+                from_json(json!({
+                    "properties": {
+                        "a": {
+                            "type": "string",
+                        },
+                        "b": {
+                            "type": "int32",
+                        },
+                    },
+                })),
+                None,
+                &BTreeMap::new(),
+                RecordStyle::Alias,
                 None,
             )
             .unwrap_err();
 
             assert_eq!(
-                err.to_string(),
-                "string names are required for enums".to_string()
+                err.to_string(),
+                "string names are required for properties".to_string()
+            );
+        }
+
+        #[test]
+        fn interprets_properties() {
+            let (type_, decls) = from_schema(json!({
+                "metadata": {
+                    "name": "Foo",
+                },
+                "properties": {
+                    "a": {},
+                    "b": {},
+                },
+            }));
+
+            assert_eq!(type_, Type::Ref("Foo".into()));
+
+            assert_eq!(
+                decls,
+                Vec::from([Decl::TypeAlias {
+                    name: "Foo".into(),
+                    discriminator: None,
+                    docs: Docs::default(),
+                    type_: Type::Record(BTreeMap::from([
+                        ("a".into(), (Type::Unit, RecordPresence::Required)),
+                        ("b".into(), (Type::Unit, RecordPresence::Required)),
+                    ])),
+                }])
+            );
+        }
+
+        #[test]
+        fn interprets_opaque_properties() {
+            let (type_, decls) = from_schema(json!({
+                "metadata": {
+                    "name": "Foo",
+                    "elm": { "record": "opaque" },
+                },
+                "properties": {
+                    "a": {},
+                },
+            }));
+
+            assert_eq!(type_, Type::Ref("Foo".into()));
+
+            assert_eq!(
+                decls,
+                Vec::from([Decl::OpaqueRecord {
+                    name: "Foo".into(),
+                    discriminator: None,
+                    fields: BTreeMap::from([("a".into(), (Type::Unit, RecordPresence::Required))]),
+                    docs: Docs::default(),
+                }])
+            );
+        }
+
+        #[test]
+        fn interprets_opaque_properties_by_default() {
+            let (_, decls) = Type::from_schema(
+                from_json(json!({"properties": {}})),
+                Some("Foo".into()),
+                &BTreeMap::new(),
+                RecordStyle::Opaque,
+                None,
+            )
+            .unwrap();
+
+            assert!(decls[0].is_opaque());
+        }
+
+        #[test]
+        fn interprets_alias_properties_overriding_default() {
+            let (_, decls) = Type::from_schema(
+                from_json(json!({
+                    "metadata": { "elm": { "record": "alias" } },
+                    "properties": {},
+                })),
+                Some("Foo".into()),
+                &BTreeMap::new(),
+                RecordStyle::Opaque,
+                None,
+            )
+            .unwrap();
+
+            assert!(!decls[0].is_opaque());
+        }
+
+        #[test]
+        fn interprets_invalid_record_style() {
+            let err = Type::from_schema(
+                from_json(json!({
+                    "metadata": { "name": "Foo", "elm": { "record": "class" } },
+                    "properties": {},
+                })),
+                None,
+                &BTreeMap::new(),
+                RecordStyle::Alias,
+                None,
+            )
+            .unwrap_err();
+
+            assert_eq!(
+                err.root_cause().to_string(),
+                "`metadata.elm.record` should be either \"alias\" or \"opaque\", but I got Some(\"class\")"
+            );
+        }
+
+        #[test]
+        fn interprets_enum_value_metadata() {
+            let (_, decls) = from_schema(json!({
+                "metadata": {
+                    "name": "Interval",
+                    "values": {
+                        "1h": {"name": "OneHour", "description": "every hour"},
+                        "60m": {"name": "SixtyMinutes", "deprecated": "use 1h"},
+                    },
+                },
+                "enum": ["1h", "60m", "day"],
+            }));
+
+            assert_eq!(
+                decls,
+                Vec::from([Decl::CustomTypeEnum {
+                    name: "Interval".into(),
+                    discriminator: None,
+                    constructor_prefix: "".into(),
+                    cases: BTreeMap::from([
+                        ("1h".into(), None),
+                        ("60m".into(), None),
+                        ("day".into(), None),
+                    ]),
+                    constructor_names: BTreeMap::from([
+                        ("1h".into(), "OneHour".to_string()),
+                        ("60m".into(), "SixtyMinutes".to_string()),
+                    ]),
+                    open: false,
+                    docs: Docs {
+                        deprecated: None,
+                        member_descriptions: BTreeMap::from([(
+                            "OneHour".to_string(),
+                            "every hour".to_string()
+                        )]),
+                        deprecated_members: BTreeMap::from([(
+                            "SixtyMinutes".to_string(),
+                            "use 1h".to_string()
+                        )]),
+                    },
+                }])
+            );
+        }
+
+        #[test]
+        fn interprets_enum_value_metadata_for_missing_value() {
+            let err = Type::from_schema(
+                from_json(json!({
+                    "metadata": { "name": "Foo", "values": { "c": {} } },
+                    "enum": ["a", "b"],
+                })),
+                None,
+                &BTreeMap::new(),
+                RecordStyle::Alias,
+                None,
+            )
+            .unwrap_err();
+
+            assert_eq!(
+                err.root_cause().to_string(),
+                "`metadata.values` has `c`, but that's not one of the values of `Foo`"
+            );
+        }
+
+        #[test]
+        fn interprets_open_enum_with_unknown_case() {
+            let err = Type::from_schema(
+                from_json(json!({
+                    "metadata": { "name": "Foo", "open": true },
+                    "enum": ["known", "unknown"],
+                })),
+                None,
+                &BTreeMap::new(),
+                RecordStyle::Alias,
+                None,
+            )
+            .unwrap_err();
+
+            assert_eq!(
+                err.root_cause().to_string(),
+                "`Foo` is open, but already has a case named `unknown`, which would conflict with the case for unknown values"
+            );
+        }
+
+        fn enum_with_value_names(names: Value) -> Result<(Type, Vec<Decl>)> {
+            let values: serde_json::Map<String, Value> = names
+                .as_object()
+                .unwrap()
+                .iter()
+                .map(|(value, name)| (value.clone(), json!({ "name": name })))
+                .collect();
+
+            Type::from_schema(
+                from_json(json!({
+                    "metadata": { "name": "Foo", "values": values },
+                    "enum": ["a", "b", "c"],
+                })),
+                None,
+                &BTreeMap::new(),
+                RecordStyle::Alias,
+                None,
+            )
+        }
+
+        #[test]
+        fn interprets_enum_value_names_like_generated_ones() {
+            let (_, decls) = enum_with_value_names(json!({"a": "lower case"})).unwrap();
+
+            assert_eq!(
+                decls[0].case_constructor_name(&"a".into()).unwrap(),
+                "LowerCase"
+            );
+        }
+
+        #[test]
+        fn interprets_enum_value_names_that_clash() {
+            let err = enum_with_value_names(json!({"a": "lower case", "b": "Same", "c": "Same"}))
+                .unwrap_err();
+
+            assert_eq!(
+                err.root_cause().to_string(),
+                "the `b` and `c` cases of `Foo` would both have a constructor named `Same`. Give one of them a different `name` in `metadata.values`"
+            );
+        }
+
+        #[test]
+        fn interprets_enum_value_names_that_clash_with_generated_ones() {
+            let err = enum_with_value_names(json!({"a": "FooB"})).unwrap_err();
+
+            assert_eq!(
+                err.root_cause().to_string(),
+                "the `a` and `b` cases of `Foo` would both have a constructor named `FooB`. Give one of them a different `name` in `metadata.values`"
             );
         }
 
         #[test]
-        fn interprets_enum() {
-            let (type_, decls) = from_schema(json!({
-                "metadata": {
-                    "name": "Foo",
-                },
-                "enum": ["a", "b"],
-            }));
-
-            assert_eq!(type_, Type::Ref("Foo".into()));
+        fn interprets_enum_value_names_that_clash_with_the_type() {
+            let err = enum_with_value_names(json!({"a": "foo"})).unwrap_err();
 
             assert_eq!(
-                decls,
-                Vec::from([Decl::CustomTypeEnum {
-                    name: "Foo".into(),
-                    discriminator: None,
-                    constructor_prefix: "".into(),
-                    cases: BTreeMap::from([("a".into(), None), ("b".into(), None)]),
-                }])
+                err.root_cause().to_string(),
+                "the `a` case of `Foo` would have a constructor named `Foo`, which is the name of the type itself. Give it a different `name` in `metadata.values`"
             );
         }
 
         #[test]
-        fn interprets_properties_no_name() {
+        fn interprets_enum_value_names_that_clash_with_unknown() {
             let err = Type::from_schema(
                 from_json(json!({
-                    "properties": {
-                        "a": {
-                            "type": "string",
-                        },
-                        "b": {
-                            "type": "int32",
-                        },
+                    "metadata": {
+                        "name": "Foo",
+                        "open": true,
+                        "values": {"a": {"name": "foo unknown"}},
                     },
+                    "enum": ["a"],
                 })),
                 None,
                 &BTreeMap::new(),
+                RecordStyle::Alias,
                 None,
             )
             .unwrap_err();
 
             assert_eq!(
-                err.to_string(),
-                "string names are required for properties".to_string()
+                err.root_cause().to_string(),
+                "`Foo` is open, but already has a case named `a`, which would conflict with the case for unknown values"
             );
         }
 
         #[test]
-        fn interprets_properties() {
-            let (type_, decls) = from_schema(json!({
-                "metadata": {
-                    "name": "Foo",
-                },
-                "properties": {
-                    "a": {},
-                    "b": {},
-                },
-            }));
-
-            assert_eq!(type_, Type::Ref("Foo".into()));
+        fn interprets_enum_value_names_that_are_not_identifiers() {
+            let err = enum_with_value_names(json!({"a": "1h"})).unwrap_err();
 
             assert_eq!(
-                decls,
-                Vec::from([Decl::TypeAlias {
-                    name: "Foo".into(),
-                    discriminator: None,
-                    type_: Type::Record(BTreeMap::from([
-                        ("a".into(), (Type::Unit, RecordPresence::Required)),
-                        ("b".into(), (Type::Unit, RecordPresence::Required)),
-                    ])),
-                }])
+                err.root_cause().to_string(),
+                "identifier `1h` cannot start with a digit"
             );
         }
 
@@ -1309,6 +2624,7 @@ mod tests {
                 Vec::from([Decl::TypeAlias {
                     name: "Foo".into(),
                     discriminator: None,
+                    docs: Docs::default(),
                     type_: Type::Record(BTreeMap::from([(
                         "a".into(),
                         (Type::Maybe(Box::new(Type::Unit)), RecordPresence::Optional)
@@ -1350,6 +2666,7 @@ mod tests {
                     Decl::TypeAlias {
                         name: "a".into(),
                         discriminator: Some(("tag".to_string(), "a".to_string())),
+                        docs: Docs::default(),
                         type_: Type::Record(BTreeMap::from([(
                             "value".into(),
                             (Type::String, RecordPresence::Required)
@@ -1358,6 +2675,7 @@ mod tests {
                     Decl::TypeAlias {
                         name: "b".into(),
                         discriminator: Some(("tag".to_string(), "b".to_string())),
+                        docs: Docs::default(),
                         type_: Type::Record(BTreeMap::from([(
                             "value".into(),
                             (Type::Float, RecordPresence::Required)
@@ -1366,7 +2684,10 @@ mod tests {
                     Decl::CustomTypeEnum {
                         name: "Foo".into(),
                         discriminator: Some("tag".to_string()),
+                        docs: Docs::default(),
                         constructor_prefix: "".into(),
+                        constructor_names: BTreeMap::new(),
+                        open: false,
                         cases: BTreeMap::from([
                             ("a".into(), Some(Type::Ref("a".into()))),
                             ("b".into(), Some(Type::Ref("b".into()))),
@@ -1376,6 +2697,36 @@ mod tests {
             );
         }
 
+        #[test]
+        fn interprets_deprecated_properties() {
+            let (_, decls) = from_schema(json!({
+                "metadata": {
+                    "name": "Foo",
+                    "deprecated": "use Bar instead",
+                },
+                "properties": {
+                    "a": {
+                        "metadata": {
+                            "deprecated": "use b instead",
+                        },
+                    },
+                    "b": {},
+                },
+            }));
+
+            assert_eq!(
+                decls[0].docs(),
+                &Docs {
+                    deprecated: Some("use Bar instead".to_string()),
+                    member_descriptions: BTreeMap::new(),
+                    deprecated_members: BTreeMap::from([(
+                        "a".to_string(),
+                        "use b instead".to_string()
+                    )]),
+                }
+            );
+        }
+
         #[test]
         fn interprets_ref_local() {
             let (type_, decls) = from_schema(json!({
@@ -1399,6 +2750,7 @@ mod tests {
                 })),
                 None,
                 &BTreeMap::from([("foo".into(), from_json(json!({"type": "string"})))]),
+                RecordStyle::Alias,
                 None,
             )
             .unwrap();
@@ -1415,6 +2767,7 @@ mod tests {
                 })),
                 None,
                 &BTreeMap::from([("foo".into(), from_json(json!({"properties": {}})))]),
+                RecordStyle::Alias,
                 None,
             )
             .unwrap();
@@ -1425,10 +2778,47 @@ mod tests {
                 Vec::from([Decl::TypeAlias {
                     name: "foo".into(),
                     discriminator: None,
+                    docs: Docs::default(),
                     type_: Type::Record(BTreeMap::new())
                 }])
             );
         }
+
+        #[test]
+        fn int_fuzzer_respects_range() {
+            let (type_, _) = from_schema(json!({"type": "int8"}));
+
+            assert_eq!(
+                type_.to_fuzzer().unwrap().to_source(),
+                "Fuzz.intRange (-128) 127"
+            );
+        }
+
+        #[test]
+        fn optional_field_fuzzer_includes_nothing() {
+            let (type_, _) = from_schema(json!({"values": {"type": "string", "nullable": true}}));
+
+            assert_eq!(
+                type_.to_fuzzer().unwrap().to_source(),
+                "Fuzz.map Dict.fromList (Fuzz.list (Fuzz.pair Fuzz.string (Fuzz.maybe Fuzz.string)))"
+            );
+        }
+
+        #[test]
+        fn record_fuzzer() {
+            let type_ = Type::Record(BTreeMap::from([
+                ("a".into(), (Type::String, RecordPresence::Required)),
+                (
+                    "b".into(),
+                    (Type::Maybe(Box::new(Type::Bool)), RecordPresence::Optional),
+                ),
+            ]));
+
+            assert_eq!(
+                type_.to_fuzzer().unwrap().to_source(),
+                "Fuzz.constant\n    (\\a_ b_ ->\n        { a = a_\n        , b = b_\n        }\n    )\n    |> Fuzz.andMap Fuzz.string\n    |> Fuzz.andMap (Fuzz.maybe Fuzz.bool)"
+            );
+        }
     }
 
     mod module {
@@ -1444,12 +2834,25 @@ mod tests {
             let mut module = Module::new(Vec::from(["Main".into()]));
 
             module
-                .insert_from_schema(from_json(value), name_suggestion, &BTreeMap::new())
+                .insert_from_schema(
+                    from_json(value),
+                    name_suggestion,
+                    &BTreeMap::new(),
+                    RecordStyle::Alias,
+                )
                 .expect("valid schema from JSON value");
 
             module
         }
 
+        fn helpers_source(decl: &Decl) -> Vec<String> {
+            decl.to_helpers()
+                .unwrap()
+                .iter()
+                .map(|helper| helper.to_source())
+                .collect()
+        }
+
         #[test]
         fn from_schema_ref() {
             let mod_ = from_schema(
@@ -1468,6 +2871,7 @@ mod tests {
                 Vec::from([Decl::TypeAlias {
                     name: "Flags".into(),
                     discriminator: None,
+                    docs: Docs::default(),
                     type_: Type::Record(BTreeMap::from([(
                         "a".into(),
                         (Type::String, RecordPresence::Required)
@@ -1485,17 +2889,347 @@ mod tests {
                 Vec::from([Decl::TypeAlias {
                     name: "Flags".into(),
                     discriminator: None,
+                    docs: Docs::default(),
                     type_: Type::String
                 }])
             );
         }
 
+        #[test]
+        fn deprecated_decl_to_source() {
+            let mod_ = from_schema(
+                json!({
+                    "metadata": {
+                        "deprecated": true,
+                    },
+                    "type": "string",
+                }),
+                Some("Flags".into()),
+            );
+
+            assert!(mod_.decls[0]
+                .to_declaration()
+                .unwrap()
+                .to_source()
+                .starts_with("{-| **Deprecated**.\n-}\ntype alias Flags =\n"));
+        }
+
+        #[test]
+        fn insert_deduplicates_decls() {
+            let mut mod_ = Module::new(Vec::from(["Main".into()]));
+            let schema = json!({"metadata": {"name": "Foo"}, "enum": ["a", "b"]});
+
+            mod_.insert_from_schema(
+                from_json(schema.clone()),
+                None,
+                &BTreeMap::new(),
+                RecordStyle::Alias,
+            )
+            .unwrap();
+            mod_.insert_from_schema(
+                from_json(schema),
+                None,
+                &BTreeMap::new(),
+                RecordStyle::Alias,
+            )
+            .unwrap();
+
+            assert_eq!(mod_.decls.len(), 1);
+        }
+
+        #[test]
+        fn hoist_shared_decls() {
+            let shared = json!({"metadata": {"name": "Shared"}, "enum": ["a", "b"]});
+
+            let mut base = Module::new(Vec::from(["Main".into(), "Ports".into()]));
+            let mut others = [
+                from_schema(shared.clone(), None),
+                from_schema(shared, None),
+                from_schema(json!({"metadata": {"name": "Mine"}, "enum": ["c"]}), None),
+            ];
+
+            base.hoist_shared_decls(&mut others).unwrap();
+
+            assert_eq!(
+                base.decls.iter().map(|d| d.name()).collect::<Vec<_>>(),
+                Vec::from([&InflectedString::from("Shared")])
+            );
+
+            assert!(others[0].decls.is_empty());
+            assert_eq!(others[0].imports, Vec::from(["Main.Ports".to_string()]));
+            assert!(others[1].decls.is_empty());
+            assert_eq!(others[2].decls.len(), 1);
+            assert!(others[2].imports.is_empty());
+        }
+
+        #[test]
+        fn hoist_shared_decls_rejects_different_types_with_the_same_name() {
+            let mut base = Module::new(Vec::from(["Main".into(), "Ports".into()]));
+            let mut others = [
+                from_schema(json!({"metadata": {"name": "Shared"}, "enum": ["a"]}), None),
+                from_schema(json!({"metadata": {"name": "Shared"}, "enum": ["b"]}), None),
+            ];
+
+            assert_eq!(
+                base.hoist_shared_decls(&mut others).unwrap_err().to_string(),
+                "two different types are both called `Shared` in the Main.Ports port modules, so we can't share them. Give one of them a different `metadata.name`."
+            );
+        }
+
+        #[test]
+        fn opaque_module_exposes_everything_but_the_constructor() {
+            let mod_ = from_schema(
+                json!({
+                    "metadata": { "elm": { "record": "opaque" } },
+                    "properties": { "a": { "type": "string" } },
+                }),
+                Some("Flags".into()),
+            );
+
+            assert!(mod_.to_source().unwrap().starts_with(
+                "module Main exposing (Flags, makeFlags, flagsA, flagsDecoder, encodeFlags)\n"
+            ));
+        }
+
+        #[test]
+        fn enum_helpers_to_source() {
+            let mod_ = from_schema(
+                json!({"metadata": {"name": "Foo"}, "enum": ["a", "b"]}),
+                None,
+            );
+
+            assert_eq!(
+                helpers_source(&mod_.decls[0]),
+                Vec::from([
+                    "allFoo : List Foo\nallFoo =\n    [ FooA\n    , FooB\n    ]".to_string(),
+                    "fooToString : Foo -> String\nfooToString foo_ =\n    case foo_ of\n        FooA ->\n            \"a\"\n\n        FooB ->\n            \"b\"".to_string(),
+                    "fooFromString : String -> Maybe Foo\nfooFromString string_ =\n    case string_ of\n        \"a\" ->\n            Just FooA\n\n        \"b\" ->\n            Just FooB\n\n        _ ->\n            Nothing".to_string(),
+                ])
+            );
+        }
+
+        #[test]
+        fn open_enum_to_source() {
+            let mod_ = from_schema(
+                json!({"metadata": {"name": "Foo", "open": true}, "enum": ["a"]}),
+                None,
+            );
+            let decl = &mod_.decls[0];
+
+            assert_eq!(
+                decl.to_declaration().unwrap().to_source(),
+                "type Foo\n    = FooA\n    | FooUnknown String"
+            );
+            assert_eq!(
+                decl.to_decoder().unwrap().to_source(),
+                "fooDecoder : Json.Decode.Decoder Foo\nfooDecoder =\n    Json.Decode.andThen\n        (\\tag ->\n            case tag of\n                \"a\" ->\n                    Json.Decode.succeed FooA\n\n                unknown ->\n                    Json.Decode.succeed (FooUnknown unknown)\n        )\n        Json.Decode.string"
+            );
+            assert_eq!(
+                decl.to_encoder().unwrap().to_source(),
+                "encodeFoo : Foo -> Json.Encode.Value\nencodeFoo foo_ =\n    case foo_ of\n        FooA ->\n            Json.Encode.string \"a\"\n\n        FooUnknown unknown ->\n            Json.Encode.string unknown"
+            );
+            assert_eq!(
+                helpers_source(decl)[1],
+                "fooToString : Foo -> String\nfooToString foo_ =\n    case foo_ of\n        FooA ->\n            \"a\"\n\n        FooUnknown unknown ->\n            unknown"
+            );
+            assert_eq!(
+                helpers_source(decl)[2],
+                "fooFromString : String -> Foo\nfooFromString string_ =\n    case string_ of\n        \"a\" ->\n            FooA\n\n        unknown ->\n            FooUnknown unknown"
+            );
+        }
+
+        #[test]
+        fn enum_value_names_to_source() {
+            let mod_ = from_schema(
+                json!({
+                    "metadata": {
+                        "name": "Interval",
+                        "values": {"1h": {"name": "OneHour", "description": "every hour"}},
+                    },
+                    "enum": ["1h"],
+                }),
+                None,
+            );
+            let decl = &mod_.decls[0];
+
+            assert_eq!(
+                decl.to_declaration().unwrap().to_source(),
+                "{-| `OneHour`: every hour\n-}\ntype Interval\n    = OneHour"
+            );
+            assert!(decl
+                .to_decoder()
+                .unwrap()
+                .to_source()
+                .contains("\"1h\" ->\n                    Json.Decode.succeed OneHour\n"));
+            assert!(decl
+                .to_encoder()
+                .unwrap()
+                .to_source()
+                .contains("OneHour ->\n            Json.Encode.string \"1h\""));
+        }
+
+        #[test]
+        fn open_discriminator_to_source() {
+            let mod_ = from_schema(
+                json!({
+                    "metadata": {"name": "Foo", "open": true},
+                    "discriminator": "tag",
+                    "mapping": {"a": {"properties": {}}},
+                }),
+                None,
+            );
+            let decl = mod_
+                .decls
+                .iter()
+                .find(|decl| decl.name().orig() == "Foo")
+                .unwrap();
+
+            assert_eq!(
+                decl.to_declaration().unwrap().to_source(),
+                "type Foo\n    = FooA A\n    | FooUnknown String Json.Decode.Value"
+            );
+            assert!(decl
+                .to_decoder()
+                .unwrap()
+                .to_source()
+                .contains("unknown ->\n                    Json.Decode.map (FooUnknown unknown) Json.Decode.value\n"));
+            assert!(decl
+                .to_encoder()
+                .unwrap()
+                .to_source()
+                .ends_with("\n\n        FooUnknown _ raw ->\n            raw"));
+        }
+
+        #[test]
+        fn no_enum_helpers_for_discriminators() {
+            let mod_ = from_schema(
+                json!({
+                    "metadata": {"name": "Foo"},
+                    "discriminator": "tag",
+                    "mapping": {"a": {"properties": {}}},
+                }),
+                None,
+            );
+
+            for decl in mod_.decls {
+                assert_eq!(helpers_source(&decl), Vec::<String>::new());
+            }
+        }
+
+        #[test]
+        fn enum_fuzzer_covers_every_case() {
+            let mod_ = from_schema(
+                json!({
+                    "metadata": {
+                        "name": "Foo",
+                        "open": true,
+                        "values": {"unknown": {"name": "FooNotKnown"}},
+                    },
+                    "enum": ["a", "unknown"],
+                }),
+                None,
+            );
+
+            assert_eq!(
+                mod_.decls[0].to_fuzzer().unwrap().to_source(),
+                "fooFuzzer : Fuzzer Foo\nfooFuzzer =\n    Fuzz.oneOfValues\n        [ FooA\n        , FooNotKnown\n        , FooUnknown \"unknown_\"\n        ]"
+            );
+        }
+
+        #[test]
+        fn discriminator_fuzzer_covers_every_case() {
+            let mod_ = from_schema(
+                json!({
+                    "metadata": {"name": "Foo", "open": true},
+                    "discriminator": "tag",
+                    "mapping": {"a": {"properties": {}}},
+                }),
+                None,
+            );
+            let decl = mod_
+                .decls
+                .iter()
+                .find(|decl| decl.name().orig() == "Foo")
+                .unwrap();
+
+            assert_eq!(
+                decl.to_fuzzer().unwrap().to_source(),
+                "fooFuzzer : Fuzzer Foo\nfooFuzzer =\n    Fuzz.oneOf\n        [ Fuzz.map FooA aFuzzer\n        , Fuzz.constant (FooUnknown \"unknown\" (Json.Encode.object [ ( \"tag\", Json.Encode.string \"unknown\" ) ]))\n        ]"
+            );
+        }
+
+        #[test]
+        fn fuzzers_import_fuzzers_of_imported_modules() {
+            let mut mod_ = from_schema(json!({"type": "string"}), Some("Foo".into()));
+            mod_.imports.push("Main.Ports".to_string());
+
+            let source = mod_.to_fuzzers_source().unwrap().unwrap();
+
+            assert!(source.starts_with("module Main.Fuzzers exposing (..)"));
+            assert!(source
+                .contains("import Main exposing (..)\nimport Main.Ports.Fuzzers exposing (..)\n"));
+            assert!(source.ends_with("fooFuzzer : Fuzzer Foo\nfooFuzzer =\n    Fuzz.string\n"));
+        }
+
+        #[test]
+        fn no_fuzzers_without_decls() {
+            let mod_ = Module::new(Vec::from(["Main".to_string()]));
+
+            assert_eq!(mod_.to_fuzzers_source().unwrap(), None);
+        }
+
+        #[test]
+        fn round_trip_tests_compare_raw_json_by_encoding() {
+            let mod_ = from_schema(
+                json!({
+                    "properties": {
+                        "event": {
+                            "metadata": {"name": "Event", "open": true},
+                            "discriminator": "tag",
+                            "mapping": {"a": {"properties": {}}},
+                        },
+                    },
+                }),
+                Some("Flags".into()),
+            );
+
+            let source = round_trip_tests_source(&[mod_]).unwrap();
+
+            assert!(source.contains("fuzz Main.Fuzzers.aFuzzer \"A\" <|\n                \\value ->\n                    Main.encodeA value\n                        |> Json.Decode.decodeValue Main.aDecoder\n                        |> Expect.equal (Ok value)\n"));
+            assert!(source.contains("            , fuzz Main.Fuzzers.flagsFuzzer \"Flags\" <|\n                \\value ->\n                    Main.encodeFlags value\n                        |> Json.Decode.decodeValue Main.flagsDecoder\n                        |> Result.map (Main.encodeFlags >> Json.Encode.encode 0)\n"));
+        }
+
+        #[test]
+        fn round_trip_tests_cover_enum_strings() {
+            let mod_ = from_schema(
+                json!({
+                    "properties": {
+                        "open": {
+                            "metadata": {"name": "Open", "open": true},
+                            "enum": ["a"],
+                        },
+                        "closed": {
+                            "metadata": {"name": "Closed"},
+                            "enum": ["a"],
+                        },
+                    },
+                }),
+                Some("Flags".into()),
+            );
+
+            let source = round_trip_tests_source(&[mod_]).unwrap();
+
+            assert!(source.contains("fuzz Main.Fuzzers.openFuzzer \"Open strings\" <|\n                \\value ->\n                    Main.openToString value\n                        |> Main.openFromString\n                        |> Expect.equal value\n"));
+            assert!(source.contains("fuzz Main.Fuzzers.closedFuzzer \"Closed strings\" <|\n                \\value ->\n                    Main.closedToString value\n                        |> Main.closedFromString\n                        |> Expect.equal (Just value)\n"));
+        }
+
         #[test]
         fn error_on_no_defs_to_source() {
             let m = Module {
                 name: Vec::from(["A".to_string(), "B".to_string()]),
                 decls: Vec::new(),
                 ports: Vec::new(),
+                imports: Vec::new(),
             };
 
             let err = m.to_source().unwrap_err();
@@ -0,0 +1,944 @@
+//! A small Elm syntax tree, covering only what we generate, and a printer that lays it out the
+//! way elm-format would. That way our output doesn't depend on having elm-format installed.
+//!
+//! The printer follows elm-format's model: everything becomes a block of lines, and blocks get
+//! combined by stacking them, indenting them, or putting something in front of them. Indentation
+//! is a tab stop (every four columns) rather than a fixed number of spaces, which is how
+//! elm-format lines things up inside lists and tuples.
+
+/// A type, as it appears in an annotation or declaration.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Type {
+    /// `Int`, `Maybe Int`, `Json.Decode.Decoder Foo`, and so on.
+    Named {
+        name: String,
+        args: Vec<Type>,
+    },
+    Unit,
+    Record(Vec<(String, Type)>),
+
+    /// `a -> b -> c`
+    Function(Vec<Type>),
+}
+
+impl Type {
+    pub fn named(name: impl Into<String>) -> Self {
+        Self::Named {
+            name: name.into(),
+            args: Vec::new(),
+        }
+    }
+
+    pub fn apply(name: impl Into<String>, args: Vec<Type>) -> Self {
+        Self::Named {
+            name: name.into(),
+            args,
+        }
+    }
+
+    pub fn function(parts: Vec<Type>) -> Self {
+        Self::Function(parts)
+    }
+
+    fn layout(&self) -> Block {
+        match self {
+            Self::Named { name, args } => {
+                let args: Vec<Block> = args.iter().map(|arg| arg.layout_arg()).collect();
+                spaced_or_indented(Block::line(name), args)
+            }
+            Self::Unit => Block::line("()"),
+            Self::Record(fields) => structure(
+                "{",
+                "}",
+                fields
+                    .iter()
+                    .map(|(name, type_)| {
+                        let type_ = type_.layout();
+                        if type_.is_line() {
+                            Block::line(format!("{name} : ")).join(type_)
+                        } else {
+                            Block::stack(Vec::from([
+                                Block::line(format!("{name} :")),
+                                type_.indent(),
+                            ]))
+                        }
+                    })
+                    .collect(),
+                true,
+            ),
+            Self::Function(parts) => {
+                let mut blocks = Vec::with_capacity(parts.len());
+                for (i, part) in parts.iter().enumerate() {
+                    // a function in argument position needs parentheses, but not in the result
+                    if i + 1 < parts.len() && matches!(part, Self::Function(_)) {
+                        blocks.push(part.layout().parenthesize());
+                    } else {
+                        blocks.push(part.layout());
+                    }
+                }
+
+                if blocks.iter().all(|block| block.is_line()) {
+                    let mut out = Block::empty();
+                    for (i, block) in blocks.into_iter().enumerate() {
+                        if i > 0 {
+                            out = out.join(Block::line(" -> "));
+                        }
+                        out = out.join(block);
+                    }
+                    out
+                } else {
+                    let mut lines = Vec::with_capacity(blocks.len());
+                    for (i, block) in blocks.into_iter().enumerate() {
+                        if i == 0 {
+                            lines.push(block);
+                        } else {
+                            lines.push(block.prefix("-> "));
+                        }
+                    }
+                    Block::stack(lines)
+                }
+            }
+        }
+    }
+
+    fn layout_arg(&self) -> Block {
+        match self {
+            Self::Named { args, .. } if !args.is_empty() => self.layout().parenthesize(),
+            Self::Function(_) => self.layout().parenthesize(),
+            _ => self.layout(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Pattern {
+    Var(String),
+    Str(String),
+    Constructor {
+        name: String,
+        args: Vec<Pattern>,
+    },
+
+    /// `_`
+    Anything,
+}
+
+impl Pattern {
+    pub fn var(name: impl Into<String>) -> Self {
+        Self::Var(name.into())
+    }
+
+    pub fn constructor(name: impl Into<String>, args: Vec<Pattern>) -> Self {
+        Self::Constructor {
+            name: name.into(),
+            args,
+        }
+    }
+
+    fn to_source(&self) -> String {
+        match self {
+            Self::Var(name) => name.clone(),
+            Self::Str(value) => string_literal(value),
+            Self::Constructor { name, args } => {
+                let mut out = name.clone();
+                for arg in args {
+                    out.push(' ');
+                    out.push_str(&arg.to_arg_source());
+                }
+                out
+            }
+            Self::Anything => String::from("_"),
+        }
+    }
+
+    fn to_arg_source(&self) -> String {
+        match self {
+            Self::Constructor { args, .. } if !args.is_empty() => format!("({})", self.to_source()),
+            _ => self.to_source(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Expr {
+    /// Variables, constructors, and field access, qualified or not (`value`, `Json.Decode.int`,
+    /// `Just`, `flags_.name`.)
+    Var(String),
+    Str(String),
+    Int(i64),
+    Unit,
+    Call {
+        function: Box<Expr>,
+        args: Vec<Expr>,
+    },
+    Lambda {
+        params: Vec<Pattern>,
+        body: Box<Expr>,
+    },
+    Case {
+        subject: Box<Expr>,
+        branches: Vec<(Pattern, Expr)>,
+    },
+    Tuple(Vec<Expr>),
+
+    /// elm-format keeps lists and records on one line if they were written that way, so we get
+    /// to choose. `multiline` puts each item on its own line even if they'd all fit on one.
+    List {
+        items: Vec<Expr>,
+        multiline: bool,
+    },
+    Record {
+        fields: Vec<(String, Expr)>,
+        multiline: bool,
+    },
+
+    /// A chain of binary operators, like `a |> f |> g`. `multiline` puts each operator on its own
+    /// line (which is how we write pipelines.)
+    Binops {
+        first: Box<Expr>,
+        rest: Vec<(String, Expr)>,
+        multiline: bool,
+    },
+}
+
+impl Expr {
+    pub fn var(name: impl Into<String>) -> Self {
+        Self::Var(name.into())
+    }
+
+    pub fn string(value: impl Into<String>) -> Self {
+        Self::Str(value.into())
+    }
+
+    pub fn call(function: impl Into<String>, args: Vec<Expr>) -> Self {
+        Self::Call {
+            function: Box::new(Self::Var(function.into())),
+            args,
+        }
+    }
+
+    pub fn lambda(params: Vec<Pattern>, body: Expr) -> Self {
+        Self::Lambda {
+            params,
+            body: Box::new(body),
+        }
+    }
+
+    pub fn case(subject: Expr, branches: Vec<(Pattern, Expr)>) -> Self {
+        Self::Case {
+            subject: Box::new(subject),
+            branches,
+        }
+    }
+
+    pub fn list(items: Vec<Expr>) -> Self {
+        Self::List {
+            items,
+            multiline: false,
+        }
+    }
+
+    pub fn multiline_list(items: Vec<Expr>) -> Self {
+        Self::List {
+            items,
+            multiline: true,
+        }
+    }
+
+    pub fn record(fields: Vec<(String, Expr)>) -> Self {
+        Self::Record {
+            fields,
+            multiline: true,
+        }
+    }
+
+    /// `self op other`, all on one line.
+    pub fn binop(self, op: &str, other: Expr) -> Self {
+        Self::Binops {
+            first: Box::new(self),
+            rest: Vec::from([(op.to_owned(), other)]),
+            multiline: false,
+        }
+    }
+
+    /// `self |> step1 |> step2 ...`, one step per line. With no steps, this is just `self`.
+    pub fn pipeline(self, steps: Vec<Expr>) -> Self {
+        if steps.is_empty() {
+            return self;
+        }
+
+        Self::Binops {
+            first: Box::new(self),
+            rest: steps
+                .into_iter()
+                .map(|step| (String::from("|>"), step))
+                .collect(),
+            multiline: true,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn to_source(&self) -> String {
+        self.layout().render()
+    }
+
+    fn layout(&self) -> Block {
+        match self {
+            Self::Var(name) => Block::line(name),
+            Self::Str(value) => Block::line(string_literal(value)),
+            Self::Int(value) => Block::line(value.to_string()),
+            Self::Unit => Block::line("()"),
+            Self::Call { function, args } => spaced_or_indented(
+                function.layout(),
+                args.iter().map(|arg| arg.layout_arg()).collect(),
+            ),
+            Self::Lambda { params, body } => {
+                let mut head = String::from("\\");
+                for (i, param) in params.iter().enumerate() {
+                    if i > 0 {
+                        head.push(' ');
+                    }
+                    head.push_str(&param.to_arg_source());
+                }
+                head.push_str(" ->");
+
+                let body = body.layout();
+                if body.is_line() {
+                    Block::line(head + " ").join(body)
+                } else {
+                    Block::stack(Vec::from([Block::line(head), body.indent()]))
+                }
+            }
+            Self::Case { subject, branches } => {
+                let mut arms = Vec::with_capacity(branches.len() * 2);
+                for (i, (pattern, body)) in branches.iter().enumerate() {
+                    if i > 0 {
+                        arms.push(Block::blank());
+                    }
+                    arms.push(Block::line(format!("{} ->", pattern.to_source())));
+                    arms.push(body.layout().indent());
+                }
+
+                Block::stack(Vec::from([
+                    Block::line("case ")
+                        .join(subject.layout())
+                        .join(Block::line(" of")),
+                    Block::stack(arms).indent(),
+                ]))
+            }
+            Self::Tuple(items) => {
+                let items: Vec<Block> = items.iter().map(|item| item.layout()).collect();
+                structure("(", ")", items, false)
+            }
+            Self::List { items, multiline } => structure(
+                "[",
+                "]",
+                items.iter().map(|item| item.layout()).collect(),
+                *multiline,
+            ),
+            Self::Record { fields, multiline } => structure(
+                "{",
+                "}",
+                fields
+                    .iter()
+                    .map(|(name, value)| {
+                        let value = value.layout();
+                        if value.is_line() {
+                            Block::line(format!("{name} = ")).join(value)
+                        } else {
+                            Block::stack(Vec::from([
+                                Block::line(format!("{name} =")),
+                                value.indent(),
+                            ]))
+                        }
+                    })
+                    .collect(),
+                *multiline,
+            ),
+            Self::Binops {
+                first,
+                rest,
+                multiline,
+            } => {
+                let last = rest.len().saturating_sub(1);
+                let first = first.layout_operand(rest.first().is_some_and(|(op, _)| op == "<|"));
+                let rest: Vec<(&str, Block)> = rest
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (op, operand))| {
+                        (op.as_str(), operand.layout_operand(i == last && op == "<|"))
+                    })
+                    .collect();
+
+                if !multiline && first.is_line() && rest.iter().all(|(_, block)| block.is_line()) {
+                    let mut out = first;
+                    for (op, block) in rest {
+                        out = out.join(Block::line(format!(" {op} "))).join(block);
+                    }
+                    return out;
+                }
+
+                // `f <| \x -> ...` keeps the operator on the first line, with the function
+                // underneath.
+                if let [("<|", right)] = rest.as_slice() {
+                    if first.is_line() {
+                        return Block::stack(Vec::from([
+                            first.join(Block::line(" <|")),
+                            right.clone().indent(),
+                        ]));
+                    }
+                }
+
+                let mut lines = Vec::with_capacity(rest.len() + 1);
+                lines.push(first);
+                for (op, block) in rest {
+                    lines.push(block.prefix(&format!("{op} ")).indent());
+                }
+                Block::stack(lines)
+            }
+        }
+    }
+
+    /// Lay out an expression that's an argument to a function, with parentheses if it needs
+    /// them.
+    fn layout_arg(&self) -> Block {
+        match self {
+            Self::Call { args, .. } if args.is_empty() => self.layout(),
+            Self::Int(value) if *value < 0 => self.layout().parenthesize(),
+            Self::Call { .. } | Self::Lambda { .. } | Self::Case { .. } | Self::Binops { .. } => {
+                self.layout().parenthesize()
+            }
+            _ => self.layout(),
+        }
+    }
+
+    /// Lay out one side of a binary operator. Lambdas can go without parentheses at the very end
+    /// of a chain (like after `<|`), since they'd swallow everything after them anyway.
+    fn layout_operand(&self, is_trailing_lambda: bool) -> Block {
+        match self {
+            Self::Lambda { .. } if is_trailing_lambda => self.layout(),
+            Self::Lambda { .. } | Self::Case { .. } | Self::Binops { .. } => {
+                self.layout().parenthesize()
+            }
+            _ => self.layout(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Declaration {
+    TypeAlias {
+        docs: Option<String>,
+        name: String,
+        type_: Type,
+    },
+    CustomType {
+        docs: Option<String>,
+        name: String,
+        constructors: Vec<(String, Vec<Type>)>,
+    },
+    Value {
+        docs: Option<String>,
+        name: String,
+        annotation: Type,
+        params: Vec<Pattern>,
+        body: Expr,
+    },
+    Port {
+        name: String,
+        annotation: Type,
+    },
+}
+
+impl Declaration {
+    pub fn to_source(&self) -> String {
+        self.layout().render()
+    }
+
+    fn layout(&self) -> Block {
+        let (docs, decl) = match self {
+            Self::TypeAlias { docs, name, type_ } => (
+                docs,
+                Block::stack(Vec::from([
+                    Block::line(format!("type alias {name} =")),
+                    type_.layout().indent(),
+                ])),
+            ),
+            Self::CustomType {
+                docs,
+                name,
+                constructors,
+            } => {
+                let mut cases = Vec::with_capacity(constructors.len());
+                for (i, (constructor, args)) in constructors.iter().enumerate() {
+                    let case = spaced_or_indented(
+                        Block::line(constructor),
+                        args.iter().map(|arg| arg.layout_arg()).collect(),
+                    );
+                    cases.push(case.prefix(if i == 0 { "= " } else { "| " }));
+                }
+
+                (
+                    docs,
+                    Block::stack(Vec::from([
+                        Block::line(format!("type {name}")),
+                        Block::stack(cases).indent(),
+                    ])),
+                )
+            }
+            Self::Value {
+                docs,
+                name,
+                annotation,
+                params,
+                body,
+            } => {
+                let annotation = annotation.layout();
+                let annotation = if annotation.is_line() {
+                    Block::line(format!("{name} : ")).join(annotation)
+                } else {
+                    Block::stack(Vec::from([
+                        Block::line(format!("{name} :")),
+                        annotation.indent(),
+                    ]))
+                };
+
+                let mut head = name.clone();
+                for param in params {
+                    head.push(' ');
+                    head.push_str(&param.to_arg_source());
+                }
+                head.push_str(" =");
+
+                (
+                    docs,
+                    Block::stack(Vec::from([
+                        annotation,
+                        Block::line(head),
+                        body.layout().indent(),
+                    ])),
+                )
+            }
+            Self::Port { name, annotation } => (
+                &None,
+                Block::line(format!("port {name} : ")).join(annotation.layout()),
+            ),
+        };
+
+        match docs {
+            Some(docs) => Block::stack(Vec::from([doc_comment(docs), decl])),
+            None => decl,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Module {
+    pub name: String,
+    pub is_port_module: bool,
+
+    /// what the module exposes, or `None` to expose everything.
+    pub exposing: Option<Vec<String>>,
+    pub docs: String,
+
+    /// imports, like `Dict exposing (Dict)`.
+    pub imports: Vec<String>,
+    pub declarations: Vec<Declaration>,
+}
+
+impl Module {
+    pub fn to_source(&self) -> String {
+        let mut out = String::new();
+
+        if self.is_port_module {
+            out.push_str("port ");
+        }
+        out.push_str("module ");
+        out.push_str(&self.name);
+        match &self.exposing {
+            Some(exposing) => {
+                out.push_str(" exposing (");
+                out.push_str(&exposing.join(", "));
+                out.push(')');
+            }
+            None => out.push_str(" exposing (..)"),
+        }
+        out.push_str("\n\n");
+        out.push_str(&doc_comment(&self.docs).render());
+        out.push_str("\n\n");
+
+        let mut imports = self.imports.clone();
+        imports.sort();
+        for import in imports {
+            out.push_str("import ");
+            out.push_str(&import);
+            out.push('\n');
+        }
+
+        for declaration in &self.declarations {
+            out.push_str("\n\n");
+            out.push_str(&declaration.to_source());
+            out.push('\n');
+        }
+
+        out
+    }
+}
+
+fn doc_comment(docs: &str) -> Block {
+    let mut lines: Vec<Block> = docs.split('\n').map(Block::line).collect();
+    lines.push(Block::line("-}"));
+    lines[0] = Block::line("{-| ").join(lines[0].clone());
+
+    Block::stack(lines)
+}
+
+/// Elm string literals escape the same things JSON strings do, except that Elm writes unicode
+/// escapes with braces.
+fn string_literal(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{{{:04X}}}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// `f a b` if everything fits on one line. Otherwise, elm-format keeps the first argument next to
+/// the function (if it fits on one line) and puts the rest underneath.
+fn spaced_or_indented(first: Block, args: Vec<Block>) -> Block {
+    if args.is_empty() {
+        return first;
+    }
+
+    if first.is_line() && args.iter().all(|arg| arg.is_line()) {
+        let mut out = first;
+        for arg in args {
+            out = out.join(Block::line(" ")).join(arg);
+        }
+        return out;
+    }
+
+    let mut args = args.into_iter();
+    let mut lines = Vec::new();
+
+    match args.next() {
+        Some(arg0) if first.is_line() && arg0.is_line() => {
+            lines.push(first.join(Block::line(" ")).join(arg0))
+        }
+        Some(arg0) => {
+            lines.push(first);
+            lines.push(arg0.indent());
+        }
+        None => lines.push(first),
+    }
+
+    lines.extend(args.map(|arg| arg.indent()));
+    Block::stack(lines)
+}
+
+/// Lists, records, and tuples: `[ a, b ]` on one line, or with one item per line and the
+/// separators in front.
+fn structure(open: &str, close: &str, items: Vec<Block>, multiline: bool) -> Block {
+    if items.is_empty() {
+        return Block::line(format!("{open}{close}"));
+    }
+
+    if !multiline && items.iter().all(|item| item.is_line()) {
+        let mut out = Block::line(format!("{open} "));
+        for (i, item) in items.into_iter().enumerate() {
+            if i > 0 {
+                out = out.join(Block::line(", "));
+            }
+            out = out.join(item);
+        }
+        return out.join(Block::line(format!(" {close}")));
+    }
+
+    let mut lines = Vec::with_capacity(items.len() + 1);
+    for (i, item) in items.into_iter().enumerate() {
+        if i == 0 {
+            lines.push(item.prefix(&format!("{open} ")));
+        } else {
+            lines.push(item.prefix(", "));
+        }
+    }
+    lines.push(Block::line(close));
+    Block::stack(lines)
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum Segment {
+    Text(String),
+
+    /// moves to the next multiple of four columns
+    Tab,
+}
+
+/// Some lines of source. Every line but the first starts at the same column as the first line,
+/// wherever that ends up being.
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Block {
+    lines: Vec<Vec<Segment>>,
+}
+
+impl Block {
+    fn empty() -> Self {
+        Self {
+            lines: Vec::from([Vec::new()]),
+        }
+    }
+
+    fn blank() -> Self {
+        Self::empty()
+    }
+
+    fn line(text: impl Into<String>) -> Self {
+        Self {
+            lines: Vec::from([Vec::from([Segment::Text(text.into())])]),
+        }
+    }
+
+    fn stack(blocks: Vec<Block>) -> Self {
+        Self {
+            lines: blocks.into_iter().flat_map(|block| block.lines).collect(),
+        }
+    }
+
+    fn is_line(&self) -> bool {
+        self.lines.len() == 1
+    }
+
+    /// Put another block at the end of this one's last line.
+    fn join(mut self, other: Block) -> Self {
+        let mut other_lines = other.lines.into_iter();
+        if let (Some(last), Some(first)) = (self.lines.last_mut(), other_lines.next()) {
+            last.extend(first);
+        }
+        self.lines.extend(other_lines);
+        self
+    }
+
+    fn indent(mut self) -> Self {
+        for line in &mut self.lines {
+            line.insert(0, Segment::Tab);
+        }
+        self
+    }
+
+    /// Put `prefix` in front of the first line, and line the rest up after it.
+    fn prefix(mut self, prefix: &str) -> Self {
+        let padding = " ".repeat(prefix.chars().count());
+        for (i, line) in self.lines.iter_mut().enumerate() {
+            if i == 0 {
+                line.insert(0, Segment::Text(prefix.to_owned()));
+            } else {
+                line.insert(0, Segment::Text(padding.clone()));
+            }
+        }
+        self
+    }
+
+    fn parenthesize(self) -> Self {
+        if self.is_line() {
+            Block::line("(").join(self).join(Block::line(")"))
+        } else {
+            Block::stack(Vec::from([self.prefix("("), Block::line(")")]))
+        }
+    }
+
+    fn render(&self) -> String {
+        let mut lines = Vec::with_capacity(self.lines.len());
+        for segments in &self.lines {
+            let mut line = String::new();
+            let mut column = 0;
+            for segment in segments {
+                match segment {
+                    Segment::Text(text) => {
+                        line.push_str(text);
+                        column += text.chars().count();
+                    }
+                    Segment::Tab => {
+                        let width = 4 - column % 4;
+                        line.push_str(&" ".repeat(width));
+                        column += width;
+                    }
+                }
+            }
+            lines.push(line.trim_end().to_owned());
+        }
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn call_fits_on_one_line() {
+        assert_eq!(
+            Expr::call(
+                "Json.Decode.field",
+                Vec::from([
+                    Expr::string("tag"),
+                    Expr::call("Json.Decode.nullable", Vec::from([Expr::var("a")])),
+                    Expr::Int(-1),
+                ])
+            )
+            .to_source(),
+            "Json.Decode.field \"tag\" (Json.Decode.nullable a) (-1)"
+        );
+    }
+
+    #[test]
+    fn call_with_multiline_argument() {
+        let lambda = Expr::lambda(
+            Vec::from([Pattern::var("tag")]),
+            Expr::case(
+                Expr::var("tag"),
+                Vec::from([
+                    (Pattern::Str("a".into()), Expr::var("A")),
+                    (Pattern::Anything, Expr::var("B")),
+                ]),
+            ),
+        );
+
+        assert_eq!(
+            Expr::call(
+                "Json.Decode.andThen",
+                Vec::from([lambda, Expr::var("Json.Decode.string")])
+            )
+            .to_source(),
+            "Json.Decode.andThen\n    (\\tag ->\n        case tag of\n            \"a\" ->\n                A\n\n            _ ->\n                B\n    )\n    Json.Decode.string"
+        );
+    }
+
+    #[test]
+    fn first_argument_stays_next_to_function() {
+        assert_eq!(
+            Expr::call(
+                "List.filterMap",
+                Vec::from([
+                    Expr::var("identity"),
+                    Expr::multiline_list(Vec::from([Expr::var("a"), Expr::var("b")]))
+                ])
+            )
+            .to_source(),
+            "List.filterMap identity\n    [ a\n    , b\n    ]"
+        );
+    }
+
+    #[test]
+    fn multiline_tuple_lines_up_with_tab_stops() {
+        let case = Expr::case(
+            Expr::var("x"),
+            Vec::from([(Pattern::var("y"), Expr::var("y"))]),
+        );
+
+        assert_eq!(
+            Expr::multiline_list(Vec::from([Expr::Tuple(Vec::from([
+                Expr::string("a"),
+                case
+            ]))]))
+            .to_source(),
+            "[ ( \"a\"\n  , case x of\n        y ->\n            y\n  )\n]"
+        );
+    }
+
+    #[test]
+    fn pipeline() {
+        assert_eq!(
+            Expr::call("Json.Decode.succeed", Vec::from([Expr::var("Foo")]))
+                .pipeline(Vec::from([Expr::call(
+                    "Json.Decode.Pipeline.required",
+                    Vec::from([Expr::string("a"), Expr::var("Json.Decode.int")])
+                )]))
+                .to_source(),
+            "Json.Decode.succeed Foo\n    |> Json.Decode.Pipeline.required \"a\" Json.Decode.int"
+        );
+    }
+
+    #[test]
+    fn trailing_lambda() {
+        assert_eq!(
+            Expr::call("fuzz", Vec::from([Expr::var("f")]))
+                .binop(
+                    "<|",
+                    Expr::lambda(
+                        Vec::from([Pattern::var("value")]),
+                        Expr::var("value").pipeline(Vec::from([Expr::var("f")]))
+                    )
+                )
+                .to_source(),
+            "fuzz f <|\n    \\value ->\n        value\n            |> f"
+        );
+    }
+
+    #[test]
+    fn escapes_strings() {
+        assert_eq!(
+            Expr::string("say \"hi\"\n").to_source(),
+            "\"say \\\"hi\\\"\\n\""
+        );
+    }
+
+    #[test]
+    fn custom_type_with_multiline_argument() {
+        assert_eq!(
+            Declaration::CustomType {
+                docs: None,
+                name: "Foo".into(),
+                constructors: Vec::from([(
+                    "Foo".into(),
+                    Vec::from([Type::Record(Vec::from([("a".into(), Type::named("Int"))]))])
+                )]),
+            }
+            .to_source(),
+            "type Foo\n    = Foo\n        { a : Int\n        }"
+        );
+    }
+
+    #[test]
+    fn multiline_annotation() {
+        assert_eq!(
+            Declaration::Value {
+                docs: Some("Make one.".into()),
+                name: "makeFoo".into(),
+                annotation: Type::function(Vec::from([
+                    Type::Record(Vec::from([("a".into(), Type::named("Int"))])),
+                    Type::named("Foo"),
+                ])),
+                params: Vec::new(),
+                body: Expr::var("Foo"),
+            }
+            .to_source(),
+            "{-| Make one.\n-}\nmakeFoo :\n    { a : Int\n    }\n    -> Foo\nmakeFoo =\n    Foo"
+        );
+    }
+
+    #[test]
+    fn function_arguments_in_types() {
+        assert_eq!(
+            Type::function(Vec::from([
+                Type::function(Vec::from([
+                    Type::named("Json.Decode.Value"),
+                    Type::named("msg")
+                ])),
+                Type::apply("Sub", Vec::from([Type::named("msg")])),
+            ]))
+            .layout()
+            .render(),
+            "(Json.Decode.Value -> msg) -> Sub msg"
+        );
+    }
+}
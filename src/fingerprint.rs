@@ -0,0 +1,132 @@
+//! Every generated file gets a line under its warning header saying which version of elm-duet
+//! wrote it, from which schema, and a hash of everything else in the file. That lets us notice
+//! when someone edited a generated file, so we can refuse to throw their changes away. (Only the
+//! content hash counts for that, so regenerating with a new version or schema isn't an edit.)
+
+const MARKER: &str = "this file is automatically generated";
+const STAMP_PREFIX: &str = "elm-duet ";
+
+/// Add a fingerprint line right after the warning header. Files without the header (like the
+/// manifest) come back unchanged.
+pub fn stamp(source: &str, schema_hash: &str) -> String {
+    let mut lines: Vec<&str> = source.split_inclusive('\n').collect();
+    let marker = match lines.iter().position(|line| line.contains(MARKER)) {
+        Some(marker) => marker,
+        None => return source.to_string(),
+    };
+
+    // TypeScript headers are line comments, so we need another one. Elm headers are a doc
+    // comment, so we're still inside it on the next line.
+    let prefix = if lines[marker].trim_start().starts_with("//") {
+        "// "
+    } else {
+        ""
+    };
+
+    let stamp = format!(
+        "{prefix}{STAMP_PREFIX}{}, schema {schema_hash}, content {}\n",
+        env!("CARGO_PKG_VERSION"),
+        hash(source.as_bytes()),
+    );
+    lines.insert(marker + 1, &stamp);
+    lines.concat()
+}
+
+/// Has this file been changed since we generated it? Files without a fingerprint (from before we
+/// added them, or not ours at all) never count as modified, since we can't tell.
+pub fn is_modified(source: &str) -> bool {
+    let lines: Vec<&str> = source.split_inclusive('\n').collect();
+    let marker = match lines.iter().position(|line| line.contains(MARKER)) {
+        Some(marker) => marker,
+        None => return false,
+    };
+
+    let expected = match lines.get(marker + 1).and_then(|line| {
+        line.trim()
+            .trim_start_matches("// ")
+            .strip_prefix(STAMP_PREFIX)?
+            .rsplit_once(", content ")
+    }) {
+        Some((_, expected)) => expected,
+        None => return false,
+    };
+
+    let mut rest = lines;
+    rest.remove(marker + 1);
+    hash(rest.concat().as_bytes()) != expected
+}
+
+/// 64-bit FNV-1a, as hex. We only need to notice edits, not resist tampering, and this is stable
+/// across platforms and Rust versions (unlike `DefaultHasher`.)
+pub fn hash(bytes: &[u8]) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{hash:016x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TS: &str =
+        "// Warning: this file is automatically generated. Don't edit by hand!\n\nexport type A = string;\n";
+
+    const ELM: &str = "module Main.Flags exposing (..)\n\n{-| Warning: this file is automatically generated. Don't edit by hand!\n-}\n\n\ntype alias Flags =\n    String\n";
+
+    #[test]
+    fn hash_is_fnv_1a() {
+        assert_eq!(hash(b""), "cbf29ce484222325");
+        assert_eq!(hash(b"a"), "af63dc4c8601ec8c");
+    }
+
+    #[test]
+    fn stamps_typescript_with_a_line_comment() {
+        let stamped = stamp(TS, "0123456789abcdef");
+
+        assert_eq!(
+            stamped,
+            format!(
+                "// Warning: this file is automatically generated. Don't edit by hand!\n// elm-duet {}, schema 0123456789abcdef, content {}\n\nexport type A = string;\n",
+                env!("CARGO_PKG_VERSION"),
+                hash(TS.as_bytes())
+            )
+        );
+        assert!(!is_modified(&stamped));
+    }
+
+    #[test]
+    fn stamps_elm_inside_the_doc_comment() {
+        let stamped = stamp(ELM, "0123456789abcdef");
+
+        assert!(stamped.contains(&format!(
+            "Don't edit by hand!\nelm-duet {}, schema 0123456789abcdef, content {}\n-}}\n",
+            env!("CARGO_PKG_VERSION"),
+            hash(ELM.as_bytes())
+        )));
+        assert!(!is_modified(&stamped));
+    }
+
+    #[test]
+    fn notices_edits() {
+        let stamped = stamp(ELM, "0123456789abcdef");
+        assert!(is_modified(&stamped.replace("String", "Int")));
+    }
+
+    #[test]
+    fn new_versions_and_schemas_are_not_edits() {
+        let stamped = stamp(ELM, "0123456789abcdef")
+            .replace(env!("CARGO_PKG_VERSION"), "0.0.0-old")
+            .replace("0123456789abcdef", "fedcba9876543210");
+        assert!(!is_modified(&stamped));
+    }
+
+    #[test]
+    fn files_without_fingerprints_are_not_modified() {
+        assert!(!is_modified(TS));
+        assert!(!is_modified("{}\n"));
+        assert_eq!(stamp("{}\n", "0123456789abcdef"), "{}\n");
+    }
+}
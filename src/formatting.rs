@@ -1,66 +1,705 @@
 use color_eyre::{Help, SectionExt};
-use eyre::{eyre, Result, WrapErr};
-use std::path::PathBuf;
-use std::process::{Command, Stdio};
+use eyre::{bail, eyre, Result, WrapErr};
+use std::collections::BTreeSet;
+use std::ffi::OsString;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::time::{Duration, Instant};
 
 pub struct Formatter {
     name: String,
     command: PathBuf,
+
+    // may contain `{files}` or `{file}`, which we fill in when we run the command
+    args: Vec<String>,
+}
+
+/// How a formatter gets the files it should format.
+#[derive(Debug, PartialEq, Eq)]
+enum Input {
+    // `{files}`: once, with every file, modifying them in place
+    AllFiles,
+
+    // `{file}`: once per file, modifying it in place
+    EachFile,
+
+    // neither: once per file, reading the source on stdin and writing the result to stdout
+    Stdin,
 }
 
 impl Formatter {
-    pub fn discover(binary_name: &str) -> Result<Option<Self>> {
-        // first look in PATH
-        for source in std::env::var("PATH")?.split(':') {
-            let command = PathBuf::from(source).join(binary_name);
-            if command.exists() {
-                return Ok(Some(Self {
-                    name: binary_name.to_owned(),
-                    command,
-                }));
+    /// Look for `binary_name` (see `find_binary` for where.) We call it with `args` followed by the
+    /// files to format.
+    pub fn discover(binary_name: &str, args: &[&str], near: &Path) -> Result<Option<Self>> {
+        Ok(find_binary(binary_name, near)?.map(|command| Self {
+            name: binary_name.to_owned(),
+            command,
+            args: args
+                .iter()
+                .map(|arg| arg.to_string())
+                .chain([String::from("{files}")])
+                .collect(),
+        }))
+    }
+
+    /// A formatter from a full command, like `biome format --write {files}`. Unlike `discover`,
+    /// the command has to exist, since someone asked for it specifically.
+    pub fn from_template(template: &str, near: &Path) -> Result<Self> {
+        let mut words = split_words(template)
+            .wrap_err_with(|| format!("could not parse formatter command `{template}`"))?
+            .into_iter();
+
+        let name = match words.next() {
+            Some(name) => name,
+            None => bail!("formatter command is empty"),
+        };
+
+        let command = find_binary(&name, near)?
+            .ok_or_else(|| eyre!("could not find `{name}` in node_modules/.bin or PATH"))?;
+
+        Ok(Self {
+            name,
+            command,
+            args: words.collect(),
+        })
+    }
+
+    /// The command's name, version, and where we found it, like `prettier 3.3.3
+    /// (/app/node_modules/.bin/prettier)`.
+    pub fn describe(&self, timeout: Duration) -> String {
+        let version = self
+            .run(&[OsString::from("--version")], None, timeout)
+            .ok()
+            .and_then(|out| {
+                String::from_utf8_lossy(&out)
+                    .lines()
+                    .next()
+                    .map(|line| line.trim().to_string())
+            })
+            .filter(|version| !version.is_empty())
+            .unwrap_or_else(|| String::from("(unknown version)"));
+
+        format!("{} {version} ({})", self.name, self.command.display())
+    }
+
+    /// Format generated sources in memory, without touching the files they'll end up in (so we
+    /// can tell afterwards which ones actually changed.) Formatters that only work on files get
+    /// copies in a scratch directory inside each output's directory, where they'll find the same
+    /// configuration (and ignore files) as the real thing.
+    pub(crate) fn format(
+        &self,
+        sources: &mut [(PathBuf, String)],
+        timeout: Duration,
+    ) -> Result<()> {
+        if sources.is_empty() {
+            return Ok(());
+        }
+
+        if self.input() == Input::Stdin {
+            for (path, source) in sources.iter_mut() {
+                let formatted = self.run(
+                    &self.args_for(&[]),
+                    Some(source.as_bytes().to_vec()),
+                    timeout,
+                )?;
+
+                *source = String::from_utf8(formatted).wrap_err_with(|| {
+                    format!("formatted {} was not valid UTF-8", path.display())
+                })?;
             }
+
+            return Ok(());
         }
 
-        // then search for node_modules up the cwd tree
-        let cwd = std::env::current_dir()?;
-        let mut search = Some(cwd.as_path());
-        while let Some(dir) = search {
-            let command = dir.join("node_modules").join(".bin").join(binary_name);
-            if command.exists() {
-                return Ok(Some(Self {
-                    name: binary_name.to_owned(),
-                    command,
-                }));
+        let mut created = Vec::new();
+        let result = self.format_copies(sources, &mut created, timeout);
+
+        // it's fine if this fails; the worst case is a stray directory
+        for dir in created.iter().rev() {
+            let _ = std::fs::remove_dir_all(dir);
+        }
+
+        result
+    }
+
+    /// Write each source to `.elm-duet-formatting-PID/` in its output's directory, format them
+    /// there, and read them back. Everything we create (including output directories that don't
+    /// exist yet) goes in `created` so the caller can clean it up.
+    fn format_copies(
+        &self,
+        sources: &mut [(PathBuf, String)],
+        created: &mut Vec<PathBuf>,
+        timeout: Duration,
+    ) -> Result<()> {
+        let scratch_name = format!(".elm-duet-formatting-{}", std::process::id());
+
+        // Copies keep their names, so formatters can tell what language they are. (Two outputs
+        // can't have the same path, so they can't collide in the same scratch directory either.)
+        let mut copies = Vec::with_capacity(sources.len());
+        for (path, source) in sources.iter() {
+            let file_name = path
+                .file_name()
+                .ok_or_else(|| eyre!("{} has no file name", path.display()))?;
+
+            let scratch = match path.parent() {
+                Some(parent) if !parent.as_os_str().is_empty() => parent.join(&scratch_name),
+                _ => PathBuf::from(&scratch_name),
+            };
+
+            if let Some(outermost) = scratch
+                .ancestors()
+                .take_while(|dir| !dir.as_os_str().is_empty() && !dir.exists())
+                .last()
+            {
+                created.push(outermost.to_path_buf());
+                std::fs::create_dir_all(&scratch)
+                    .wrap_err_with(|| format!("could not create {}", scratch.display()))?;
+            }
+
+            let copy = scratch.join(file_name);
+            std::fs::write(&copy, source)
+                .wrap_err_with(|| format!("could not write {}", copy.display()))?;
+            copies.push(copy);
+        }
+
+        if self.input() == Input::AllFiles {
+            self.run(&self.args_for(&copies), None, timeout)?;
+        } else {
+            for copy in &copies {
+                self.run(&self.args_for(std::slice::from_ref(copy)), None, timeout)?;
             }
+        }
 
-            search = dir.parent()
+        for ((_, source), copy) in sources.iter_mut().zip(&copies) {
+            *source = std::fs::read_to_string(copy)
+                .wrap_err_with(|| format!("could not read {}", copy.display()))?;
         }
 
-        Ok(None)
+        Ok(())
+    }
+
+    fn input(&self) -> Input {
+        if self.args.iter().any(|arg| arg.contains("{files}")) {
+            Input::AllFiles
+        } else if self.args.iter().any(|arg| arg.contains("{file}")) {
+            Input::EachFile
+        } else {
+            Input::Stdin
+        }
     }
 
-    pub(crate) fn format(&self, args: &[&str], files: &Vec<PathBuf>) -> Result<()> {
-        let process = Command::new(&self.command)
+    /// Fill in `{files}` and `{file}`. A bare `{files}` becomes one argument per file.
+    fn args_for(&self, files: &[PathBuf]) -> Vec<OsString> {
+        let joined = files
+            .iter()
+            .map(|file| file.to_string_lossy())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let mut out = Vec::with_capacity(self.args.len() + files.len());
+        for arg in &self.args {
+            if arg == "{files}" {
+                out.extend(files.iter().map(|file| file.as_os_str().to_owned()));
+            } else {
+                out.push(
+                    arg.replace("{files}", &joined)
+                        .replace("{file}", &joined)
+                        .into(),
+                );
+            }
+        }
+
+        out
+    }
+
+    /// Run the command, giving up after `timeout`. Returns whatever it printed to stdout.
+    fn run(&self, args: &[OsString], stdin: Option<Vec<u8>>, timeout: Duration) -> Result<Vec<u8>> {
+        let display = self.display(args);
+
+        let mut process = Command::new(&self.command)
             .args(args)
-            .args(files)
+            .stdin(if stdin.is_some() {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()
-            .wrap_err_with(|| format!("could not start `{}`", self.name))?;
+            .wrap_err_with(|| format!("could not start `{display}`"))?;
+
+        // Read and write in the background, so a chatty formatter can't fill up a pipe and wait
+        // for us while we wait for it.
+        if let (Some(input), Some(mut pipe)) = (stdin, process.stdin.take()) {
+            std::thread::spawn(move || pipe.write_all(&input));
+        }
+        let stdout = read_in_background(process.stdout.take());
+        let stderr = read_in_background(process.stderr.take());
+
+        let status = match wait_with_timeout(&mut process, timeout)
+            .wrap_err_with(|| format!("could not get output for `{display}`"))?
+        {
+            Some(status) => status,
+            None => {
+                // it's fine if this fails; the process may have just exited on its own
+                let _ = process.kill();
+                let _ = process.wait();
 
-        let out = process
-            .wait_with_output()
-            .wrap_err_with(|| format!("could not get output for `{}`", self.name))?;
+                bail!("`{display}` didn't finish within {timeout:?}, so we stopped it")
+            }
+        };
+
+        let stdout = stdout.join().unwrap_or_default();
+        let stderr = stderr.join().unwrap_or_default();
 
-        if !out.status.success() {
-            let stdout = String::from_utf8_lossy(&out.stdout);
-            let stderr = String::from_utf8_lossy(&out.stderr);
+        if !status.success() {
+            let stdout = String::from_utf8_lossy(&stdout).trim().to_string();
+            let stderr = String::from_utf8_lossy(&stderr).trim().to_string();
 
-            return Err(eyre!("cmd exited with non-zero status code"))
-                .with_section(move || stdout.trim().to_string().header("Stdout:"))
-                .with_section(move || stderr.trim().to_string().header("Stderr:"));
+            return Err(eyre!("`{display}` failed ({status})"))
+                .with_section(move || stdout.header("Stdout:"))
+                .with_section(move || stderr.header("Stderr:"));
         }
 
-        Ok(())
+        Ok(stdout)
+    }
+
+    /// The command as someone would type it, for error messages.
+    fn display(&self, args: &[OsString]) -> String {
+        let mut out = quote(&self.name);
+        for arg in args {
+            out.push(' ');
+            out.push_str(&quote(&arg.to_string_lossy()));
+        }
+        out
+    }
+}
+
+/// Find a formatter, preferring the copy a project pins over whatever happens to be installed
+/// globally: we look near `near` (the files we're formatting) first, then near the working
+/// directory, and only then in PATH.
+fn find_binary(binary_name: &str, near: &Path) -> Result<Option<PathBuf>> {
+    // paths (like `./scripts/format`) don't need searching
+    if binary_name.contains(std::path::MAIN_SEPARATOR) || binary_name.contains('/') {
+        let command = PathBuf::from(binary_name);
+        return Ok(is_executable(&command).then_some(command));
+    }
+
+    let cwd = std::env::current_dir()?;
+    let mut searched = BTreeSet::new();
+    for start in [cwd.join(near), cwd.clone()] {
+        for dir in start.ancestors() {
+            if !searched.insert(dir.to_path_buf()) {
+                continue;
+            }
+
+            if let Some(command) = elm_tooling_binary(dir, binary_name, elm_home().as_deref())? {
+                return Ok(Some(command));
+            }
+
+            if let Some(command) =
+                executable_in(&dir.join("node_modules").join(".bin"), binary_name)
+            {
+                return Ok(Some(command));
+            }
+        }
+    }
+
+    if let Some(path) = std::env::var_os("PATH") {
+        for dir in std::env::split_paths(&path) {
+            if let Some(command) = executable_in(&dir, binary_name) {
+                return Ok(Some(command));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// If `dir` has an `elm-tooling.json` that pins `binary_name`, find the version it asks for in
+/// `elm_home`.
+fn elm_tooling_binary(
+    dir: &Path,
+    binary_name: &str,
+    elm_home: Option<&Path>,
+) -> Result<Option<PathBuf>> {
+    let config_path = dir.join("elm-tooling.json");
+    if !config_path.is_file() {
+        return Ok(None);
+    }
+
+    let config: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(&config_path)
+            .wrap_err_with(|| format!("could not read {}", config_path.display()))?,
+    )
+    .wrap_err_with(|| format!("could not parse {}", config_path.display()))?;
+
+    let version = match config
+        .get("tools")
+        .and_then(|tools| tools.get(binary_name))
+        .and_then(|version| version.as_str())
+    {
+        Some(version) => version,
+        None => return Ok(None),
+    };
+
+    let installed = elm_home.and_then(|home| {
+        executable_in(
+            &home.join("elm-tooling").join(binary_name).join(version),
+            binary_name,
+        )
+    });
+
+    if installed.is_none() {
+        eprintln!(
+            "warning: {} pins {binary_name} {version}, but it isn't installed (try `npx elm-tooling install`)",
+            config_path.display()
+        );
+    }
+
+    Ok(installed)
+}
+
+/// Where elm-tooling keeps the tools it installs.
+fn elm_home() -> Option<PathBuf> {
+    if let Some(home) = std::env::var_os("ELM_HOME") {
+        return Some(PathBuf::from(home));
+    }
+
+    if cfg!(windows) {
+        std::env::var_os("APPDATA").map(|app_data| PathBuf::from(app_data).join("elm"))
+    } else {
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".elm"))
+    }
+}
+
+fn executable_in(dir: &Path, binary_name: &str) -> Option<PathBuf> {
+    let mut candidates = Vec::from([dir.join(binary_name)]);
+    if cfg!(windows) {
+        for extension in ["exe", "cmd", "bat"] {
+            candidates.push(dir.join(format!("{binary_name}.{extension}")));
+        }
+    }
+
+    candidates
+        .into_iter()
+        .find(|candidate| is_executable(candidate))
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    std::fs::metadata(path)
+        .map(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+fn read_in_background<R: Read + Send + 'static>(
+    pipe: Option<R>,
+) -> std::thread::JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut out = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut out);
+        }
+        out
+    })
+}
+
+fn wait_with_timeout(process: &mut Child, timeout: Duration) -> Result<Option<ExitStatus>> {
+    let start = Instant::now();
+
+    loop {
+        if let Some(status) = process.try_wait()? {
+            return Ok(Some(status));
+        }
+
+        if start.elapsed() >= timeout {
+            return Ok(None);
+        }
+
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+/// Split a command into words like a shell would, minus everything except quoting: single quotes
+/// are literal, and double quotes allow `\"` and `\\`. Backslashes outside quotes are left alone,
+/// so Windows paths work.
+fn split_words(command: &str) -> Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut current: Option<String> = None;
+    let mut chars = command.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                let word = current.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => bail!("unterminated single quote"),
+                    }
+                }
+            }
+            '"' => {
+                let word = current.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\')) => word.push(c),
+                            Some(c) => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                            None => bail!("unterminated double quote"),
+                        },
+                        Some(c) => word.push(c),
+                        None => bail!("unterminated double quote"),
+                    }
+                }
+            }
+            c if c.is_whitespace() => {
+                if let Some(word) = current.take() {
+                    words.push(word);
+                }
+            }
+            c => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+
+    if let Some(word) = current {
+        words.push(word);
+    }
+
+    Ok(words)
+}
+
+fn quote(word: &str) -> String {
+    if word.is_empty() || word.contains(char::is_whitespace) || word.contains('\'') {
+        format!("\"{}\"", word.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        word.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::ScratchDir;
+
+    fn formatter(template: &str) -> Formatter {
+        let mut words = split_words(template).unwrap().into_iter();
+        let name = words.next().unwrap();
+
+        Formatter {
+            command: PathBuf::from(&name),
+            name,
+            args: words.collect(),
+        }
+    }
+
+    fn files(names: &[&str]) -> Vec<PathBuf> {
+        names.iter().map(PathBuf::from).collect()
+    }
+
+    #[test]
+    fn split_words_handles_quotes() {
+        assert_eq!(
+            split_words(r#"fmt  --config 'my config.json' "say \"hi\"" C:\bin {files}"#).unwrap(),
+            Vec::from([
+                "fmt",
+                "--config",
+                "my config.json",
+                "say \"hi\"",
+                r"C:\bin",
+                "{files}"
+            ])
+        );
+    }
+
+    #[test]
+    fn split_words_rejects_unterminated_quotes() {
+        assert!(split_words("fmt 'oops").is_err());
+        assert!(split_words("fmt \"oops").is_err());
+    }
+
+    #[test]
+    fn input_depends_on_placeholders() {
+        assert_eq!(
+            formatter("biome format --write {files}").input(),
+            Input::AllFiles
+        );
+        assert_eq!(formatter("fmt --path={file}").input(), Input::EachFile);
+        assert_eq!(formatter("elm-format --stdin").input(), Input::Stdin);
+    }
+
+    #[test]
+    fn bare_files_placeholder_becomes_one_arg_per_file() {
+        assert_eq!(
+            formatter("biome format --write {files}").args_for(&files(&["a.ts", "b.ts"])),
+            Vec::from([
+                OsString::from("format"),
+                OsString::from("--write"),
+                OsString::from("a.ts"),
+                OsString::from("b.ts"),
+            ])
+        );
+    }
+
+    #[test]
+    fn file_placeholder_is_replaced_inside_args() {
+        assert_eq!(
+            formatter("fmt --path={file}").args_for(&files(&["src/Main.elm"])),
+            Vec::from([OsString::from("--path=src/Main.elm")])
+        );
+    }
+
+    #[test]
+    fn display_quotes_args_with_spaces() {
+        let formatter = formatter("fmt");
+        assert_eq!(
+            formatter.display(&[OsString::from("--write"), OsString::from("my file.ts")]),
+            "fmt --write \"my file.ts\""
+        );
+    }
+
+    fn fake_tool(path: &Path) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, "#!/bin/sh\n").unwrap();
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        }
+    }
+
+    #[test]
+    fn find_binary_prefers_the_nearest_project() {
+        let root = ScratchDir::new("nearest");
+        fake_tool(&root.join("node_modules/.bin/fmt-nearest"));
+        fake_tool(&root.join("app/node_modules/.bin/fmt-nearest"));
+
+        assert_eq!(
+            find_binary("fmt-nearest", &root.join("app/src")).unwrap(),
+            Some(root.join("app/node_modules/.bin/fmt-nearest"))
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn find_binary_skips_files_that_are_not_executable() {
+        let root = ScratchDir::new("not-executable");
+        fake_tool(&root.join("node_modules/.bin/fmt-executable"));
+        std::fs::create_dir_all(root.join("app/node_modules/.bin")).unwrap();
+        std::fs::write(root.join("app/node_modules/.bin/fmt-executable"), "").unwrap();
+
+        assert_eq!(
+            find_binary("fmt-executable", &root.join("app")).unwrap(),
+            Some(root.join("node_modules/.bin/fmt-executable"))
+        );
+    }
+
+    #[test]
+    fn elm_tooling_binary_uses_the_pinned_version() {
+        let root = ScratchDir::new("elm-tooling");
+        std::fs::write(
+            root.join("elm-tooling.json"),
+            r#"{"tools": {"elm-format": "0.8.7"}}"#,
+        )
+        .unwrap();
+        fake_tool(&root.join("home/elm-tooling/elm-format/0.8.6/elm-format"));
+
+        // not installed yet
+        assert_eq!(
+            elm_tooling_binary(&root, "elm-format", Some(&root.join("home"))).unwrap(),
+            None
+        );
+
+        fake_tool(&root.join("home/elm-tooling/elm-format/0.8.7/elm-format"));
+        assert_eq!(
+            elm_tooling_binary(&root, "elm-format", Some(&root.join("home"))).unwrap(),
+            Some(root.join("home/elm-tooling/elm-format/0.8.7/elm-format"))
+        );
+
+        // and it only applies to tools it mentions
+        assert_eq!(
+            elm_tooling_binary(&root, "prettier", Some(&root.join("home"))).unwrap(),
+            None
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn format_copies_go_next_to_each_output() {
+        let root = ScratchDir::new("copies");
+        let existing = root.join("existing");
+        std::fs::create_dir_all(&existing).unwrap();
+
+        // replaces each file with the path it was given
+        let formatter = Formatter {
+            name: String::from("sh"),
+            command: PathBuf::from("sh"),
+            args: Vec::from([
+                String::from("-c"),
+                String::from("echo \"$0\" > \"$0\""),
+                String::from("{file}"),
+            ]),
+        };
+
+        let mut sources = Vec::from([
+            (existing.join("elm.ts"), String::new()),
+            (root.join("new/Main/Flags.elm"), String::new()),
+        ]);
+        formatter
+            .format(&mut sources, Duration::from_secs(10))
+            .unwrap();
+
+        let scratch_name = format!(".elm-duet-formatting-{}", std::process::id());
+        assert_eq!(
+            sources[0].1,
+            format!(
+                "{}\n",
+                existing.join(&scratch_name).join("elm.ts").display()
+            )
+        );
+        assert_eq!(
+            sources[1].1,
+            format!(
+                "{}\n",
+                root.join("new/Main")
+                    .join(&scratch_name)
+                    .join("Flags.elm")
+                    .display()
+            )
+        );
+
+        // and we clean up after ourselves, including directories we had to create
+        assert_eq!(std::fs::read_dir(&existing).unwrap().count(), 0);
+        assert!(!root.join("new").exists());
+    }
+
+    #[test]
+    fn discovered_formatters_get_files_last() {
+        let formatter = Formatter {
+            name: String::from("prettier"),
+            command: PathBuf::from("prettier"),
+            args: Vec::from([String::from("-w"), String::from("{files}")]),
+        };
+
+        assert_eq!(formatter.input(), Input::AllFiles);
+        assert_eq!(
+            formatter.display(&formatter.args_for(&files(&["elm.ts"]))),
+            "prettier -w elm.ts"
+        );
     }
 }
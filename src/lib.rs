@@ -0,0 +1,360 @@
+//! Generate Elm and TypeScript types from a single shared definition.
+//!
+//! This is the library behind the `elm-duet` command, for build scripts and other tools that
+//! would rather not shell out. Parse (or build) a [`Schema`], then generate files in memory:
+//!
+//! ```
+//! use elm_duet::{Format, Options, Schema};
+//! use std::path::PathBuf;
+//!
+//! let schema = Schema::parse(
+//!     r#"{"modules": {"Main": {"flags": {"properties": {"name": {"type": "string"}}}}}}"#,
+//!     Format::Json,
+//! )?;
+//!
+//! let files = elm_duet::generate(&schema, &Options::default())?;
+//! assert!(files.contains_key(&PathBuf::from("elm.ts")));
+//! assert!(files.contains_key(&PathBuf::from("src/Main/Flags.elm")));
+//! # Ok::<(), eyre::Report>(())
+//! ```
+//!
+//! Unlike the command, we don't format, write, or fingerprint anything here, so run the files
+//! through `prettier` and `elm-format` yourself if you need them to match exactly.
+
+mod elm;
+mod inflected_string;
+mod metadata;
+mod runtime;
+mod schema;
+mod typescript;
+mod zod;
+
+pub use elm::RecordStyle;
+pub use metadata::Deprecation;
+pub use schema::{Format, Module, Port, PortDirection, PortMeta, Schema};
+pub use typescript::{DeclarationStyle, EnumStyle, ModuleStyle, Style as TypeScriptStyle};
+
+// schemas are made of these, so you'll need it to build one
+pub use jtd;
+
+use eyre::Result;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// What to generate, and where. The defaults match the command's. New options may show up in
+/// later versions, so start from `Options::default()` and change what you need.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Options {
+    /// Where to put TypeScript types, unless `typescript_split_dest` is set.
+    pub typescript_dest: PathBuf,
+
+    /// Write one TypeScript declaration file per Elm module to this directory instead.
+    pub typescript_split_dest: Option<PathBuf>,
+
+    /// Write an `index.d.ts` referencing every split declaration file.
+    pub typescript_index: bool,
+
+    /// How the generated TypeScript looks (module style, declarations, `readonly`, and enums.)
+    pub typescript_style: TypeScriptStyle,
+
+    /// Where to put TypeScript runtime helpers (type guards, enum values), if anywhere.
+    pub typescript_runtime_dest: Option<PathBuf>,
+
+    /// Where to put zod schemas, if anywhere.
+    pub zod_dest: Option<PathBuf>,
+
+    /// The root of the Elm source directory we write modules below.
+    pub elm_dest: PathBuf,
+
+    /// Where to put Elm fuzzers, if anywhere.
+    pub elm_fuzzers_dest: Option<PathBuf>,
+
+    /// Also write round-trip tests next to the fuzzers.
+    pub elm_round_trip_tests: bool,
+
+    /// How to generate Elm types for JTD `properties`, unless a schema sets
+    /// `metadata.elm.record`.
+    pub elm_records: RecordStyle,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            typescript_dest: PathBuf::from("elm.ts"),
+            typescript_split_dest: None,
+            typescript_index: false,
+            typescript_style: TypeScriptStyle::default(),
+            typescript_runtime_dest: None,
+            zod_dest: None,
+            elm_dest: PathBuf::from("src/"),
+            elm_fuzzers_dest: None,
+            elm_round_trip_tests: false,
+            elm_records: RecordStyle::default(),
+        }
+    }
+}
+
+/// Generate every file, keyed by where it should go.
+pub fn generate(schema: &Schema, options: &Options) -> Result<BTreeMap<PathBuf, String>> {
+    let mut out = generate_typescript(schema, options)?;
+    out.extend(generate_elm(schema, options)?);
+    Ok(out)
+}
+
+/// Generate just the TypeScript files (types, runtime helpers, and zod schemas.)
+pub fn generate_typescript(
+    schema: &Schema,
+    options: &Options,
+) -> Result<BTreeMap<PathBuf, String>> {
+    let style = &options.typescript_style;
+
+    let mut out = match &options.typescript_split_dest {
+        Some(split_dest) => below(
+            split_dest,
+            schema.to_ts_split(style, options.typescript_index)?,
+        ),
+        None => BTreeMap::from([(options.typescript_dest.clone(), schema.to_ts(style)?)]),
+    };
+
+    if let Some(runtime_dest) = &options.typescript_runtime_dest {
+        out.extend(below(runtime_dest, schema.to_ts_runtime(style)?));
+    }
+
+    if let Some(zod_dest) = &options.zod_dest {
+        out.insert(zod_dest.clone(), schema.to_zod()?);
+    }
+
+    Ok(out)
+}
+
+/// Generate just the Elm files (modules, plus fuzzers and round-trip tests if asked for.)
+pub fn generate_elm(schema: &Schema, options: &Options) -> Result<BTreeMap<PathBuf, String>> {
+    let mut out = below(&options.elm_dest, schema.to_elm(options.elm_records)?);
+
+    if let Some(fuzzers_dest) = &options.elm_fuzzers_dest {
+        out.extend(below(
+            fuzzers_dest,
+            schema.to_elm_fuzzers(options.elm_records)?,
+        ));
+
+        if options.elm_round_trip_tests {
+            out.extend(below(
+                fuzzers_dest,
+                schema.to_elm_round_trip_tests(options.elm_records)?,
+            ));
+        }
+    }
+
+    Ok(out)
+}
+
+/// Put generated files below `dest`.
+fn below(dest: &Path, files: BTreeMap<PathBuf, String>) -> BTreeMap<PathBuf, String> {
+    files
+        .into_iter()
+        .map(|(name, contents)| (dest.join(name), contents))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flags_only() -> Schema {
+        let flags = jtd::SerdeSchema {
+            properties: Some(BTreeMap::from([(
+                String::from("name"),
+                jtd::SerdeSchema {
+                    type_: Some(String::from("string")),
+                    ..Default::default()
+                },
+            )])),
+            ..Default::default()
+        };
+
+        Schema::new(
+            BTreeMap::new(),
+            BTreeMap::from([(String::from("Main"), Module::new(Some(flags), None))]),
+        )
+    }
+
+    #[test]
+    fn built_schemas_serialize_like_parsed_ones() {
+        let mut schema = flags_only();
+        schema.modules.get_mut("Main").unwrap().ports = Some(BTreeMap::from([(
+            String::from("ping"),
+            Port::new(PortDirection::ElmToJs, jtd::SerdeSchema::default()),
+        )]));
+
+        let yaml = serde_yaml::to_string(&schema).unwrap();
+        assert_eq!(
+            yaml,
+            "modules:\n  Main:\n    flags:\n      properties:\n        name:\n          type: string\n    ports:\n      ping:\n        metadata:\n          direction: ElmToJs\n"
+        );
+
+        let parsed = Schema::parse(&yaml, Format::Yaml).unwrap();
+        assert_eq!(
+            generate(&parsed, &Options::default()).unwrap(),
+            generate(&schema, &Options::default()).unwrap()
+        );
+    }
+
+    #[test]
+    fn generate_uses_destinations_from_options() {
+        let options = Options {
+            typescript_dest: PathBuf::from("assets/elm.d.ts"),
+            zod_dest: Some(PathBuf::from("assets/flags.ts")),
+            elm_dest: PathBuf::from("frontend"),
+            ..Options::default()
+        };
+
+        assert_eq!(
+            generate(&flags_only(), &options)
+                .unwrap()
+                .into_keys()
+                .collect::<Vec<_>>(),
+            Vec::from([
+                PathBuf::from("assets/elm.d.ts"),
+                PathBuf::from("assets/flags.ts"),
+                PathBuf::from("frontend/Main/Flags.elm"),
+            ])
+        );
+    }
+
+    #[test]
+    fn refs_have_the_same_names_in_both_languages() {
+        let schema = Schema::parse(
+            r#"{
+                "definitions": {
+                    "jwt": {"type": "string"},
+                    "session": {"properties": {"token": {"ref": "jwt"}}}
+                },
+                "modules": {
+                    "Main": {
+                        "ports": {
+                            "newJwt": {"metadata": {"direction": "JsToElm"}, "ref": "jwt"},
+                            "login": {"metadata": {"direction": "JsToElm"}, "ref": "session"}
+                        }
+                    }
+                }
+            }"#,
+            Format::Json,
+        )
+        .unwrap();
+
+        let files = generate(&schema, &Options::default()).unwrap();
+        let ts = &files[&PathBuf::from("elm.ts")];
+        let elm = &files[&PathBuf::from("src/Main/Ports.elm")];
+
+        // Elm names refs after whatever refers to them, and TypeScript follows along.
+        for name in ["NewJwt", "Login"] {
+            assert!(ts.contains(&format!("type {name} =")), "{name} in {ts}");
+            assert!(
+                ts.contains(&format!("(value: Elm.{name})")),
+                "{name} in {ts}"
+            );
+            assert!(
+                elm.contains(&format!("type alias {name} =")),
+                "{name} in {elm}"
+            );
+            assert!(
+                elm.contains(&format!("(Result Json.Decode.Error {name} -> msg)")),
+                "{name} in {elm}"
+            );
+        }
+        assert!(!elm.contains("type alias Jwt"), "{elm}");
+        assert!(!ts.contains("Elm.Jwt)"), "{ts}");
+    }
+
+    #[test]
+    fn enum_helpers_have_the_same_name_in_elm_and_the_runtime() {
+        let schema = Schema::parse(
+            r#"{
+                "definitions": {
+                    "notificationPermission": {"enum": ["default", "denied", "granted"]}
+                },
+                "modules": {
+                    "Main": {
+                        "ports": {
+                            "gotPermission": {
+                                "metadata": {"direction": "JsToElm"},
+                                "properties": {"permission": {"ref": "notificationPermission"}}
+                            }
+                        }
+                    }
+                }
+            }"#,
+            Format::Json,
+        )
+        .unwrap();
+
+        let options = Options {
+            typescript_runtime_dest: Some(PathBuf::from("runtime")),
+            ..Options::default()
+        };
+        let files = generate(&schema, &options).unwrap();
+        let elm = &files[&PathBuf::from("src/Main/Ports.elm")];
+        let runtime = &files[&PathBuf::from("runtime/Main.ts")];
+
+        let elm_names: Vec<&str> = elm
+            .lines()
+            .filter_map(|line| line.strip_prefix("all"))
+            .filter_map(|line| line.split_once(" :"))
+            .map(|(name, _)| name)
+            .collect();
+        let runtime_names: Vec<&str> = runtime
+            .lines()
+            .filter_map(|line| line.strip_prefix("export const all"))
+            .filter_map(|line| line.split_once(" ="))
+            .map(|(name, _)| name)
+            .collect();
+
+        assert_eq!(elm_names, Vec::from(["Permission"]));
+        assert_eq!(runtime_names, elm_names);
+    }
+
+    #[test]
+    fn matchers_for_refs_are_named_like_the_types_they_match() {
+        let schema = Schema::parse(
+            r#"{
+                "definitions": {
+                    "event": {
+                        "discriminator": "tag",
+                        "mapping": {"ping": {"properties": {}}}
+                    }
+                },
+                "modules": {
+                    "Main": {
+                        "ports": {
+                            "lastEvent": {"metadata": {"direction": "JsToElm"}, "ref": "event"}
+                        }
+                    }
+                }
+            }"#,
+            Format::Json,
+        )
+        .unwrap();
+
+        let options = Options {
+            typescript_runtime_dest: Some(PathBuf::from("runtime")),
+            ..Options::default()
+        };
+        let files = generate(&schema, &options).unwrap();
+        let ts = &files[&PathBuf::from("elm.ts")];
+        let elm = &files[&PathBuf::from("src/Main/Ports.elm")];
+        let runtime = &files[&PathBuf::from("runtime/Main.ts")];
+
+        assert!(ts.contains("type LastEvent ="), "{ts}");
+        assert!(elm.contains("type LastEvent\n"), "{elm}");
+        assert!(
+            runtime.contains("export function matchLastEvent<R>(\n  value: Elm.LastEvent,\n"),
+            "{runtime}"
+        );
+    }
+
+    #[test]
+    fn parse_reports_bad_json() {
+        assert!(Schema::parse("{", Format::Json).is_err());
+    }
+}
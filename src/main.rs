@@ -1,14 +1,20 @@
-mod elm;
+mod fingerprint;
 mod formatting;
-mod inflected_string;
-mod schema;
-mod typescript;
+mod manifest;
+mod output;
+#[cfg(test)]
+mod test_support;
 
 use crate::formatting::Formatter;
+use crate::manifest::Manifest;
+use crate::output::write_changed;
 use clap::Parser;
 use color_eyre::Result;
-use eyre::WrapErr;
-use std::path::PathBuf;
+use elm_duet::{DeclarationStyle, EnumStyle, Format, ModuleStyle, Options, RecordStyle, Schema};
+use eyre::{bail, WrapErr};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 #[derive(Debug, Parser)]
 #[clap(version, about)]
@@ -20,10 +26,64 @@ struct Cli {
     #[clap(long, default_value = "elm.ts")]
     typescript_dest: PathBuf,
 
+    /// Write one TypeScript declaration file per Elm module (like `Elm.Main.d.ts`) to this
+    /// directory instead of writing everything to `--typescript-dest`.
+    #[clap(long)]
+    typescript_split_dest: Option<PathBuf>,
+
+    /// Write an `index.d.ts` that references every split declaration file.
+    #[clap(long, requires = "typescript_split_dest")]
+    typescript_index: bool,
+
+    /// How should I declare the `Elm` object in TypeScript? Use `es-module` if you import Elm
+    /// modules through a bundler (`import { Elm } from "./Main.elm"`.)
+    #[clap(long, value_enum, default_value_t = ModuleStyle::Global)]
+    typescript_modules: ModuleStyle,
+
+    /// How should I declare object types in TypeScript?
+    #[clap(long, value_enum, default_value_t = DeclarationStyle::Alias)]
+    typescript_declarations: DeclarationStyle,
+
+    /// Mark flags, port values, and named types as `readonly` in TypeScript.
+    #[clap(long)]
+    typescript_readonly: bool,
+
+    /// How should I represent enums in the TypeScript runtime helpers? (The declarations always
+    /// use unions of string literals, which enum members are assignable to. You can override this
+    /// for a single enum with `metadata.typescript.enum`.)
+    #[clap(long, value_enum, default_value_t = EnumStyle::Union, requires = "typescript_runtime_dest")]
+    typescript_enums: EnumStyle,
+
+    /// Destination for TypeScript runtime helpers (like lists of enum values and type guards for
+    /// flags and ports.) We'll write one file per Elm module in this directory. If you don't set
+    /// this, we won't generate them.
+    #[clap(long)]
+    typescript_runtime_dest: Option<PathBuf>,
+
+    /// Destination for zod schemas (with TypeScript types inferred from them.) If you don't set
+    /// this, we won't generate them.
+    #[clap(long)]
+    zod_dest: Option<PathBuf>,
+
     /// Destination for Elm types
     #[clap(long, default_value = "src/")]
     elm_dest: PathBuf,
 
+    /// Destination for Elm fuzzers (for use with elm-explorations/test.) We'll write a `Fuzzers`
+    /// module for each generated module. If you don't set this, we won't generate them.
+    #[clap(long)]
+    elm_fuzzers_dest: Option<PathBuf>,
+
+    /// Generate `Generated/RoundTripTest.elm` next to the fuzzers, to check that every decoder can
+    /// read what its encoder writes.
+    #[clap(long, requires = "elm_fuzzers_dest")]
+    elm_round_trip_tests: bool,
+
+    /// How should I generate Elm types for JTD `properties`? (You can override this for a single
+    /// type with `metadata.elm.record`.)
+    #[clap(long, value_enum, default_value_t = RecordStyle::Alias)]
+    elm_records: RecordStyle,
+
     /// Turn off automatic formatting discovery
     #[clap(long)]
     no_format: bool,
@@ -33,53 +93,216 @@ struct Cli {
     #[clap(long, default_value = "prettier")]
     ts_formatter: String,
 
+    /// A full command to format TypeScript with instead of `--ts-formatter`, like
+    /// `biome format --write {files}`. `{files}` becomes every generated file, `{file}` runs the
+    /// command once per file, and a command with neither gets each file on stdin and should print
+    /// the formatted source.
+    #[clap(long, conflicts_with = "ts_formatter")]
+    ts_format_command: Option<String>,
+
     /// What formatter should I use for Elm? (Assumed to take a `--yes` flag to modify files in
     /// place without confirmation.)
     #[clap(long, default_value = "elm-format")]
     elm_formatter: String,
+
+    /// A full command to format Elm with instead of `--elm-formatter`, like
+    /// `elm-format --stdin`. Placeholders work the same as in `--ts-format-command`.
+    #[clap(long, conflicts_with = "elm_formatter")]
+    elm_format_command: Option<String>,
+
+    /// How many seconds should I give each formatter command before giving up?
+    #[clap(long, default_value_t = 60)]
+    format_timeout: u64,
+
+    /// Remove files we generated on an earlier run but don't any more (like `Main/Ports.elm` after
+    /// removing every port.) Without this, we only list them.
+    #[clap(long)]
+    prune: bool,
+
+    /// Overwrite generated files even if they were edited by hand since we wrote them.
+    #[clap(long)]
+    force: bool,
+
+    /// Tell me more about what you're doing (like which formatters you found.)
+    #[clap(long, short)]
+    verbose: bool,
 }
 
 impl Cli {
     fn run(&self) -> Result<()> {
-        let schema = schema::Schema::from_fs(&self.source).wrap_err("could not read schema")?;
+        // we hash the same bytes we parse, so the manifest can't disagree with what we generated.
+        let format = Format::from_path(&self.source).wrap_err("could not read schema")?;
+        let schema_source = std::fs::read(&self.source)
+            .wrap_err_with(|| format!("could not read {}", self.source.display()))?;
+        let schema = Schema::from_reader(schema_source.as_slice(), format)
+            .wrap_err_with(|| format!("could not read schema from {}", self.source.display()))?;
+
+        let deprecations = schema.deprecations()?;
+        if !deprecations.is_empty() {
+            eprintln!("warning: the schema still contains deprecated items:");
+            for (path, reason) in deprecations {
+                if reason.is_empty() {
+                    eprintln!("  - {path}");
+                } else {
+                    eprintln!("  - {path}: {reason}");
+                }
+            }
+        }
 
         // TODO: better error message in all of this
 
-        std::fs::write(&self.typescript_dest, schema.to_ts()?)?;
-        println!("wrote {}", self.typescript_dest.display());
+        let options = self.options();
+        let mut ts_files: Vec<(PathBuf, String)> =
+            elm_duet::generate_typescript(&schema, &options)?
+                .into_iter()
+                .collect();
+        let mut elm_files: Vec<(PathBuf, String)> = elm_duet::generate_elm(&schema, &options)?
+            .into_iter()
+            .collect();
 
-        let mut elm_files = Vec::new();
-        for (name, contents) in schema.to_elm()? {
-            let dest = self.elm_dest.join(name);
-            if let Some(parent) = dest.parent() {
-                if !parent.exists() {
-                    std::fs::create_dir_all(parent)?
-                };
-            }
+        if !self.no_format {
+            self.format(
+                "TypeScript",
+                &mut ts_files,
+                self.ts_format_command.as_deref(),
+                &self.ts_formatter,
+                &["-w"],
+            )?;
+            self.format(
+                "Elm",
+                &mut elm_files,
+                self.elm_format_command.as_deref(),
+                &self.elm_formatter,
+                &["--yes"],
+            )?;
+        }
+
+        // stamp files after formatting, so the fingerprint matches exactly what we write.
+        let schema_hash = fingerprint::hash(&schema_source);
+        let mut files: Vec<(PathBuf, String)> = ts_files
+            .into_iter()
+            .chain(elm_files)
+            .map(|(path, contents)| {
+                let stamped = fingerprint::stamp(&contents, &schema_hash);
+                (path, stamped)
+            })
+            .collect();
+
+        if !self.force {
+            let modified: Vec<&Path> = files
+                .iter()
+                .filter(|(path, contents)| {
+                    std::fs::read_to_string(path).is_ok_and(|existing| {
+                        &existing != contents && fingerprint::is_modified(&existing)
+                    })
+                })
+                .map(|(path, _)| path.as_path())
+                .collect();
 
-            std::fs::write(&dest, contents)?;
-            println!("wrote {}", dest.display());
-            elm_files.push(dest);
+            if !modified.is_empty() {
+                let list = modified
+                    .iter()
+                    .map(|path| format!("  - {}", path.display()))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                bail!("these generated files were edited by hand, so I stopped instead of overwriting them:\n{list}\n\nMove your changes somewhere else, or use --force to overwrite them anyway.");
+            }
         }
 
-        if !self.no_format {
-            if let Some(ts_formatter) = Formatter::discover(&self.ts_formatter)? {
-                ts_formatter
-                    // this is a silly clone but it doesn't matter much from a performance
-                    // perspective. If it bugs you, feel free to refactor it but know in advance
-                    // it'll just be for ergonomics or cleanliness.
-                    .format(&["-w"], &Vec::from([self.typescript_dest.clone()]))
-                    .wrap_err("could not format TypeScript")?;
-
-                println!("formatted TypeScript")
+        // we keep track of what we generated next to the schema, so we can tell when we've stopped
+        // generating something and clean it up.
+        let cwd = std::env::current_dir().wrap_err("could not get the current directory")?;
+        let manifest_dir = self.source.parent().unwrap_or(Path::new(""));
+        let manifest_path = manifest_dir.join(manifest::FILE_NAME);
+        let schema_name = self
+            .source
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let mut manifest = Manifest::load(&manifest_path)?;
+        let generated: BTreeSet<String> = files
+            .iter()
+            .map(|(path, _)| manifest::manifest_name(&cwd, manifest_dir, path))
+            .collect::<Result<_>>()?;
+        let stale: Vec<PathBuf> = manifest
+            .files(&schema_name)
+            .difference(&generated)
+            .map(|name| manifest::normalize(&manifest_dir.join(name)))
+            .filter(|path| path.exists())
+            .collect();
+
+        // until they're pruned, stale files stay in the manifest so we keep reminding you
+        let mut listed = generated;
+        if !self.prune {
+            for path in stale.iter().filter(|path| manifest::is_generated(path)) {
+                listed.insert(manifest::manifest_name(&cwd, manifest_dir, path)?);
             }
+        }
+        manifest.set_files(&schema_name, &schema_hash, listed);
+        files.push((manifest_path, manifest.to_json()?));
 
-            if let Some(elm_formatter) = Formatter::discover(&self.elm_formatter)? {
-                elm_formatter
-                    .format(&["--yes"], &elm_files)
-                    .wrap_err("could not format Elm")?;
+        write_changed(files)?;
+        manifest::clean_up(&stale, self.prune, manifest_dir)
+    }
+
+    fn options(&self) -> Options {
+        let mut options = Options::default();
+        options.typescript_dest = self.typescript_dest.clone();
+        options.typescript_split_dest = self.typescript_split_dest.clone();
+        options.typescript_index = self.typescript_index;
+        options.typescript_style = elm_duet::TypeScriptStyle::new(
+            self.typescript_modules,
+            self.typescript_declarations,
+            self.typescript_readonly,
+            self.typescript_enums,
+        );
+        options.typescript_runtime_dest = self.typescript_runtime_dest.clone();
+        options.zod_dest = self.zod_dest.clone();
+        options.elm_dest = self.elm_dest.clone();
+        options.elm_fuzzers_dest = self.elm_fuzzers_dest.clone();
+        options.elm_round_trip_tests = self.elm_round_trip_tests;
+        options.elm_records = self.elm_records;
+        options
+    }
 
-                println!("formatted Elm")
+    /// Find a formatter for `files` (the command template if there is one, otherwise the first
+    /// `binary_name` near them) and format their contents.
+    fn format(
+        &self,
+        language: &str,
+        files: &mut [(PathBuf, String)],
+        template: Option<&str>,
+        binary_name: &str,
+        args: &[&str],
+    ) -> Result<()> {
+        let near = match files.first().and_then(|(path, _)| path.parent()) {
+            Some(dir) => dir,
+            None => return Ok(()),
+        };
+
+        let formatter = match template {
+            Some(template) => Some(Formatter::from_template(template, near)?),
+            None => Formatter::discover(binary_name, args, near)?,
+        };
+
+        let timeout = Duration::from_secs(self.format_timeout);
+        match formatter {
+            Some(formatter) => {
+                if self.verbose {
+                    println!("formatting {language} with {}", formatter.describe(timeout));
+                }
+
+                formatter
+                    .format(files, timeout)
+                    .wrap_err_with(|| format!("could not format {language}"))?;
+
+                println!("formatted {language}")
+            }
+            None => {
+                if self.verbose {
+                    println!("not formatting {language}: could not find `{binary_name}`");
+                }
             }
         }
 
@@ -0,0 +1,317 @@
+use crate::fingerprint;
+use eyre::{bail, Result, WrapErr};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Component, Path, PathBuf};
+
+pub const FILE_NAME: &str = ".elm-duet-manifest.json";
+
+/// What we generated from each schema in a directory, so we can notice when we stop generating
+/// something (like `Main/Ports.elm` after removing every port) and clean it up. Paths are relative
+/// to the manifest's directory, with `/` as the separator so the file is the same everywhere.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Manifest {
+    schemas: BTreeMap<String, Entry>,
+}
+
+/// Everything we know about the last run for one schema.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct Entry {
+    // the version of elm-duet that generated the files
+    version: String,
+
+    // a hash of the schema file the files came from
+    schema: String,
+
+    files: BTreeSet<String>,
+}
+
+impl Manifest {
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let source = std::fs::read_to_string(path)
+            .wrap_err_with(|| format!("could not read {}", path.display()))?;
+
+        // this is only a record of what we generated, so rather than making people delete an old
+        // or broken one by hand, we start over (and just can't clean up after the last run.)
+        match serde_json::from_str(&source) {
+            Ok(manifest) => Ok(manifest),
+            Err(err) => {
+                eprintln!(
+                    "warning: could not parse {} ({err}), so we're starting a new one. Files it listed won't be cleaned up this time.",
+                    path.display()
+                );
+                Ok(Self::default())
+            }
+        }
+    }
+
+    pub fn files(&self, schema: &str) -> BTreeSet<String> {
+        self.schemas
+            .get(schema)
+            .map(|entry| entry.files.clone())
+            .unwrap_or_default()
+    }
+
+    /// Remember what we generated from `schema` (whose contents hash to `schema_hash`) this time.
+    pub fn set_files(&mut self, schema: &str, schema_hash: &str, files: BTreeSet<String>) {
+        self.schemas.insert(
+            schema.to_string(),
+            Entry {
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                schema: schema_hash.to_string(),
+                files,
+            },
+        );
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        let mut out = serde_json::to_string_pretty(self)?;
+        out.push('\n');
+        Ok(out)
+    }
+}
+
+/// How we refer to `path` in a manifest living in `manifest_dir`. Both are relative to `cwd` (or
+/// absolute.) Files outside `manifest_dir` get `..`, so the manifest still makes sense after
+/// moving the whole project somewhere else.
+pub fn manifest_name(cwd: &Path, manifest_dir: &Path, path: &Path) -> Result<String> {
+    let manifest_dir = normalize(&cwd.join(manifest_dir));
+    let path = normalize(&cwd.join(path));
+
+    let mut from = manifest_dir.components().peekable();
+    let mut to = path.components().peekable();
+    let mut shared = 0;
+    while let (Some(a), Some(b)) = (from.peek(), to.peek()) {
+        if a != b {
+            break;
+        }
+        from.next();
+        to.next();
+        shared += 1;
+    }
+
+    // on Windows, paths on different drives have nothing in common
+    if shared == 0 {
+        bail!(
+            "could not refer to {} from the manifest in {} without an absolute path. Keep generated files on the same drive as the schema.",
+            path.display(),
+            manifest_dir.display()
+        );
+    }
+
+    Ok(from
+        .map(|_| Cow::Borrowed(".."))
+        .chain(to.map(|component| component.as_os_str().to_string_lossy()))
+        .collect::<Vec<_>>()
+        .join("/"))
+}
+
+/// Resolve `.` and `..` without touching the filesystem. (Leading `..`s in relative paths stay,
+/// since there's nothing to resolve them against.)
+pub fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => (),
+            Component::ParentDir => match out.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    out.pop();
+                }
+                Some(Component::RootDir | Component::Prefix(_)) => (),
+                _ => out.push(Component::ParentDir),
+            },
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+/// Is `path` still exactly what we generated? That is, does it have the warning we put at the
+/// top of every generated file, and still match its fingerprint?
+pub fn is_generated(path: &Path) -> bool {
+    std::fs::read_to_string(path).is_ok_and(|contents| {
+        contents.contains("this file is automatically generated")
+            && !fingerprint::is_modified(&contents)
+    })
+}
+
+/// Delete files we used to generate but don't any more (or just list them, if `prune` is off.)
+/// We leave files alone if they've lost the generated-file warning or been edited since we wrote
+/// them, since that means someone has taken them over by hand. Directories inside `root` that end
+/// up empty go too.
+pub fn clean_up(stale: &[PathBuf], prune: bool, root: &Path) -> Result<()> {
+    for path in stale {
+        if !is_generated(path) {
+            println!(
+                "kept {} (we don't generate it any more, but it looks like it was edited by hand)",
+                path.display()
+            );
+            continue;
+        }
+
+        if !prune {
+            println!(
+                "stale {} (we don't generate it any more; use --prune to remove it)",
+                path.display()
+            );
+            continue;
+        }
+
+        std::fs::remove_file(path)
+            .wrap_err_with(|| format!("could not remove {}", path.display()))?;
+        println!("removed {}", path.display());
+
+        // we only tidy up inside `root`, even for files we generated outside it.
+        let root = normalize(root);
+        let mut dir = path.parent();
+        while let Some(current) = dir {
+            let normalized = normalize(current);
+            if current.as_os_str().is_empty()
+                || normalized == root
+                || !normalized.starts_with(&root)
+                || normalized.starts_with("..")
+            {
+                break;
+            }
+
+            // fails if the directory isn't empty, which is when we want to stop anyway
+            if std::fs::remove_dir(current).is_err() {
+                break;
+            }
+            dir = current.parent();
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::ScratchDir;
+
+    #[test]
+    fn manifest_name_is_relative_to_the_manifest() {
+        let cwd = Path::new("/app");
+
+        assert_eq!(
+            manifest_name(cwd, Path::new(""), Path::new("src/Main/Flags.elm")).unwrap(),
+            "src/Main/Flags.elm"
+        );
+        assert_eq!(
+            manifest_name(
+                cwd,
+                Path::new("schemas"),
+                Path::new("./schemas/../schemas/elm.ts")
+            )
+            .unwrap(),
+            "elm.ts"
+        );
+        assert_eq!(
+            manifest_name(cwd, Path::new("schemas"), Path::new("src/Main/Flags.elm")).unwrap(),
+            "../src/Main/Flags.elm"
+        );
+        assert_eq!(
+            manifest_name(cwd, Path::new("schemas/app"), Path::new("/elm.ts")).unwrap(),
+            "../../../elm.ts"
+        );
+    }
+
+    #[test]
+    fn manifest_names_round_trip_through_normalize() {
+        let cwd = Path::new("/app");
+        let name =
+            manifest_name(cwd, Path::new("schemas"), Path::new("src/Main/Flags.elm")).unwrap();
+
+        assert_eq!(
+            normalize(&Path::new("schemas").join(name)),
+            PathBuf::from("src/Main/Flags.elm")
+        );
+        assert_eq!(normalize(Path::new("../a/./b/..")), PathBuf::from("../a"));
+        assert_eq!(normalize(Path::new("/..")), PathBuf::from("/"));
+    }
+
+    #[test]
+    fn manifest_round_trips() {
+        let mut manifest = Manifest::default();
+        manifest.set_files(
+            "elm-duet.yaml",
+            "0123456789abcdef",
+            BTreeSet::from([String::from("elm.ts"), String::from("src/Main/Flags.elm")]),
+        );
+
+        let json = manifest.to_json().unwrap();
+        assert_eq!(
+            json,
+            format!(
+                "{{\n  \"elm-duet.yaml\": {{\n    \"version\": \"{}\",\n    \"schema\": \"0123456789abcdef\",\n    \"files\": [\n      \"elm.ts\",\n      \"src/Main/Flags.elm\"\n    ]\n  }}\n}}\n",
+                env!("CARGO_PKG_VERSION")
+            )
+        );
+        assert_eq!(serde_json::from_str::<Manifest>(&json).unwrap(), manifest);
+    }
+
+    #[test]
+    fn load_starts_over_when_the_manifest_does_not_parse() {
+        let root = ScratchDir::new("unparseable");
+        let path = root.join(FILE_NAME);
+
+        // the format before we tracked each schema separately
+        std::fs::write(&path, r#"{"files": ["elm.ts"]}"#).unwrap();
+        assert_eq!(Manifest::load(&path).unwrap(), Manifest::default());
+
+        std::fs::write(&path, "{").unwrap();
+        assert_eq!(Manifest::load(&path).unwrap(), Manifest::default());
+    }
+
+    #[test]
+    fn clean_up_only_prunes_generated_files() {
+        let root = ScratchDir::new("clean-up");
+        std::fs::create_dir_all(root.join("src/Main")).unwrap();
+        std::fs::create_dir_all(root.join("src/Other")).unwrap();
+
+        std::fs::write(
+            root.join("src/Main/Ports.elm"),
+            "{-| Warning: this file is automatically generated. Don't edit by hand!",
+        )
+        .unwrap();
+        std::fs::write(root.join("src/Other/Ports.elm"), "module Other.Ports").unwrap();
+
+        let stale = [
+            root.join("src/Main/Ports.elm"),
+            root.join("src/Other/Ports.elm"),
+        ];
+
+        // listing doesn't remove anything
+        clean_up(&stale, false, &root).unwrap();
+        assert!(root.join("src/Main/Ports.elm").exists());
+
+        clean_up(&stale, true, &root).unwrap();
+        assert!(!root.join("src/Main").exists());
+        assert!(root.join("src/Other/Ports.elm").exists());
+    }
+
+    #[test]
+    fn clean_up_keeps_directories_outside_the_root() {
+        let root = ScratchDir::new("outside");
+        std::fs::create_dir_all(root.join("schemas")).unwrap();
+        std::fs::create_dir_all(root.join("src/Main")).unwrap();
+
+        let stale = root.join("src/Main/Ports.elm");
+        std::fs::write(
+            &stale,
+            "{-| Warning: this file is automatically generated. Don't edit by hand!",
+        )
+        .unwrap();
+
+        clean_up(std::slice::from_ref(&stale), true, &root.join("schemas")).unwrap();
+        assert!(!stale.exists());
+        assert!(root.join("src/Main").exists());
+    }
+}
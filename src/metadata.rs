@@ -0,0 +1,123 @@
+use color_eyre::Result;
+use eyre::WrapErr;
+use jtd::Metadata;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// How `metadata.deprecated` is written: either a string (the reason) or `true` (deprecated, but
+/// without saying what to do instead.)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Deprecation {
+    Bool(bool),
+    Reason(String),
+}
+
+impl Deprecation {
+    /// The note to show, if this means the thing is deprecated at all.
+    pub fn reason(&self) -> Option<String> {
+        match self {
+            Deprecation::Bool(true) => Some(String::new()),
+            Deprecation::Bool(false) => None,
+            Deprecation::Reason(reason) => Some(reason.clone()),
+        }
+    }
+}
+
+/// Get the deprecation note from a schema's metadata, if any.
+pub fn deprecation(metadata: &Metadata) -> Option<String> {
+    serde_json::from_value::<Deprecation>(metadata.get("deprecated")?.clone())
+        .ok()?
+        .reason()
+}
+
+/// Should we accept values we don't know about for this enum or discriminator? This lets the JS
+/// side add new values before the Elm side knows about them.
+pub fn is_open(metadata: &Metadata) -> bool {
+    metadata
+        .get("open")
+        .and_then(|open| open.as_bool())
+        .unwrap_or(false)
+}
+
+/// Get the metadata for individual enum values from `metadata.values`. Each value gets a map of its
+/// own (with `name`, `description`, and `deprecated`) so we can read it the same way we read the
+/// metadata on a schema.
+pub fn enum_values(metadata: &Metadata) -> Result<BTreeMap<String, Metadata>> {
+    match metadata.get("values") {
+        Some(values) => serde_json::from_value(values.clone())
+            .wrap_err("`metadata.values` should map each enum value to an object"),
+        None => Ok(BTreeMap::new()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn deprecation_from_string() {
+        let metadata = Metadata::from([("deprecated".to_string(), json!("use b instead"))]);
+
+        assert_eq!(deprecation(&metadata), Some("use b instead".to_string()))
+    }
+
+    #[test]
+    fn deprecation_from_bool() {
+        let metadata = Metadata::from([("deprecated".to_string(), json!(true))]);
+
+        assert_eq!(deprecation(&metadata), Some(String::new()))
+    }
+
+    #[test]
+    fn not_deprecated() {
+        assert_eq!(deprecation(&Metadata::new()), None);
+
+        let metadata = Metadata::from([("deprecated".to_string(), json!(false))]);
+        assert_eq!(deprecation(&metadata), None)
+    }
+
+    #[test]
+    fn deprecation_reads_both_forms() {
+        let reasons: Vec<Option<String>> =
+            serde_json::from_value::<Vec<Deprecation>>(json!([true, false, "use b instead"]))
+                .unwrap()
+                .iter()
+                .map(Deprecation::reason)
+                .collect();
+
+        assert_eq!(
+            reasons,
+            Vec::from([Some(String::new()), None, Some("use b instead".to_string())])
+        )
+    }
+
+    #[test]
+    fn open_from_bool() {
+        let metadata = Metadata::from([("open".to_string(), json!(true))]);
+
+        assert!(is_open(&metadata));
+        assert!(!is_open(&Metadata::new()))
+    }
+
+    #[test]
+    fn enum_values_from_map() {
+        let metadata = Metadata::from([("values".to_string(), json!({"1h": {"name": "OneHour"}}))]);
+
+        assert_eq!(
+            enum_values(&metadata).unwrap(),
+            BTreeMap::from([(
+                "1h".to_string(),
+                Metadata::from([("name".to_string(), json!("OneHour"))])
+            )])
+        )
+    }
+
+    #[test]
+    fn enum_values_not_a_map() {
+        let metadata = Metadata::from([("values".to_string(), json!(["1h"]))]);
+
+        assert!(enum_values(&metadata).is_err())
+    }
+}
@@ -0,0 +1,225 @@
+use eyre::{Result, WrapErr};
+use std::path::{Path, PathBuf};
+
+/// A file we're about to replace, and what was there before (if anything) so we can put it back.
+struct Change {
+    dest: PathBuf,
+    contents: String,
+    previous: Option<Vec<u8>>,
+    temp: PathBuf,
+}
+
+/// Write generated files, creating directories as needed. Files that already have the right
+/// contents are left alone, so watchers don't rebuild everything when nothing changed.
+///
+/// This is all or nothing: we write every file next to its destination first, then rename them
+/// into place. If anything goes wrong, we put back whatever we already replaced, so a failed run
+/// never leaves some files updated and others stale.
+pub fn write_changed(files: impl IntoIterator<Item = (PathBuf, String)>) -> Result<()> {
+    let mut report = Vec::new();
+    let mut changes = Vec::new();
+
+    for (dest, contents) in files {
+        let previous = std::fs::read(&dest).ok();
+        if previous.as_deref() == Some(contents.as_bytes()) {
+            report.push(format!("unchanged {}", dest.display()));
+            continue;
+        }
+
+        report.push(format!("wrote {}", dest.display()));
+        changes.push(Change {
+            temp: temp_path(&dest),
+            dest,
+            contents,
+            previous,
+        });
+    }
+
+    let mut transaction = Transaction::default();
+    if let Err(problem) = transaction.apply(&changes) {
+        transaction.roll_back();
+        return Err(problem);
+    }
+
+    for line in report {
+        println!("{line}");
+    }
+
+    Ok(())
+}
+
+/// Where we stage `dest` before renaming it into place. It's in the same directory so the rename
+/// doesn't cross filesystems, and hidden with a `.tmp` extension so watchers ignore it.
+fn temp_path(dest: &Path) -> PathBuf {
+    let file_name = dest
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    dest.with_file_name(format!(".{file_name}.elm-duet-{}.tmp", std::process::id()))
+}
+
+/// Everything we've done so far, so we can undo it.
+#[derive(Default)]
+struct Transaction<'a> {
+    created_dirs: Vec<PathBuf>,
+    temps: Vec<PathBuf>,
+    renamed: Vec<&'a Change>,
+}
+
+impl<'a> Transaction<'a> {
+    fn apply(&mut self, changes: &'a [Change]) -> Result<()> {
+        for change in changes {
+            if let Some(parent) = change.dest.parent() {
+                self.create_dir_all(parent)?;
+            }
+
+            std::fs::write(&change.temp, &change.contents)
+                .wrap_err_with(|| format!("could not write {}", change.temp.display()))?;
+            self.temps.push(change.temp.clone());
+        }
+
+        for change in changes {
+            std::fs::rename(&change.temp, &change.dest).wrap_err_with(|| {
+                format!(
+                    "could not move {} to {}",
+                    change.temp.display(),
+                    change.dest.display()
+                )
+            })?;
+            self.renamed.push(change);
+        }
+
+        Ok(())
+    }
+
+    /// Like `std::fs::create_dir_all`, but remembering which directories we made.
+    fn create_dir_all(&mut self, dir: &Path) -> Result<()> {
+        let mut missing = Vec::new();
+        for ancestor in dir.ancestors() {
+            if ancestor.as_os_str().is_empty() || ancestor.exists() {
+                break;
+            }
+            missing.push(ancestor);
+        }
+
+        for dir in missing.into_iter().rev() {
+            std::fs::create_dir(dir)
+                .wrap_err_with(|| format!("could not create {}", dir.display()))?;
+            self.created_dirs.push(dir.to_path_buf());
+        }
+
+        Ok(())
+    }
+
+    /// Undo as much as we can. We're already failing at this point, so we don't report problems
+    /// here; the original error is more useful.
+    fn roll_back(self) {
+        for change in self.renamed.iter().rev() {
+            let _ = match &change.previous {
+                Some(previous) => std::fs::write(&change.dest, previous),
+                None => std::fs::remove_file(&change.dest),
+            };
+        }
+
+        for temp in self.temps {
+            let _ = std::fs::remove_file(temp);
+        }
+
+        for dir in self.created_dirs.iter().rev() {
+            let _ = std::fs::remove_dir(dir);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::ScratchDir;
+
+    fn files_in(dir: &Path) -> Vec<PathBuf> {
+        let mut out = Vec::new();
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                out.extend(files_in(&path));
+            }
+            out.push(path);
+        }
+        out.sort();
+        out
+    }
+
+    #[test]
+    fn writes_new_files_and_directories() {
+        let root = ScratchDir::new("output-new");
+
+        write_changed([
+            (root.join("elm.ts"), String::from("ts")),
+            (root.join("src/Main/Flags.elm"), String::from("elm")),
+        ])
+        .unwrap();
+
+        assert_eq!(std::fs::read_to_string(root.join("elm.ts")).unwrap(), "ts");
+        assert_eq!(
+            std::fs::read_to_string(root.join("src/Main/Flags.elm")).unwrap(),
+            "elm"
+        );
+        assert_eq!(
+            files_in(&root),
+            Vec::from([
+                root.join("elm.ts"),
+                root.join("src"),
+                root.join("src/Main"),
+                root.join("src/Main/Flags.elm"),
+            ])
+        );
+    }
+
+    #[test]
+    fn rolls_back_when_staging_fails() {
+        let root = ScratchDir::new("output-staging");
+        std::fs::write(root.join("elm.ts"), "old").unwrap();
+        std::fs::write(root.join("src"), "a file where a directory should be").unwrap();
+
+        assert!(write_changed([
+            (root.join("elm.ts"), String::from("new")),
+            (root.join("new/Thing.elm"), String::from("new")),
+            (root.join("src/Main/Flags.elm"), String::from("new")),
+        ])
+        .is_err());
+
+        assert_eq!(std::fs::read_to_string(root.join("elm.ts")).unwrap(), "old");
+        assert_eq!(
+            files_in(&root),
+            Vec::from([root.join("elm.ts"), root.join("src")])
+        );
+    }
+
+    #[test]
+    fn rolls_back_when_renaming_fails() {
+        let root = ScratchDir::new("output-renaming");
+        std::fs::write(root.join("elm.ts"), "old").unwrap();
+
+        // you can't replace a non-empty directory with a file
+        std::fs::create_dir_all(root.join("src/Main.elm/oops")).unwrap();
+
+        assert!(write_changed([
+            (root.join("elm.ts"), String::from("new")),
+            (root.join("fresh.ts"), String::from("new")),
+            (root.join("src/Main.elm"), String::from("new")),
+        ])
+        .is_err());
+
+        assert_eq!(std::fs::read_to_string(root.join("elm.ts")).unwrap(), "old");
+        assert_eq!(
+            files_in(&root),
+            Vec::from([
+                root.join("elm.ts"),
+                root.join("src"),
+                root.join("src/Main.elm"),
+                root.join("src/Main.elm/oops"),
+            ])
+        );
+    }
+}
@@ -0,0 +1,1518 @@
+//! TypeScript that exists at runtime, as opposed to the declarations in `typescript`. We generate
+//! one of these per Elm module, for people who want helpers on top of the types.
+
+use crate::inflected_string::InflectedString;
+use crate::metadata;
+use crate::typescript::doc::{broken_group, concat, group, indent, join, text, Doc};
+use crate::typescript::{
+    array_doc, assignment_doc, object_key, object_members_doc, parameters_doc, type_arguments_doc,
+    EnumStyle, NamedTypes, TSType,
+};
+use color_eyre::Result;
+use eyre::{bail, WrapErr};
+use jtd::Schema;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Which way a port goes, from the point of view of the JS side.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PortDirection {
+    // JS sends values to Elm.
+    Send,
+
+    // JS subscribes to values from Elm.
+    Subscribe,
+}
+
+/// A `match*` helper for a discriminated union.
+#[derive(Debug)]
+struct Matcher {
+    // the TypeScript type of the union
+    type_: Doc,
+
+    // the property that holds the tag, as it's named in TypeScript
+    discriminator: String,
+    tags: Vec<String>,
+    open: bool,
+}
+
+#[derive(Debug)]
+pub struct Module {
+    pub name: Vec<String>,
+    enums: BTreeMap<String, Vec<String>>,
+
+    // names for enum members from `metadata.values`, keyed by enum name and then value. (Values
+    // without one get a PascalCase version of the value.)
+    enum_member_names: BTreeMap<String, BTreeMap<String, String>>,
+    enum_style: EnumStyle,
+
+    // enums that set their own style with `metadata.typescript.enum`, keyed by enum name.
+    enum_styles: BTreeMap<String, EnumStyle>,
+
+    // type guards, keyed by the name we give them (so `Flags` gets `isFlags` and `assertFlags`)
+    // and pointing to the TypeScript type they narrow to.
+    guards: BTreeMap<String, Doc>,
+
+    // the functions that do the actual checking for the guards (and for any refs they use),
+    // keyed by function name.
+    checks: BTreeMap<String, Doc>,
+
+    // `match*` helpers for discriminated unions, keyed by the name we give them.
+    matchers: BTreeMap<String, Matcher>,
+
+    // ports for the `connect` function, keyed by their name in JS and pointing to the type of
+    // value that goes through them.
+    ports: BTreeMap<String, (PortDirection, Doc)>,
+
+    // where to import the `Elm` types from, if they're not declared globally.
+    elm_import: Option<String>,
+}
+
+impl Module {
+    pub fn new(name: Vec<String>) -> Self {
+        Self {
+            name,
+            enums: BTreeMap::new(),
+            enum_member_names: BTreeMap::new(),
+            enum_style: EnumStyle::Union,
+            enum_styles: BTreeMap::new(),
+            guards: BTreeMap::new(),
+            checks: BTreeMap::new(),
+            matchers: BTreeMap::new(),
+            ports: BTreeMap::new(),
+            elm_import: None,
+        }
+    }
+
+    /// Import the `Elm` types the guards refer to from a module instead of expecting them to be
+    /// declared globally.
+    pub fn import_elm_types_from(&mut self, path: String) {
+        self.elm_import = Some(path);
+    }
+
+    /// Declare enums as real TypeScript enums or `as const` objects in addition to the list of
+    /// values.
+    pub fn represent_enums_as(&mut self, style: EnumStyle) {
+        self.enum_style = style;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.enums.is_empty()
+            && self.guards.is_empty()
+            && self.matchers.is_empty()
+            && self.ports.is_empty()
+    }
+
+    /// Add a port to the `connect` function, which requires a handler for every port Elm sends
+    /// values through and gives back a function for every port Elm subscribes to.
+    pub fn insert_port(
+        &mut self,
+        name: &str,
+        value_type: Doc,
+        direction: PortDirection,
+    ) -> Result<()> {
+        self.ports.insert(
+            InflectedString::from(name).to_camel_case()?,
+            (direction, value_type),
+        );
+
+        Ok(())
+    }
+
+    /// Find all the enums in a schema and remember them so we can generate a list of values. We
+    /// name them the same way the Elm side does, so `allNotificationPermission` refers to the same
+    /// thing in both languages.
+    pub fn insert_enums_from_schema(
+        &mut self,
+        schema: &Schema,
+        name_suggestion: Option<&str>,
+        globals: &BTreeMap<String, Schema>,
+    ) -> Result<()> {
+        match schema {
+            Schema::Empty { .. } | Schema::Type { .. } => (),
+            Schema::Ref {
+                definitions, ref_, ..
+            } => match definitions.get(ref_).or_else(|| globals.get(ref_)) {
+                Some(def) => self
+                    .insert_enums_from_schema(def, name_suggestion.or(Some(ref_)), globals)
+                    .wrap_err_with(|| format!("could not find enums in ref `{ref_}`"))?,
+                None => bail!("could not find a definition for `{ref_}`"),
+            },
+            Schema::Enum {
+                metadata, enum_, ..
+            } => match metadata
+                .get("name")
+                .and_then(|n| n.as_str())
+                .or(name_suggestion)
+            {
+                Some(name) => {
+                    let name = InflectedString::from(name).to_pascal_case()?;
+
+                    let mut member_names = BTreeMap::new();
+                    for (value, value_metadata) in metadata::enum_values(metadata)? {
+                        if let Some(member) = value_metadata.get("name").and_then(|n| n.as_str()) {
+                            member_names.insert(value, member.to_owned());
+                        }
+                    }
+
+                    if let Some(style) = EnumStyle::from_metadata(metadata)? {
+                        self.enum_styles.insert(name.clone(), style);
+                    }
+
+                    self.enums
+                        .insert(name.clone(), enum_.iter().cloned().collect());
+                    self.enum_member_names.insert(name, member_names);
+                }
+                None => bail!("string names are required for enums"),
+            },
+            Schema::Elements { elements, .. } => self.insert_enums_from_schema(
+                elements,
+                name_suggestion.map(|n| format!("{n}Elements")).as_deref(),
+                globals,
+            )?,
+            Schema::Values { values, .. } => self.insert_enums_from_schema(
+                values,
+                name_suggestion.map(|n| format!("{n}Values")).as_deref(),
+                globals,
+            )?,
+            Schema::Properties {
+                properties,
+                optional_properties,
+                ..
+            } => {
+                for (field_name, field_schema) in properties.iter().chain(optional_properties) {
+                    self.insert_enums_from_schema(field_schema, Some(field_name), globals)
+                        .wrap_err_with(|| format!("could not find enums in `{field_name}`"))?
+                }
+            }
+            Schema::Discriminator { mapping, .. } => {
+                for (tag, tag_schema) in mapping {
+                    self.insert_enums_from_schema(tag_schema, Some(tag), globals)
+                        .wrap_err_with(|| format!("could not find enums in `{tag}`"))?
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Find all the discriminated unions in a schema and remember them so we can generate a
+    /// `match*` helper for each. These are named the same way as enums.
+    pub fn insert_matchers_from_schema(
+        &mut self,
+        schema: &Schema,
+        name_suggestion: Option<&str>,
+        globals: &BTreeMap<String, Schema>,
+    ) -> Result<()> {
+        self.insert_matchers(schema, name_suggestion, None, globals, &mut BTreeSet::new())
+    }
+
+    fn insert_matchers(
+        &mut self,
+        schema: &Schema,
+        name_suggestion: Option<&str>,
+        type_: Option<Doc>,
+        globals: &BTreeMap<String, Schema>,
+        seen_refs: &mut BTreeSet<String>,
+    ) -> Result<()> {
+        match schema {
+            Schema::Empty { .. } | Schema::Type { .. } | Schema::Enum { .. } => (),
+            Schema::Ref {
+                definitions, ref_, ..
+            } => {
+                if !seen_refs.insert(ref_.clone()) {
+                    return Ok(());
+                }
+
+                // definitions have a named type we can refer to, but local definitions don't.
+                let (def, def_type) = match definitions.get(ref_) {
+                    Some(def) => (def, None),
+                    None => match globals.get(ref_) {
+                        Some(def) => {
+                            let mut def_type = TSType::from_definition(
+                                ref_,
+                                name_suggestion,
+                                globals,
+                                &mut NamedTypes::new(),
+                            )?;
+                            def_type.set_nullable(false);
+                            (def, Some(def_type.to_type_doc()?))
+                        }
+                        None => bail!("could not find a definition for `{ref_}`"),
+                    },
+                };
+
+                self.insert_matchers(
+                    def,
+                    name_suggestion.or(Some(ref_)),
+                    def_type,
+                    globals,
+                    seen_refs,
+                )
+                .wrap_err_with(|| format!("could not find discriminators in ref `{ref_}`"))?
+            }
+            Schema::Elements { elements, .. } => self.insert_matchers(
+                elements,
+                name_suggestion.map(|n| format!("{n}Elements")).as_deref(),
+                None,
+                globals,
+                seen_refs,
+            )?,
+            Schema::Values { values, .. } => self.insert_matchers(
+                values,
+                name_suggestion.map(|n| format!("{n}Values")).as_deref(),
+                None,
+                globals,
+                seen_refs,
+            )?,
+            Schema::Properties {
+                properties,
+                optional_properties,
+                ..
+            } => {
+                for (field_name, field_schema) in properties.iter().chain(optional_properties) {
+                    self.insert_matchers(field_schema, Some(field_name), None, globals, seen_refs)
+                        .wrap_err_with(|| {
+                            format!("could not find discriminators in `{field_name}`")
+                        })?
+                }
+            }
+            Schema::Discriminator {
+                metadata,
+                discriminator,
+                mapping,
+                ..
+            } => {
+                let name = match metadata
+                    .get("name")
+                    .and_then(|n| n.as_str())
+                    .or(name_suggestion)
+                {
+                    Some(name) => InflectedString::from(name).to_pascal_case()?,
+                    None => bail!("string names are required for discriminators"),
+                };
+
+                let type_ = match type_ {
+                    Some(type_) => type_,
+                    None => {
+                        // null has no tag to match on, so callers have to deal with it first.
+                        let mut union = TSType::from_schema(
+                            schema.clone(),
+                            name_suggestion,
+                            globals,
+                            &mut NamedTypes::new(),
+                        )?;
+                        union.set_nullable(false);
+                        union.to_type_doc()?
+                    }
+                };
+
+                self.matchers.insert(
+                    name,
+                    Matcher {
+                        type_,
+                        discriminator: InflectedString::from(discriminator.as_str())
+                            .to_camel_case()?,
+                        tags: mapping.keys().cloned().collect(),
+                        open: metadata::is_open(metadata),
+                    },
+                );
+
+                for (tag, tag_schema) in mapping {
+                    self.insert_matchers(tag_schema, Some(tag), None, globals, seen_refs)
+                        .wrap_err_with(|| format!("could not find discriminators in `{tag}`"))?
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Add `is{Name}` and `assert{Name}` guards that check a value against the schema at runtime,
+    /// and narrow it to `type_` if it passes.
+    pub fn insert_guard(
+        &mut self,
+        name: &str,
+        type_: Doc,
+        schema: &Schema,
+        globals: &BTreeMap<String, Schema>,
+    ) -> Result<()> {
+        let name = InflectedString::from(name).to_pascal_case()?;
+        let check_name = format!("check{name}");
+
+        if self.guards.contains_key(&name) || self.checks.contains_key(&check_name) {
+            bail!(
+                "two different things would get an `is{name}` guard in the {} module, so we can't generate both. Rename one of them.",
+                self.name.join("."),
+            )
+        }
+
+        // claim the name first, so refs we check along the way can't take it.
+        self.guards.insert(name, type_);
+
+        let check = self.check_function_doc(&check_name, schema, globals)?;
+        self.checks.insert(check_name, check);
+
+        Ok(())
+    }
+
+    fn check_function_doc(
+        &mut self,
+        check_name: &str,
+        schema: &Schema,
+        globals: &BTreeMap<String, Schema>,
+    ) -> Result<Doc> {
+        let mut body = self.check_doc(schema, "value", "${path}", 0, globals)?;
+        body.push(text("return null;"));
+
+        Ok(function_doc(
+            format!("function {check_name}"),
+            Vec::from([text("value: unknown"), text("path: string")]),
+            text("string | null"),
+            body,
+        ))
+    }
+
+    /// Statements that check `value` against `schema` and return a problem (starting with the
+    /// JSON path in `path`, which is the inside of a template literal) if it doesn't match.
+    /// `depth` keeps variable names from shadowing each other when we nest.
+    fn check_doc(
+        &mut self,
+        schema: &Schema,
+        value: &str,
+        path: &str,
+        depth: usize,
+        globals: &BTreeMap<String, Schema>,
+    ) -> Result<Vec<Doc>> {
+        let (nullable, checks) = match schema {
+            Schema::Empty { .. } => return Ok(Vec::new()),
+            Schema::Ref {
+                definitions,
+                nullable,
+                ref_,
+                ..
+            } => {
+                let check_name = format!(
+                    "check{}Ref",
+                    InflectedString::from(ref_.as_str()).to_pascal_case()?
+                );
+
+                if self.guards.contains_key(&check_name["check".len()..]) {
+                    bail!(
+                        "the check for ref `{ref_}` would be called `{check_name}`, but that's already the check for a guard in the {} module. Rename one of them.",
+                        self.name.join("."),
+                    )
+                }
+
+                // refs can be recursive, so we claim the name before we generate the check
+                if !self.checks.contains_key(&check_name) {
+                    self.checks.insert(check_name.clone(), Doc::empty());
+
+                    let check = match definitions.get(ref_).or_else(|| globals.get(ref_)) {
+                        Some(def) => self
+                            .check_function_doc(&check_name, def, globals)
+                            .wrap_err_with(|| format!("could not check ref `{ref_}`"))?,
+                        None => bail!("could not find a definition for `{ref_}`"),
+                    };
+                    self.checks.insert(check_name.clone(), check);
+                }
+
+                (
+                    *nullable,
+                    Vec::from([braces(Vec::from([
+                        concat(Vec::from([
+                            assignment_doc(
+                                text("const problem ="),
+                                call_doc(
+                                    &check_name,
+                                    Vec::from([text(value), text(format!("`{path}`"))]),
+                                ),
+                            ),
+                            text(";"),
+                        ])),
+                        if_doc(
+                            Vec::from([text("problem !== null")]),
+                            Vec::from([text("return problem;")]),
+                        ),
+                    ]))]),
+                )
+            }
+            Schema::Type {
+                nullable, type_, ..
+            } => {
+                let (condition, expected) = match type_ {
+                    jtd::Type::Boolean => (
+                        Vec::from([text(format!("typeof {value} !== \"boolean\""))]),
+                        String::from("a boolean"),
+                    ),
+                    jtd::Type::String => (
+                        Vec::from([text(format!("typeof {value} !== \"string\""))]),
+                        String::from("a string"),
+                    ),
+                    jtd::Type::Timestamp => (
+                        Vec::from([
+                            text(format!("typeof {value} !== \"string\"")),
+                            text(format!("Number.isNaN(Date.parse({value}))")),
+                        ]),
+                        String::from("a timestamp"),
+                    ),
+                    jtd::Type::Float32 | jtd::Type::Float64 => (
+                        Vec::from([text(format!("typeof {value} !== \"number\""))]),
+                        String::from("a number"),
+                    ),
+                    jtd::Type::Int8 => int_check(value, i8::MIN.into(), i8::MAX.into()),
+                    jtd::Type::Uint8 => int_check(value, u8::MIN.into(), u8::MAX.into()),
+                    jtd::Type::Int16 => int_check(value, i16::MIN.into(), i16::MAX.into()),
+                    jtd::Type::Uint16 => int_check(value, u16::MIN.into(), u16::MAX.into()),
+                    jtd::Type::Int32 => int_check(value, i32::MIN.into(), i32::MAX.into()),
+                    jtd::Type::Uint32 => int_check(value, u32::MIN.into(), u32::MAX.into()),
+                };
+
+                (
+                    *nullable,
+                    Vec::from([problem_if(condition, path, &expected)]),
+                )
+            }
+            Schema::Enum {
+                metadata,
+                nullable,
+                enum_,
+                ..
+            } => {
+                let values: Vec<String> = enum_
+                    .iter()
+                    .map(serde_json::to_string)
+                    .collect::<Result<_, _>>()?;
+
+                let check = if metadata::is_open(metadata) {
+                    problem_if(
+                        Vec::from([text(format!("typeof {value} !== \"string\""))]),
+                        path,
+                        "a string",
+                    )
+                } else {
+                    problem_if(
+                        Vec::from([concat(Vec::from([
+                            text("!"),
+                            array_doc(values.iter().map(text).collect()),
+                            text(format!(".includes({value} as string)")),
+                        ]))]),
+                        path,
+                        &format!("one of {}", template_escape(&values.join(", "))),
+                    )
+                };
+
+                (*nullable, Vec::from([check]))
+            }
+            Schema::Elements {
+                nullable, elements, ..
+            } => {
+                let index = format!("i{depth}");
+                let item = format!("value{}", depth + 1);
+
+                let mut body =
+                    Vec::from([text(format!("const {item}: unknown = {value}[{index}];"))]);
+                body.extend(self.check_doc(
+                    elements,
+                    &item,
+                    &format!("{path}[${{{index}}}]"),
+                    depth + 1,
+                    globals,
+                )?);
+
+                (
+                    *nullable,
+                    Vec::from([
+                        problem_if(
+                            Vec::from([text(format!("!Array.isArray({value})"))]),
+                            path,
+                            "an array",
+                        ),
+                        concat(Vec::from([
+                            text(format!(
+                                "for (let {index} = 0; {index} < {value}.length; {index}++) "
+                            )),
+                            braces(body),
+                        ])),
+                    ]),
+                )
+            }
+            Schema::Values {
+                nullable, values, ..
+            } => {
+                let key = format!("key{depth}");
+                let item = format!("value{}", depth + 1);
+
+                let body = self.check_doc(
+                    values,
+                    &item,
+                    &format!("{path}.${{{key}}}"),
+                    depth + 1,
+                    globals,
+                )?;
+
+                (
+                    *nullable,
+                    Vec::from([
+                        problem_if(object_condition(value), path, "an object"),
+                        concat(Vec::from([
+                            text(format!("for (const [{key}, {item}] of ")),
+                            call_doc(
+                                "Object.entries",
+                                Vec::from([text(format!("{value} as Record<string, unknown>"))]),
+                            ),
+                            text(") "),
+                            braces(body),
+                        ])),
+                    ]),
+                )
+            }
+            Schema::Properties {
+                nullable,
+                properties,
+                optional_properties,
+                ..
+            } => {
+                let object = format!("object{depth}");
+                let item = format!("value{}", depth + 1);
+
+                let mut checks =
+                    Vec::from([problem_if(object_condition(value), path, "an object")]);
+                if !properties.is_empty() || !optional_properties.is_empty() {
+                    checks.push(text(format!(
+                        "const {object} = {value} as Record<string, unknown>;"
+                    )));
+                }
+
+                for (field, field_schema) in properties {
+                    let field_json = serde_json::to_string(field)?;
+                    let field_path = format!("{path}.{}", template_escape(field));
+
+                    let mut body =
+                        Vec::from([text(format!("const {item} = {object}[{field_json}];"))]);
+                    body.extend(self.check_doc(
+                        field_schema,
+                        &item,
+                        &field_path,
+                        depth + 1,
+                        globals,
+                    )?);
+
+                    checks.push(if_doc(
+                        Vec::from([text(format!("!({field_json} in {object})"))]),
+                        Vec::from([text(format!(
+                            "return `{path}: missing {}`;",
+                            template_escape(&field_json)
+                        ))]),
+                    ));
+                    checks.push(braces(body));
+                }
+
+                for (field, field_schema) in optional_properties {
+                    let field_json = serde_json::to_string(field)?;
+                    let field_path = format!("{path}.{}", template_escape(field));
+
+                    let mut body =
+                        Vec::from([text(format!("const {item} = {object}[{field_json}];"))]);
+                    body.extend(self.check_doc(
+                        field_schema,
+                        &item,
+                        &field_path,
+                        depth + 1,
+                        globals,
+                    )?);
+
+                    checks.push(if_doc(
+                        Vec::from([text(format!("{object}[{field_json}] !== undefined"))]),
+                        body,
+                    ));
+                }
+
+                (*nullable, checks)
+            }
+            Schema::Discriminator {
+                metadata,
+                nullable,
+                discriminator,
+                mapping,
+                ..
+            } => {
+                let object = format!("object{depth}");
+                let tag_json = serde_json::to_string(discriminator)?;
+                let tag_path = format!("{path}.{}", template_escape(discriminator));
+
+                let mut cases = Vec::with_capacity(mapping.len() + 1);
+                for (tag, tag_schema) in mapping {
+                    let mut body = self.check_doc(tag_schema, value, path, depth + 1, globals)?;
+                    body.push(text("break;"));
+
+                    cases.push(concat(Vec::from([
+                        text(format!("case {}: ", serde_json::to_string(tag)?)),
+                        braces(body),
+                    ])));
+                }
+
+                let fallback = if metadata::is_open(metadata) {
+                    // we don't know anything about tags we haven't heard of, but it should still
+                    // be a string so Elm can decode it.
+                    problem_if(
+                        Vec::from([text(format!("typeof {object}[{tag_json}] !== \"string\""))]),
+                        &tag_path,
+                        "a string",
+                    )
+                } else {
+                    let tags: Vec<String> = mapping
+                        .keys()
+                        .map(serde_json::to_string)
+                        .collect::<Result<_, _>>()?;
+                    text(format!(
+                        "return `{tag_path}: expected one of {}`;",
+                        template_escape(&tags.join(", "))
+                    ))
+                };
+                cases.push(concat(Vec::from([
+                    text("default:"),
+                    indent(concat(Vec::from([Doc::HardLine, fallback]))),
+                ])));
+
+                (
+                    *nullable,
+                    Vec::from([
+                        problem_if(object_condition(value), path, "an object"),
+                        text(format!(
+                            "const {object} = {value} as Record<string, unknown>;"
+                        )),
+                        concat(Vec::from([
+                            text(format!("switch ({object}[{tag_json}]) ")),
+                            braces(cases),
+                        ])),
+                    ]),
+                )
+            }
+        };
+
+        if nullable {
+            Ok(Vec::from([if_doc(
+                Vec::from([text(format!("{value} !== null"))]),
+                checks,
+            )]))
+        } else {
+            Ok(checks)
+        }
+    }
+
+    pub fn to_source(&self) -> Result<String> {
+        let mut items = Vec::from([text(
+            "// Warning: this file is automatically generated. Don't edit by hand!",
+        )]);
+
+        if let (Some(path), false) = (
+            &self.elm_import,
+            self.guards.is_empty() && self.matchers.is_empty() && self.ports.is_empty(),
+        ) {
+            items.push(text(format!(
+                "import type {{ Elm }} from {};",
+                serde_json::to_string(path)?
+            )));
+        }
+
+        for (name, values) in &self.enums {
+            let values = values
+                .iter()
+                .map(|value| Ok(text(serde_json::to_string(value)?)))
+                .collect::<Result<Vec<_>>>()?;
+
+            items.push(concat(Vec::from([
+                assignment_doc(
+                    text(format!("export const all{name} =")),
+                    concat(Vec::from([array_doc(values), text(" as const")])),
+                ),
+                text(";"),
+            ])));
+
+            if let Some(enum_) = self.enum_doc(name)? {
+                items.push(enum_);
+            }
+        }
+
+        for (name, type_) in &self.guards {
+            items.push(function_doc(
+                format!("export function is{name}"),
+                Vec::from([text("value: unknown")]),
+                concat(Vec::from([text("value is "), type_.clone()])),
+                Vec::from([text(format!("return check{name}(value, \"$\") === null;"))]),
+            ));
+            items.push(function_doc(
+                format!("export function assert{name}"),
+                Vec::from([text("value: unknown")]),
+                concat(Vec::from([text("asserts value is "), type_.clone()])),
+                Vec::from([
+                    text(format!("const problem = check{name}(value, \"$\");")),
+                    if_doc(
+                        Vec::from([text("problem !== null")]),
+                        Vec::from([text("throw new TypeError(problem);")]),
+                    ),
+                ]),
+            ));
+        }
+
+        for (name, matcher) in &self.matchers {
+            items.push(matcher.to_doc(name)?);
+        }
+
+        if !self.ports.is_empty() {
+            items.push(self.connect_doc()?);
+        }
+
+        items.extend(self.checks.values().cloned());
+
+        let mut out = join(concat(Vec::from([Doc::HardLine, Doc::HardLine])), items).render();
+        out.push('\n');
+        Ok(out)
+    }
+
+    fn enum_doc(&self, name: &str) -> Result<Option<Doc>> {
+        let style = self
+            .enum_styles
+            .get(name)
+            .copied()
+            .unwrap_or(self.enum_style);
+        let values = match self.enums.get(name) {
+            Some(values) if style != EnumStyle::Union => values,
+            _ => return Ok(None),
+        };
+
+        let mut members = Vec::with_capacity(values.len());
+        for value in values {
+            let member = match self
+                .enum_member_names
+                .get(name)
+                .and_then(|names| names.get(value))
+            {
+                Some(member) => member.clone(),
+                None => InflectedString::from(value.as_str())
+                    .to_pascal_case()
+                    .wrap_err_with(|| {
+                        format!("could not name the `{value}` member of `{name}`. Try setting a name in `metadata.values`")
+                    })?,
+            };
+            let value = serde_json::to_string(value)?;
+
+            members.push(text(match style {
+                EnumStyle::Enum => format!("{member} = {value},"),
+                EnumStyle::Union | EnumStyle::Object => format!("{member}: {value},"),
+            }));
+        }
+
+        Ok(Some(match style {
+            EnumStyle::Union => return Ok(None),
+            EnumStyle::Enum => concat(Vec::from([
+                text(format!("export enum {name} ")),
+                braces(members),
+            ])),
+            EnumStyle::Object => concat(Vec::from([
+                text(format!("export const {name} = ")),
+                braces(members),
+                text(" as const;"),
+                Doc::HardLine,
+                assignment_doc(
+                    text(format!("export type {name} =")),
+                    text(format!("(typeof {name})[keyof typeof {name}]")),
+                ),
+                text(";"),
+            ])),
+        }))
+    }
+
+    fn connect_doc(&self) -> Result<Doc> {
+        let module_name = self.name.join(".");
+        let ports_type = format!("Elm.{module_name}.Ports");
+
+        let mut handlers = Vec::new();
+        let mut senders = Vec::new();
+        let mut connections = Vec::new();
+        let mut send_functions = Vec::new();
+
+        for (name, (direction, value_type)) in &self.ports {
+            let key = serde_json::to_string(name)?;
+            let signature = concat(Vec::from([
+                text(format!("{name}: ")),
+                group(concat(Vec::from([
+                    parameters_doc(Vec::from([concat(Vec::from([
+                        text("value: "),
+                        value_type.clone(),
+                    ]))])),
+                    text(" => void"),
+                ]))),
+            ]));
+
+            match direction {
+                PortDirection::Subscribe => {
+                    handlers.push(signature);
+                    connections.push(concat(Vec::from([
+                        text(format!("if (ports.{name}) ")),
+                        braces(Vec::from([text(format!(
+                            "ports.{name}.subscribe(handlers.{name});"
+                        ))])),
+                        text(" else "),
+                        braces(Vec::from([text(format!("missing({key});"))])),
+                    ])));
+                }
+                PortDirection::Send => {
+                    senders.push(signature);
+                    connections.push(if_doc(
+                        Vec::from([text(format!("!ports.{name}"))]),
+                        Vec::from([text(format!("missing({key});"))]),
+                    ));
+                    send_functions.push(concat(Vec::from([
+                        text(format!("{name}: (value) => ")),
+                        braces(Vec::from([text(format!("ports.{name}?.send(value);"))])),
+                        text(","),
+                    ])));
+                }
+            }
+        }
+
+        let mut body = Vec::from([
+            text(format!("const ports: {ports_type} = app.ports ?? {{}};")),
+            concat(Vec::from([
+                text("const missing = (port: string) => "),
+                braces(Vec::from([
+                    text(format!("const message = `the {module_name} Elm module does not have a \"${{port}}\" port. Is it used in Elm?`;")),
+                    concat(Vec::from([
+                        text("if (options.missingPorts === \"throw\") "),
+                        braces(Vec::from([text("throw new Error(message);")])),
+                        text(" else if (options.missingPorts !== \"ignore\") "),
+                        braces(Vec::from([text("console.warn(message);")])),
+                    ])),
+                ])),
+                text(";"),
+            ])),
+        ]);
+
+        // a blank line before each port and before the return, like we'd write it by hand
+        for connection in connections {
+            body.push(Doc::empty());
+            body.push(connection);
+        }
+        body.push(Doc::empty());
+        body.push(if send_functions.is_empty() {
+            text("return {};")
+        } else {
+            concat(Vec::from([
+                text("return "),
+                braces(send_functions),
+                text(";"),
+            ]))
+        });
+
+        Ok(concat(Vec::from([
+            text("/**"),
+            Doc::HardLine,
+            text(" * Subscribe to every port Elm sends values through, and get functions for sending values to"),
+            Doc::HardLine,
+            text(" * Elm. Elm removes ports that are never used, so we warn about missing ports (or throw, or"),
+            Doc::HardLine,
+            text(" * ignore them, depending on `options.missingPorts`.)"),
+            Doc::HardLine,
+            text(" */"),
+            Doc::HardLine,
+            function_doc(
+                format!("export function connect{}", self.name.concat()),
+                Vec::from([
+                    concat(Vec::from([
+                        text("app: "),
+                        group(object_members_doc(Vec::from([text(format!(
+                            "ports?: {ports_type}"
+                        ))]))),
+                    ])),
+                    concat(Vec::from([
+                        text("handlers: "),
+                        broken_group(object_members_doc(handlers)),
+                    ])),
+                    text("options: { missingPorts?: \"throw\" | \"warn\" | \"ignore\" } = {}"),
+                ]),
+                broken_group(object_members_doc(senders)),
+                body,
+            ),
+        ])))
+    }
+}
+
+impl Matcher {
+    fn to_doc(&self, name: &str) -> Result<Doc> {
+        let discriminator = object_key(&self.discriminator);
+
+        let mut handlers = Vec::with_capacity(self.tags.len());
+        let mut cases = Vec::with_capacity(self.tags.len());
+        for tag in &self.tags {
+            let tag_json = serde_json::to_string(tag)?;
+            let payload = type_arguments_doc(
+                "Extract",
+                Vec::from([
+                    text("typeof value"),
+                    text(format!("{{ {discriminator}: {tag_json} }}")),
+                ]),
+            );
+
+            handlers.push(concat(Vec::from([
+                text(format!("{}: ", object_key(tag))),
+                group(concat(Vec::from([
+                    parameters_doc(Vec::from([concat(Vec::from([
+                        text("payload: "),
+                        payload.clone(),
+                    ]))])),
+                    text(" => R"),
+                ]))),
+            ])));
+
+            // the `{ tag: string }` member of open unions matches every case, so TypeScript can't
+            // narrow it away for us.
+            let argument = if self.open {
+                concat(Vec::from([text("value as "), payload]))
+            } else {
+                text("value")
+            };
+            cases.push(concat(Vec::from([
+                text(format!("case {tag_json}:")),
+                indent(concat(Vec::from([
+                    Doc::HardLine,
+                    text("return "),
+                    call_doc(&member("handlers", tag), Vec::from([argument])),
+                    text(";"),
+                ]))),
+            ])));
+        }
+
+        let mut params = Vec::from([
+            concat(Vec::from([assignment_doc(
+                text("value:"),
+                self.type_.clone(),
+            )])),
+            concat(Vec::from([
+                text("handlers: "),
+                broken_group(object_members_doc(handlers)),
+            ])),
+        ]);
+
+        let fallback = if self.open {
+            params.push(concat(Vec::from([
+                text("otherwise: "),
+                group(concat(Vec::from([
+                    parameters_doc(Vec::from([text(format!(
+                        "payload: {{ {discriminator}: string }}"
+                    ))])),
+                    text(" => R"),
+                ]))),
+            ])));
+
+            concat(Vec::from([
+                text("return "),
+                call_doc(
+                    "otherwise",
+                    Vec::from([text(format!("value as {{ {discriminator}: string }}"))]),
+                ),
+                text(";"),
+            ]))
+        } else {
+            concat(Vec::from([
+                text("throw new "),
+                call_doc(
+                    "TypeError",
+                    Vec::from([text(format!(
+                        "`unexpected {}: ${{JSON.stringify({})}}`",
+                        self.discriminator,
+                        member(
+                            &format!("(value as {{ {discriminator}: unknown }})"),
+                            &self.discriminator
+                        ),
+                    ))]),
+                ),
+                text(";"),
+            ]))
+        };
+
+        Ok(function_doc(
+            format!("export function match{name}<R>"),
+            params,
+            text("R"),
+            Vec::from([
+                concat(Vec::from([
+                    text(format!(
+                        "switch ({}) ",
+                        member("value", &self.discriminator)
+                    )),
+                    braces(cases),
+                ])),
+                Doc::empty(),
+                fallback,
+            ]),
+        ))
+    }
+}
+
+/// The TypeScript type of the flags for an Elm module.
+pub fn flags_type(module_name: &str) -> Doc {
+    text(format!("Elm.{module_name}.Flags"))
+}
+
+/// The TypeScript type of the values going through a port. The declarations don't name these, so
+/// we dig them out of the port's functions.
+pub fn port_value_type(module_name: &str, port: &str, direction: PortDirection) -> Result<Doc> {
+    let port_type = type_arguments_doc(
+        "NonNullable",
+        Vec::from([text(format!(
+            "Elm.{module_name}.Ports[{}]",
+            serde_json::to_string(&InflectedString::from(port).to_camel_case()?)?
+        ))]),
+    );
+
+    // `Parameters<F>[0]`, the type of the first argument to `F`
+    let first_parameter = |function: Doc| {
+        concat(Vec::from([
+            type_arguments_doc("Parameters", Vec::from([function])),
+            text("[0]"),
+        ]))
+    };
+
+    Ok(match direction {
+        PortDirection::Send => first_parameter(concat(Vec::from([port_type, text("[\"send\"]")]))),
+        PortDirection::Subscribe => first_parameter(first_parameter(concat(Vec::from([
+            port_type,
+            text("[\"subscribe\"]"),
+        ])))),
+    })
+}
+
+/// A function declaration. Like prettier, we break the parameters if the whole signature doesn't
+/// fit on one line.
+fn function_doc(head: String, params: Vec<Doc>, returns: Doc, body: Vec<Doc>) -> Doc {
+    concat(Vec::from([
+        group(concat(Vec::from([
+            text(head),
+            parameters_doc(params),
+            text(": "),
+            returns,
+        ]))),
+        text(" "),
+        braces(body),
+    ]))
+}
+
+fn call_doc(function: &str, args: Vec<Doc>) -> Doc {
+    group(concat(Vec::from([text(function), parameters_doc(args)])))
+}
+
+/// Statements in a block, one per line.
+fn braces(statements: Vec<Doc>) -> Doc {
+    if statements.is_empty() {
+        return text("{}");
+    }
+
+    concat(Vec::from([
+        text("{"),
+        indent(concat(Vec::from([
+            Doc::HardLine,
+            join(Doc::HardLine, statements),
+        ]))),
+        Doc::HardLine,
+        text("}"),
+    ]))
+}
+
+/// `if (a || b) { ... }`. Conditions that don't fit go on their own lines, with `||` at the end of
+/// each line.
+fn if_doc(conditions: Vec<Doc>, body: Vec<Doc>) -> Doc {
+    let condition = if conditions.len() == 1 {
+        concat(conditions)
+    } else {
+        group(concat(Vec::from([
+            indent(concat(Vec::from([
+                Doc::SoftLine,
+                join(concat(Vec::from([text(" ||"), Doc::Line])), conditions),
+            ]))),
+            Doc::SoftLine,
+        ])))
+    };
+
+    concat(Vec::from([
+        text("if ("),
+        condition,
+        text(") "),
+        braces(body),
+    ]))
+}
+
+/// Get a property of an object, with bracket syntax if the name isn't a valid identifier.
+fn member(object: &str, name: &str) -> String {
+    let key = object_key(name);
+    if key.starts_with('"') {
+        format!("{object}[{key}]")
+    } else {
+        format!("{object}.{key}")
+    }
+}
+
+/// Escape text from the schema so it's safe to put in a template literal.
+fn template_escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('`', "\\`")
+        .replace("${", "\\${")
+}
+
+fn problem_if(conditions: Vec<Doc>, path: &str, expected: &str) -> Doc {
+    if_doc(
+        conditions,
+        Vec::from([text(format!("return `{path}: expected {expected}`;"))]),
+    )
+}
+
+fn object_condition(value: &str) -> Vec<Doc> {
+    Vec::from([
+        text(format!("typeof {value} !== \"object\"")),
+        text(format!("{value} === null")),
+        text(format!("Array.isArray({value})")),
+    ])
+}
+
+fn int_check(value: &str, min: i64, max: i64) -> (Vec<Doc>, String) {
+    (
+        Vec::from([
+            text(format!("typeof {value} !== \"number\"")),
+            text(format!("!Number.isInteger({value})")),
+            text(format!("{value} < {min}")),
+            text(format!("{value} > {max}")),
+        ]),
+        format!("an integer between {min} and {max}"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    fn from_json(value: Value) -> Schema {
+        let json = serde_json::from_value(value).unwrap();
+        Schema::from_serde_schema(json).unwrap()
+    }
+
+    #[test]
+    fn enums_to_source() {
+        let mut module = Module::new(Vec::from(["Main".to_string()]));
+        module
+            .insert_enums_from_schema(
+                &from_json(json!({
+                    "properties": {
+                        "permission": {"enum": ["granted", "denied"]},
+                        "other": {"metadata": {"name": "Named"}, "enum": ["a"]},
+                    }
+                })),
+                Some("Flags"),
+                &BTreeMap::new(),
+            )
+            .unwrap();
+
+        assert_eq!(
+            module.to_source().unwrap(),
+            "// Warning: this file is automatically generated. Don't edit by hand!\n\nexport const allNamed = [\"a\"] as const;\n\nexport const allPermission = [\"denied\", \"granted\"] as const;\n"
+        );
+    }
+
+    #[test]
+    fn enums_as_typescript_enums() {
+        let mut module = Module::new(Vec::from(["Main".to_string()]));
+        module.represent_enums_as(EnumStyle::Enum);
+        module
+            .insert_enums_from_schema(
+                &from_json(json!({
+                    "metadata": {"values": {"1h": {"name": "OneHour"}}},
+                    "enum": ["1h", "one-day"],
+                })),
+                Some("Interval"),
+                &BTreeMap::new(),
+            )
+            .unwrap();
+
+        assert_eq!(
+            module.to_source().unwrap(),
+            "// Warning: this file is automatically generated. Don't edit by hand!\n\nexport const allInterval = [\"1h\", \"one-day\"] as const;\n\nexport enum Interval {\n  OneHour = \"1h\",\n  OneDay = \"one-day\",\n}\n"
+        );
+    }
+
+    #[test]
+    fn enums_as_objects() {
+        let mut module = Module::new(Vec::from(["Main".to_string()]));
+        module.represent_enums_as(EnumStyle::Object);
+        module
+            .insert_enums_from_schema(
+                &from_json(json!({"enum": ["granted"]})),
+                Some("Permission"),
+                &BTreeMap::new(),
+            )
+            .unwrap();
+
+        assert_eq!(
+            module.to_source().unwrap(),
+            "// Warning: this file is automatically generated. Don't edit by hand!\n\nexport const allPermission = [\"granted\"] as const;\n\nexport const Permission = {\n  Granted: \"granted\",\n} as const;\nexport type Permission = (typeof Permission)[keyof typeof Permission];\n"
+        );
+    }
+
+    #[test]
+    fn enums_override_their_style_in_metadata() {
+        let mut module = Module::new(Vec::from(["Main".to_string()]));
+        module
+            .insert_enums_from_schema(
+                &from_json(json!({
+                    "metadata": {"typescript": {"enum": "enum"}},
+                    "enum": ["granted"],
+                })),
+                Some("Permission"),
+                &BTreeMap::new(),
+            )
+            .unwrap();
+
+        assert_eq!(
+            module.to_source().unwrap(),
+            "// Warning: this file is automatically generated. Don't edit by hand!\n\nexport const allPermission = [\"granted\"] as const;\n\nexport enum Permission {\n  Granted = \"granted\",\n}\n"
+        );
+    }
+
+    #[test]
+    fn enums_reject_unknown_styles_in_metadata() {
+        let mut module = Module::new(Vec::from(["Main".to_string()]));
+        let err = module
+            .insert_enums_from_schema(
+                &from_json(json!({
+                    "metadata": {"typescript": {"enum": "const"}},
+                    "enum": ["granted"],
+                })),
+                Some("Permission"),
+                &BTreeMap::new(),
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "`metadata.typescript.enum` should be \"union\", \"enum\", or \"object\", but I got Some(\"const\")"
+        );
+    }
+
+    #[test]
+    fn enums_through_refs_use_ref_name() {
+        let mut module = Module::new(Vec::from(["Main".to_string()]));
+        module
+            .insert_enums_from_schema(
+                &from_json(json!({"ref": "perm"})),
+                None,
+                &BTreeMap::from([("perm".to_string(), from_json(json!({"enum": ["a"]})))]),
+            )
+            .unwrap();
+
+        assert_eq!(
+            module.enums,
+            BTreeMap::from([("Perm".to_string(), Vec::from(["a".to_string()]))])
+        );
+    }
+
+    #[test]
+    fn guard_checks_properties() {
+        let mut module = Module::new(Vec::from(["Main".to_string()]));
+        module
+            .insert_guard(
+                "Notify",
+                text("Notification"),
+                &from_json(json!({
+                    "properties": {"title": {"type": "string"}},
+                    "optionalProperties": {"count": {"type": "uint8"}},
+                })),
+                &BTreeMap::new(),
+            )
+            .unwrap();
+
+        let source = module.to_source().unwrap();
+        assert!(
+            source.contains("export function isNotify(value: unknown): value is Notification {")
+        );
+        assert!(source.contains(
+            "export function assertNotify(value: unknown): asserts value is Notification {"
+        ));
+        assert!(source.contains("missing \"title\""));
+        assert!(source.contains("expected an integer between 0 and 255"));
+    }
+
+    #[test]
+    fn guards_reject_names_that_are_already_taken() {
+        let mut module = Module::new(Vec::from(["Main".to_string()]));
+        let schema = from_json(json!({"type": "string"}));
+        module
+            .insert_guard("Flags", text("Flags"), &schema, &BTreeMap::new())
+            .unwrap();
+
+        assert_eq!(
+            module
+                .insert_guard("flags", text("string"), &schema, &BTreeMap::new())
+                .unwrap_err()
+                .to_string(),
+            "two different things would get an `isFlags` guard in the Main module, so we can't generate both. Rename one of them."
+        );
+    }
+
+    #[test]
+    fn guards_reject_names_of_ref_checks() {
+        let mut module = Module::new(Vec::from(["Main".to_string()]));
+        let globals = BTreeMap::from([("jwt".to_string(), from_json(json!({"type": "string"})))]);
+        assert_eq!(
+            module
+                .insert_guard(
+                    "jwtRef",
+                    text("string"),
+                    &from_json(json!({"ref": "jwt"})),
+                    &globals,
+                )
+                .unwrap_err()
+                .root_cause()
+                .to_string(),
+            "the check for ref `jwt` would be called `checkJwtRef`, but that's already the check for a guard in the Main module. Rename one of them."
+        );
+
+        let mut module = Module::new(Vec::from(["Main".to_string()]));
+        module
+            .insert_guard(
+                "Jwt",
+                text("string"),
+                &from_json(json!({"ref": "jwt"})),
+                &globals,
+            )
+            .unwrap();
+        assert_eq!(
+            module
+                .insert_guard("jwtRef", text("string"), &from_json(json!({"type": "string"})), &globals)
+                .unwrap_err()
+                .to_string(),
+            "two different things would get an `isJwtRef` guard in the Main module, so we can't generate both. Rename one of them."
+        );
+    }
+
+    #[test]
+    fn guards_report_nested_paths() {
+        let mut module = Module::new(Vec::from(["Main".to_string()]));
+        module
+            .insert_guard(
+                "Tags",
+                text("string[]"),
+                &from_json(json!({"elements": {"type": "string"}})),
+                &BTreeMap::new(),
+            )
+            .unwrap();
+
+        assert!(module
+            .to_source()
+            .unwrap()
+            .contains("`${path}[${i0}]: expected a string`"));
+    }
+
+    #[test]
+    fn connect_requires_handlers_and_returns_senders() {
+        let mut module = Module::new(Vec::from(["Main".to_string()]));
+        module
+            .insert_port("new_jwt", text("string"), PortDirection::Subscribe)
+            .unwrap();
+        module
+            .insert_port("logout", text("null"), PortDirection::Send)
+            .unwrap();
+
+        let source = module.to_source().unwrap();
+        assert!(source.contains("export function connectMain(\n  app: { ports?: Elm.Main.Ports },\n  handlers: {\n    newJwt: (value: string) => void;\n  },"));
+        assert!(source.contains("): {\n  logout: (value: null) => void;\n} {"));
+        assert!(source.contains("ports.newJwt.subscribe(handlers.newJwt);"));
+        assert!(source.contains("missing(\"logout\");"));
+    }
+
+    #[test]
+    fn connect_without_senders_returns_an_empty_object() {
+        let mut module = Module::new(Vec::from(["Main".to_string()]));
+        module
+            .insert_port("new_jwt", text("string"), PortDirection::Subscribe)
+            .unwrap();
+
+        let source = module.to_source().unwrap();
+        assert!(source.contains("\n): {} {\n"));
+        assert!(source.contains("\n  return {};\n}\n"));
+    }
+
+    #[test]
+    fn port_guards_fit_in_80_columns() {
+        let mut module = Module::new(Vec::from(["Main".to_string()]));
+        module
+            .insert_guard(
+                "gotNewNotificationsPermission",
+                port_value_type("Main", "gotNewNotificationsPermission", PortDirection::Send)
+                    .unwrap(),
+                &from_json(json!({"type": "int32"})),
+                &BTreeMap::new(),
+            )
+            .unwrap();
+
+        let source = module.to_source().unwrap();
+        assert!(source.contains("export function isGotNewNotificationsPermission(\n  value: unknown,\n): value is Parameters<\n  NonNullable<Elm.Main.Ports[\"gotNewNotificationsPermission\"]>[\"send\"]\n>[0] {\n"));
+        for line in source.lines() {
+            // template literals can't be broken up, so the error message is allowed to overflow
+            if !line.trim_start().starts_with("return `") {
+                assert!(line.len() <= 80, "{line:?} is too long");
+            }
+        }
+    }
+
+    #[test]
+    fn matchers_require_every_tag() {
+        let mut module = Module::new(Vec::from(["Main".to_string()]));
+        module
+            .insert_matchers_from_schema(
+                &from_json(json!({
+                    "discriminator": "tag",
+                    "mapping": {
+                        "close": {"properties": {}},
+                        "send-message": {"properties": {"message": {"type": "string"}}},
+                    },
+                })),
+                Some("toWorld"),
+                &BTreeMap::new(),
+            )
+            .unwrap();
+
+        let source = module.to_source().unwrap();
+        assert!(source.contains("export function matchToWorld<R>("));
+        assert!(source.contains(
+            "    close: (payload: Extract<typeof value, { tag: \"close\" }>) => R;\n    \"send-message\": (\n      payload: Extract<typeof value, { tag: \"send-message\" }>,\n    ) => R;\n"
+        ));
+        assert!(source.contains("      return handlers[\"send-message\"](value);"));
+        assert!(source.contains("throw new TypeError("));
+    }
+
+    #[test]
+    fn matchers_for_open_unions_take_a_fallback() {
+        let mut module = Module::new(Vec::from(["Main".to_string()]));
+        module
+            .insert_matchers_from_schema(
+                &from_json(json!({"ref": "event"})),
+                Some("fromWorld"),
+                &BTreeMap::from([(
+                    "event".to_string(),
+                    from_json(json!({
+                        "metadata": {"open": true},
+                        "discriminator": "tag",
+                        "mapping": {"ping": {"properties": {}}},
+                    })),
+                )]),
+            )
+            .unwrap();
+
+        let source = module.to_source().unwrap();
+        assert!(source.contains("  value: Elm.FromWorld,\n"));
+        assert!(source.contains("  otherwise: (payload: { tag: string }) => R,\n"));
+        assert!(source.contains(
+            "      return handlers.ping(value as Extract<typeof value, { tag: \"ping\" }>);\n"
+        ));
+        assert!(source.contains("return otherwise(value as { tag: string });"));
+    }
+}
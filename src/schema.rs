@@ -1,54 +1,126 @@
 use crate::elm;
+use crate::inflected_string::InflectedString;
+use crate::metadata::{self, Deprecation};
+use crate::runtime;
 use crate::typescript::NamespaceBuilder;
-use crate::typescript::{FieldPresence, TSType};
+use crate::typescript::{self, FieldPresence, ModuleStyle, NamedTypes, TSType};
+use crate::zod;
 use color_eyre::Result;
 use eyre::{bail, WrapErr};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
+use std::io::Read;
 use std::path::Path;
 use std::path::PathBuf;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct Schema {
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub definitions: BTreeMap<String, jtd::SerdeSchema>,
     pub modules: BTreeMap<String, Module>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct Module {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub flags: Option<jtd::SerdeSchema>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub ports: Option<BTreeMap<String, Port>>,
 }
 
-#[derive(Debug, Deserialize)]
+impl Module {
+    pub fn new(flags: Option<jtd::SerdeSchema>, ports: Option<BTreeMap<String, Port>>) -> Self {
+        Self { flags, ports }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct Port {
-    metadata: PortMeta,
+    pub metadata: PortMeta,
 
     #[serde(flatten)]
     pub schema: jtd::SerdeSchema,
 }
 
-#[derive(Debug, Deserialize)]
+impl Port {
+    pub fn new(direction: PortDirection, schema: jtd::SerdeSchema) -> Self {
+        Self {
+            metadata: PortMeta::new(direction),
+            schema,
+        }
+    }
+
+    /// The port's type. Any metadata we don't use for the port itself (like `name`, `values`, or
+    /// `elm.record`) is about the type, so we move it over.
+    pub fn type_schema(&self) -> jtd::SerdeSchema {
+        let mut schema = self.schema.clone();
+        if !self.metadata.other.is_empty() {
+            schema
+                .metadata
+                .get_or_insert_with(BTreeMap::new)
+                .extend(self.metadata.other.clone());
+        }
+
+        schema
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct PortMeta {
-    direction: PortDirection,
+    pub direction: PortDirection,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<Deprecation>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+impl PortMeta {
+    /// Metadata for a port going in `direction`, without a deprecation note or group. (Set
+    /// those afterwards if you need them.)
+    pub fn new(direction: PortDirection) -> Self {
+        Self {
+            direction,
+            deprecated: None,
+            group: None,
+            other: BTreeMap::new(),
+        }
+    }
+
+    /// The deprecation note for this port, if it's deprecated.
+    pub fn deprecation(&self) -> Option<String> {
+        self.deprecated.as_ref().and_then(Deprecation::reason)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PortDirection {
     JsToElm,
     ElmToJs,
 }
 
-impl Schema {
-    pub fn from_fs(path: &Path) -> Result<Schema> {
-        let bytes = std::fs::read(path).wrap_err_with(|| format!("could not read {path:?}"))?;
+/// What a schema is written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Yaml,
+}
 
+impl Format {
+    /// Guess the format from a file extension.
+    pub fn from_path(path: &Path) -> Result<Self> {
         match path.extension().and_then(std::ffi::OsStr::to_str) {
-            Some("json") => serde_json::from_slice(&bytes)
-                .wrap_err_with(|| format!("could not read schema from {path:?}")),
-            Some("yaml") => serde_yaml::from_slice(&bytes)
-                .wrap_err_with(|| format!("could not read schema from {path:?}")),
+            Some("json") => Ok(Format::Json),
+            Some("yaml") => Ok(Format::Yaml),
             Some(_) => bail!(
                 "I can't deserialize a schema from a {:?} file",
                 path.extension()
@@ -59,6 +131,33 @@ impl Schema {
             ),
         }
     }
+}
+
+impl Schema {
+    pub fn new(
+        definitions: BTreeMap<String, jtd::SerdeSchema>,
+        modules: BTreeMap<String, Module>,
+    ) -> Self {
+        Self {
+            definitions,
+            modules,
+        }
+    }
+
+    pub fn parse(source: &str, format: Format) -> Result<Schema> {
+        Self::from_reader(source.as_bytes(), format)
+    }
+
+    pub fn from_reader(reader: impl Read, format: Format) -> Result<Schema> {
+        match format {
+            Format::Json => {
+                serde_json::from_reader(reader).wrap_err("could not parse schema as JSON")
+            }
+            Format::Yaml => {
+                serde_yaml::from_reader(reader).wrap_err("could not parse schema as YAML")
+            }
+        }
+    }
 
     fn globals(&self) -> Result<BTreeMap<String, jtd::Schema>> {
         let mut out = BTreeMap::new();
@@ -74,93 +173,456 @@ impl Schema {
         Ok(out)
     }
 
+    /// Find everything marked with `metadata.deprecated`, as (path, reason) pairs. We use this to
+    /// warn about deprecated things that are still hanging around.
+    pub fn deprecations(&self) -> Result<Vec<(String, String)>> {
+        let mut out = Vec::new();
+
+        for (name, schema) in self.globals()? {
+            collect_deprecations(format!("definitions.{name}"), &schema, &mut out);
+        }
+
+        for (module_name, module) in &self.modules {
+            if let Some(flags) = &module.flags {
+                collect_deprecations(
+                    format!("modules.{module_name}.flags"),
+                    &jtd::Schema::from_serde_schema(flags.clone()).wrap_err_with(|| {
+                        format!(
+                            "could not interpret JTD schema for flags in the {module_name} module"
+                        )
+                    })?,
+                    &mut out,
+                );
+            }
+
+            for (port_name, port) in module.ports.iter().flatten() {
+                let path = format!("modules.{module_name}.ports.{port_name}");
+
+                if let Some(reason) = port.metadata.deprecation() {
+                    out.push((path.clone(), reason));
+                }
+
+                collect_deprecations(
+                    path,
+                    &jtd::Schema::from_serde_schema(port.type_schema()).wrap_err_with(|| {
+                        format!("could not interpret JTD schema for port {port_name}")
+                    })?,
+                    &mut out,
+                );
+            }
+        }
+
+        Ok(out)
+    }
+
     // TODO: audit how much work this does and consider moving responsibility into the TS module
-    pub fn to_ts(&self) -> Result<String> {
+    pub fn to_ts(&self, style: &typescript::Style) -> Result<String> {
         let mut builder = NamespaceBuilder::root("Elm");
+        let mut named = NamedTypes::new();
+        let mut ambient_modules = Vec::new();
+
+        let globals = self.globals()?;
+
+        if style.modules == ModuleStyle::Global {
+            for ref_ in globals.keys() {
+                TSType::from_definition(ref_, None, &globals, &mut named)?;
+            }
+        }
+
+        for (module_name, module) in &self.modules {
+            match style.modules {
+                ModuleStyle::Global => {
+                    let module_path: Vec<&str> = module_name.split('.').collect();
+                    for member in
+                        self.ts_module_members(module_name, module, &globals, &mut named, style)?
+                    {
+                        builder.insert(&module_path, member)?;
+                    }
+                }
+                ModuleStyle::EsModule => ambient_modules.push(self.ts_ambient_module(
+                    module_name,
+                    module,
+                    &globals,
+                    style,
+                )?),
+            }
+        }
+
+        for decl in named.into_decls(style) {
+            builder.insert(&[], decl)?;
+        }
+
+        let declarations = match style.modules {
+            ModuleStyle::Global => builder.into_tstype().to_source()?,
+            ModuleStyle::EsModule => {
+                let mut sources = Vec::with_capacity(ambient_modules.len());
+                for ambient_module in ambient_modules {
+                    sources.push(ambient_module.to_source()?);
+                }
+                sources.join("\n\n")
+            }
+        };
+
+        Ok(format!(
+            "// Warning: this file is automatically generated. Don't edit by hand!\n\n{declarations}\n"
+        ))
+    }
+
+    /// Like `to_ts`, but with one declaration file per module (like `Elm.Main.d.ts`.) Named types
+    /// that modules share go in `Elm.d.ts`, and TypeScript merges all the `Elm` namespaces back
+    /// together. If `index` is set, we also write an `index.d.ts` that references every file.
+    pub fn to_ts_split(
+        &self,
+        style: &typescript::Style,
+        index: bool,
+    ) -> Result<BTreeMap<PathBuf, String>> {
+        let mut named = NamedTypes::new();
+        let mut files = BTreeMap::new();
 
         let globals = self.globals()?;
 
+        if style.modules == ModuleStyle::Global {
+            for ref_ in globals.keys() {
+                TSType::from_definition(ref_, None, &globals, &mut named)?;
+            }
+        }
+
         for (module_name, module) in &self.modules {
-            let module_path: Vec<&str> = module_name.split('.').collect();
-
-            match &module.flags {
-                Some(flags_serde) => builder.insert(
-                    &module_path,
-                    TSType::from_schema(
-                        jtd::Schema::from_serde_schema(flags_serde.clone()).wrap_err_with(
-                            || {
-                                format!(
-                                    "could not interpret JTD schema for flags in the {} module",
-                                    module_name
-                                )
-                            },
+            let declaration = match style.modules {
+                ModuleStyle::Global => {
+                    let module_path: Vec<&str> = module_name.split('.').collect();
+                    let mut builder = NamespaceBuilder::root("Elm");
+                    for member in
+                        self.ts_module_members(module_name, module, &globals, &mut named, style)?
+                    {
+                        builder.insert(&module_path, member)?;
+                    }
+                    builder.into_tstype()
+                }
+                ModuleStyle::EsModule => {
+                    self.ts_ambient_module(module_name, module, &globals, style)?
+                }
+            };
+
+            files.insert(
+                format!("Elm.{module_name}.d.ts").into(),
+                format!(
+                    "// Warning: this file is automatically generated. Don't edit by hand!\n\n{}\n",
+                    declaration.to_source()?
+                ),
+            );
+        }
+
+        let shared = named.into_decls(style);
+        if !shared.is_empty() {
+            let mut builder = NamespaceBuilder::root("Elm");
+            for decl in shared {
+                builder.insert(&[], decl)?;
+            }
+
+            files.insert(
+                PathBuf::from("Elm.d.ts"),
+                format!(
+                    "// Warning: this file is automatically generated. Don't edit by hand!\n\n{}\n",
+                    builder.into_tstype().to_source()?
+                ),
+            );
+        }
+
+        if index {
+            let mut references = String::new();
+            for path in files.keys() {
+                references.push_str(&format!(
+                    "/// <reference path=\"./{}\" />\n",
+                    path.display()
+                ));
+            }
+
+            files.insert(
+                PathBuf::from("index.d.ts"),
+                format!("// Warning: this file is automatically generated. Don't edit by hand!\n\n{references}"),
+            );
+        }
+
+        Ok(files)
+    }
+
+    /// Declare a module on its own, as bundlers see it: `declare module "*/Main.elm"`, with
+    /// its own `Elm` object that only knows about itself.
+    fn ts_ambient_module(
+        &self,
+        module_name: &str,
+        module: &Module,
+        globals: &BTreeMap<String, jtd::Schema>,
+        style: &typescript::Style,
+    ) -> Result<TSType> {
+        let module_path: Vec<&str> = module_name.split('.').collect();
+        let mut builder = NamespaceBuilder::root("Elm");
+        let mut named = NamedTypes::new();
+
+        for member in self.ts_module_members(module_name, module, globals, &mut named, style)? {
+            builder.insert(&module_path, member)?;
+        }
+
+        for decl in named.into_decls(style) {
+            builder.insert(&[], decl)?;
+        }
+
+        Ok(TSType::new_ambient_module(
+            &format!("*/{}.elm", module_path.join("/")),
+            Vec::from([builder.into_namespace()]),
+        ))
+    }
+
+    /// `Flags`, `Ports`, and `init` for a module, declaring any named types they use in `named`.
+    fn ts_module_members(
+        &self,
+        module_name: &str,
+        module: &Module,
+        globals: &BTreeMap<String, jtd::Schema>,
+        named: &mut NamedTypes,
+        style: &typescript::Style,
+    ) -> Result<Vec<TSType>> {
+        let mut members = Vec::with_capacity(3);
+
+        match &module.flags {
+            Some(flags_serde) => members.push(
+                TSType::from_schema(
+                    jtd::Schema::from_serde_schema(flags_serde.clone()).wrap_err_with(|| {
+                        format!(
+                            "could not interpret JTD schema for flags in the {} module",
+                            module_name
+                        )
+                    })?,
+                    Some("Flags"),
+                    globals,
+                    named,
+                )
+                .wrap_err("could not convert flags")?
+                .into_data(style)
+                .into_decl("Flags", style),
+            ),
+            None => members.push(TSType::new_neverobject().into_typedecl("Flags")),
+        }
+
+        match &module.ports {
+            Some(ports) => {
+                let mut port_keys = BTreeMap::new();
+
+                for (name, value) in ports {
+                    let type_ = TSType::from_schema(
+                        jtd::Schema::from_serde_schema(value.type_schema()).wrap_err_with(
+                            || format!("could not interpret JTD schema for port {name}"),
                         )?,
-                        &globals,
+                        Some(name),
+                        globals,
+                        named,
                     )
-                    .wrap_err("could not convert flags")?
-                    .into_typedecl("Flags"),
-                )?,
-                None => builder.insert(
-                    &module_path,
-                    TSType::new_neverobject().into_typedecl("Flags"),
-                )?,
-            }
-
-            match &module.ports {
-                Some(ports) => {
-                    let mut port_keys = BTreeMap::new();
-
-                    for (name, value) in ports {
-                        let type_ = TSType::from_schema(
-                            jtd::Schema::from_serde_schema(value.schema.clone()).wrap_err_with(
-                                || format!("could not interpret JTD schema for port {name}"),
-                            )?,
-                            &globals,
-                        )
-                        .wrap_err_with(|| format!("could not convert port {name}"))?;
-
-                        let func_record = match value.metadata.direction {
-                            PortDirection::JsToElm => TSType::new_singleton_object(
-                                "send",
-                                TSType::new_send_function(type_),
-                                FieldPresence::Required,
-                            ),
-                            PortDirection::ElmToJs => TSType::new_singleton_object(
-                                "subscribe",
-                                TSType::new_subscribe_function(type_),
-                                FieldPresence::Required,
-                            ),
-                        };
-
-                        // if a port is defined in Elm but not hooked up, Elm will omit it. That
-                        // means this could be optional and we need to deal with that.
-                        port_keys.insert(name.as_str(), (func_record, FieldPresence::Optional));
+                    .wrap_err_with(|| format!("could not convert port {name}"))?
+                    .into_data(style);
+
+                    let mut func_record = match value.metadata.direction {
+                        PortDirection::JsToElm => TSType::new_singleton_object(
+                            "send",
+                            TSType::new_send_function(type_),
+                            FieldPresence::Required,
+                        ),
+                        PortDirection::ElmToJs => TSType::new_singleton_object(
+                            "subscribe",
+                            TSType::new_subscribe_function(type_),
+                            FieldPresence::Required,
+                        ),
+                    };
+
+                    if let Some(reason) = value.metadata.deprecation() {
+                        func_record = func_record.into_deprecated(&reason);
                     }
 
-                    builder.insert(
-                        &module_path,
-                        TSType::new_object(port_keys).into_typedecl("Ports"),
-                    )?
+                    // if a port is defined in Elm but not hooked up, Elm will omit it. That
+                    // means this could be optional and we need to deal with that.
+                    port_keys.insert(name.as_str(), (func_record, FieldPresence::Optional));
                 }
-                None => builder.insert(
-                    &module_path,
-                    TSType::new_neverobject().into_typedecl("Ports"),
-                )?,
+
+                members.push(TSType::new_object(port_keys).into_decl("Ports", style))
+            }
+            None => members.push(TSType::new_neverobject().into_typedecl("Ports")),
+        }
+
+        members.push(TSType::new_ref("Flags").into_init());
+
+        Ok(members)
+    }
+
+    pub fn to_ts_runtime(&self, style: &typescript::Style) -> Result<BTreeMap<PathBuf, String>> {
+        let globals = self.globals()?;
+        let mut files = BTreeMap::new();
+
+        for (name, module) in &self.modules {
+            let mut runtime_module =
+                runtime::Module::new(name.split('.').map(|s| s.to_owned()).collect());
+            runtime_module.represent_enums_as(style.enums);
+
+            if style.modules == ModuleStyle::EsModule {
+                // this path doesn't have to exist: it only has to match the `*/Main.elm` pattern
+                // we declared the module under, and `import type` goes away at build time.
+                runtime_module
+                    .import_elm_types_from(format!("./{}.elm", runtime_module.name.join("/")));
+            }
+
+            if let Some(flags) = &module.flags {
+                let flags_schema = jtd::Schema::from_serde_schema(flags.clone())?;
+
+                runtime_module
+                    .insert_enums_from_schema(&flags_schema, Some("Flags"), &globals)
+                    .wrap_err("could not find enums in flags")?;
+
+                runtime_module
+                    .insert_matchers_from_schema(&flags_schema, Some("Flags"), &globals)
+                    .wrap_err("could not find discriminators in flags")?;
+
+                runtime_module
+                    .insert_guard("Flags", runtime::flags_type(name), &flags_schema, &globals)
+                    .wrap_err("could not generate a guard for flags")?;
             }
 
-            builder.insert(&module_path, TSType::new_ref("Flags").into_init())?;
+            for (port, port_schema) in module.ports.iter().flatten() {
+                let schema = jtd::Schema::from_serde_schema(port_schema.type_schema())?;
+
+                runtime_module
+                    .insert_enums_from_schema(&schema, Some(port), &globals)
+                    .wrap_err_with(|| format!("could not find enums in the `{port}` port"))?;
+
+                runtime_module
+                    .insert_matchers_from_schema(&schema, Some(port), &globals)
+                    .wrap_err_with(|| {
+                        format!("could not find discriminators in the `{port}` port")
+                    })?;
+
+                let direction = match port_schema.metadata.direction {
+                    PortDirection::JsToElm => runtime::PortDirection::Send,
+                    PortDirection::ElmToJs => runtime::PortDirection::Subscribe,
+                };
+                let value_type = runtime::port_value_type(name, port, direction)?;
+
+                runtime_module
+                    .insert_guard(port, value_type.clone(), &schema, &globals)
+                    .wrap_err_with(|| {
+                        format!("could not generate a guard for the `{port}` port")
+                    })?;
+
+                runtime_module.insert_port(port, value_type, direction)?;
+            }
+
+            if !runtime_module.is_empty() {
+                files.insert(
+                    format!("{}.ts", runtime_module.name.join("/")).into(),
+                    runtime_module.to_source()?,
+                );
+            }
         }
 
-        Ok(format!(
-            "// Warning: this file is automatically generated. Don't edit by hand!\n\n{}",
-            builder.into_tstype().to_source(true)?
-        ))
+        Ok(files)
     }
 
-    pub fn to_elm(&self) -> Result<BTreeMap<PathBuf, String>> {
+    /// Generate zod schemas for every definition, flags type, and port. Module schemas are
+    /// prefixed with the module name (so the `notify` port in `Main` gets `MainNotifySchema`.)
+    pub fn to_zod(&self) -> Result<String> {
         let globals = self.globals()?;
+        let mut schemas = zod::Schemas::new();
+
+        schemas
+            .insert_definitions(&globals)
+            .wrap_err("could not convert definitions to zod")?;
+
+        for (module_name, module) in &self.modules {
+            let prefix = module_name.replace('.', "_");
+
+            if let Some(flags) = &module.flags {
+                schemas
+                    .insert(
+                        &format!("{prefix}_flags"),
+                        &jtd::Schema::from_serde_schema(flags.clone()).wrap_err_with(|| {
+                            format!(
+                                "could not interpret JTD schema for flags in the {module_name} module"
+                            )
+                        })?,
+                        Some("Flags"),
+                        &globals,
+                    )
+                    .wrap_err("could not convert flags to zod")?;
+            }
+
+            for (port, port_schema) in module.ports.iter().flatten() {
+                schemas
+                    .insert(
+                        &format!("{prefix}_{port}"),
+                        &jtd::Schema::from_serde_schema(port_schema.type_schema()).wrap_err_with(
+                            || format!("could not interpret JTD schema for port {port}"),
+                        )?,
+                        Some(port),
+                        &globals,
+                    )
+                    .wrap_err_with(|| format!("could not convert the `{port}` port to zod"))?;
+            }
+        }
+
+        Ok(schemas.to_source())
+    }
+
+    pub fn to_elm(&self, record_style: elm::RecordStyle) -> Result<BTreeMap<PathBuf, String>> {
+        let mut files = BTreeMap::new();
+
+        for elm_module in self.elm_modules(record_style)? {
+            files.insert(
+                format!("{}.elm", elm_module.name.join("/")).into(),
+                elm_module.to_source().wrap_err("could not get source")?,
+            );
+        }
+
+        Ok(files)
+    }
+
+    /// Generate a module of fuzzers to go along with each Elm module that has types in it.
+    pub fn to_elm_fuzzers(
+        &self,
+        record_style: elm::RecordStyle,
+    ) -> Result<BTreeMap<PathBuf, String>> {
         let mut files = BTreeMap::new();
 
+        for elm_module in self.elm_modules(record_style)? {
+            if let Some(source) = elm_module
+                .to_fuzzers_source()
+                .wrap_err("could not get fuzzers source")?
+            {
+                files.insert(
+                    format!("{}.elm", elm_module.fuzzers_name().join("/")).into(),
+                    source,
+                );
+            }
+        }
+
+        Ok(files)
+    }
+
+    /// Generate tests that check every decoder can read what its encoder writes. These use the
+    /// fuzzers from `to_elm_fuzzers`, so they need to be written to the same place.
+    pub fn to_elm_round_trip_tests(
+        &self,
+        record_style: elm::RecordStyle,
+    ) -> Result<BTreeMap<PathBuf, String>> {
+        let modules = self.elm_modules(record_style)?;
+
+        Ok(BTreeMap::from([(
+            PathBuf::from("Generated/RoundTripTest.elm"),
+            elm::round_trip_tests_source(&modules)
+                .wrap_err("could not generate round-trip tests")?,
+        )]))
+    }
+
+    fn elm_modules(&self, record_style: elm::RecordStyle) -> Result<Vec<elm::Module>> {
+        let globals = self.globals()?;
+        let mut modules = Vec::new();
+
         for (name, module) in &self.modules {
             let name_base: Vec<String> = name.split('.').map(|s| s.to_owned()).collect();
 
@@ -171,18 +633,22 @@ impl Schema {
                 flags_name.push("Flags".into());
 
                 let mut flags_module = elm::Module::new(flags_name);
-                flags_module
+                let flags_decl = flags_module
                     .insert_from_schema(
                         jtd::Schema::from_serde_schema(flags.clone())?,
                         Some("Flags".to_string()),
                         &globals,
+                        record_style,
                     )
                     .wrap_err("could not convert flags type to Elm module")?;
 
-                files.insert(
-                    format!("{}.elm", flags_module.name.join("/")).into(),
-                    flags_module.to_source().wrap_err("could not get source")?,
-                );
+                // flags that are just a ref are named after the definition, but `Flags` is what
+                // everyone looks for (and what TypeScript calls it.)
+                if flags_decl.name().to_pascal_case()? != "Flags" {
+                    flags_module.insert_alias("Flags", &flags_decl);
+                }
+
+                modules.push(flags_module);
             };
 
             // generate ports
@@ -191,34 +657,101 @@ impl Schema {
                 ports_name.extend(name_base.clone());
                 ports_name.push("Ports".into());
 
-                let mut ports_module = elm::Module::new(ports_name);
+                let mut ports_module = elm::Module::new(ports_name.clone());
+
+                // ports with a `metadata.group` go in their own modules below the main ports
+                // module (so `group: notifications` gets you `Main.Ports.Notifications`.)
+                let mut group_modules: BTreeMap<&str, elm::Module> = BTreeMap::new();
 
                 for (port, port_schema) in ports {
-                    let port_type = ports_module
+                    let target_module = match &port_schema.metadata.group {
+                        None => &mut ports_module,
+                        Some(group) => match group_modules.entry(group) {
+                            std::collections::btree_map::Entry::Occupied(entry) => entry.into_mut(),
+                            std::collections::btree_map::Entry::Vacant(entry) => {
+                                let mut group_name = ports_name.clone();
+                                group_name.push(
+                                    InflectedString::from(group.as_str())
+                                        .to_pascal_case()
+                                        .wrap_err_with(|| {
+                                            format!("could not use `{group}` as a module name")
+                                        })?,
+                                );
+
+                                entry.insert(elm::Module::new(group_name))
+                            }
+                        },
+                    };
+
+                    let port_type = target_module
                         .insert_from_schema(
-                            jtd::Schema::from_serde_schema(port_schema.schema.clone())?,
+                            jtd::Schema::from_serde_schema(port_schema.type_schema())?,
                             Some(port.into()),
                             &globals,
+                            record_style,
                         )
                         .wrap_err_with(|| format!("could not convert the `{port}` port to Elm"))?;
 
-                    ports_module.insert_port(elm::Port::new(
+                    target_module.insert_port(elm::Port::new(
                         port.to_owned(),
                         match port_schema.metadata.direction {
                             PortDirection::ElmToJs => elm::PortDirection::Send,
                             PortDirection::JsToElm => elm::PortDirection::Subscribe,
                         },
                         port_type,
+                        port_schema.metadata.deprecation(),
                     ))
                 }
 
-                files.insert(
-                    format!("{}.elm", ports_module.name.join("/")).into(),
-                    ports_module.to_source().wrap_err("could not get source")?,
+                let mut group_modules: Vec<elm::Module> = group_modules.into_values().collect();
+                ports_module.hoist_shared_decls(&mut group_modules)?;
+
+                modules.extend(
+                    std::iter::once(ports_module)
+                        .chain(group_modules)
+                        .filter(|elm_module| !elm_module.is_empty()),
                 );
             }
         }
 
-        Ok(files)
+        Ok(modules)
+    }
+}
+
+fn collect_deprecations(path: String, schema: &jtd::Schema, out: &mut Vec<(String, String)>) {
+    if let Some(reason) = metadata::deprecation(schema.metadata()) {
+        out.push((path.clone(), reason));
+    }
+
+    match schema {
+        jtd::Schema::Empty { .. } | jtd::Schema::Ref { .. } | jtd::Schema::Type { .. } => (),
+        jtd::Schema::Enum { metadata, .. } => {
+            // we'll complain about malformed `metadata.values` when we generate code
+            for (value, value_metadata) in metadata::enum_values(metadata).unwrap_or_default() {
+                if let Some(reason) = metadata::deprecation(&value_metadata) {
+                    out.push((format!("{path}.{value}"), reason))
+                }
+            }
+        }
+        jtd::Schema::Elements { elements, .. } => {
+            collect_deprecations(format!("{path}[]"), elements, out)
+        }
+        jtd::Schema::Values { values, .. } => {
+            collect_deprecations(format!("{path}{{}}"), values, out)
+        }
+        jtd::Schema::Properties {
+            properties,
+            optional_properties,
+            ..
+        } => {
+            for (name, property) in properties.iter().chain(optional_properties) {
+                collect_deprecations(format!("{path}.{name}"), property, out)
+            }
+        }
+        jtd::Schema::Discriminator { mapping, .. } => {
+            for (tag, value) in mapping {
+                collect_deprecations(format!("{path}.{tag}"), value, out)
+            }
+        }
     }
 }
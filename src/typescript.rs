@@ -1,8 +1,108 @@
 use crate::inflected_string::InflectedString;
+use crate::metadata;
 use color_eyre::Result;
+use doc::{
+    align, broken_group, concat, group, group_with_id, if_break, indent, indent_if_break, join,
+    text, Doc,
+};
 use eyre::{bail, WrapErr};
 use jtd::{Schema, Type};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
+
+pub mod doc;
+
+/// How we declare the `Elm` object that the compiled Elm code exports.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, clap::ValueEnum)]
+pub enum ModuleStyle {
+    // `declare module Elm { ... }`, for loading compiled Elm with a `<script>` tag.
+    #[default]
+    Global,
+
+    // `declare module "*/Main.elm" { ... }` for each module, for bundlers that let you
+    // `import { Elm } from "./Main.elm"`.
+    EsModule,
+}
+
+/// How we declare object types.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, clap::ValueEnum)]
+pub enum DeclarationStyle {
+    // `type Flags = { ... }`
+    #[default]
+    Alias,
+
+    // `interface Flags { ... }` (only for objects: everything else is still a type alias.)
+    Interface,
+}
+
+/// How we represent enums in the runtime helpers. The declarations always use string literal
+/// unions, since they can't create values.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, clap::ValueEnum)]
+pub enum EnumStyle {
+    // just the `all*` list of values
+    #[default]
+    Union,
+
+    // `export enum Permission { Granted = "granted", ... }`
+    Enum,
+
+    // `export const Permission = { Granted: "granted", ... } as const`, plus a type of the same
+    // name for its values.
+    Object,
+}
+
+impl EnumStyle {
+    /// Read the style from `metadata.typescript.enum`, if it's set.
+    pub fn from_metadata(metadata: &jtd::Metadata) -> Result<Option<Self>> {
+        match metadata
+            .get("typescript")
+            .and_then(|typescript| typescript.get("enum"))
+            .map(|style| style.as_str())
+        {
+            None => Ok(None),
+            Some(Some("union")) => Ok(Some(Self::Union)),
+            Some(Some("enum")) => Ok(Some(Self::Enum)),
+            Some(Some("object")) => Ok(Some(Self::Object)),
+            Some(other) => bail!(
+                "`metadata.typescript.enum` should be \"union\", \"enum\", or \"object\", but I got {other:?}"
+            ),
+        }
+    }
+}
+
+/// Everything about how the generated TypeScript looks. None of these change what values are
+/// valid, only how we write the types down. More of these may show up later, so use
+/// `Style::new` or `Style::default()` instead of building one field by field.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[non_exhaustive]
+pub struct Style {
+    /// Whether the `Elm` object is declared globally or as an ES module.
+    pub modules: ModuleStyle,
+
+    /// Whether objects are type aliases or interfaces.
+    pub declarations: DeclarationStyle,
+
+    /// Mark data coming from the schema (flags, port values, and named types) as `readonly`.
+    pub readonly: bool,
+
+    /// How enums show up in the runtime helpers.
+    pub enums: EnumStyle,
+}
+
+impl Style {
+    pub fn new(
+        modules: ModuleStyle,
+        declarations: DeclarationStyle,
+        readonly: bool,
+        enums: EnumStyle,
+    ) -> Self {
+        Self {
+            modules,
+            declarations,
+            readonly,
+            enums,
+        }
+    }
+}
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum TSType {
@@ -33,6 +133,17 @@ pub enum TSType {
         args: BTreeMap<InflectedString, TSType>,
         returning: Box<TSType>,
     },
+    Deprecated {
+        reason: String,
+        type_: Box<TSType>,
+    },
+    Described {
+        description: String,
+        type_: Box<TSType>,
+    },
+    Readonly {
+        type_: Box<TSType>,
+    },
 
     // For the following members, we're making no effort to constrain what's valid where. That's up
     // to our tests!
@@ -40,6 +151,10 @@ pub enum TSType {
         name: InflectedString,
         definition: Box<TSType>,
     },
+    InterfaceDecl {
+        name: InflectedString,
+        definition: Box<TSType>, // in practice, should always be an `Object` (maybe `Readonly`)
+    },
     ModuleDecl {
         name: InflectedString,
         members: Vec<TSType>,
@@ -48,6 +163,10 @@ pub enum TSType {
         name: InflectedString,
         members: Vec<TSType>,
     },
+    AmbientModuleDecl {
+        path: String,
+        members: Vec<TSType>,
+    },
     NamedFunctionDecl {
         name: InflectedString,
         function: Box<TSType>, // in practice, should always be a `Function`
@@ -60,8 +179,367 @@ pub enum FieldPresence {
     Optional,
 }
 
+/// Where a type appears, since prettier lays out unions differently depending on their parent.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Position {
+    Plain,
+    TypeArgument,
+    ArrayElement,
+}
+
+// Only one assignment is ever in progress, so every type declaration can use the same group ID.
+const ASSIGNMENT_GROUP: usize = 0;
+
+fn nullable_doc(doc: Doc, nullable: bool, is_object: bool, position: Position) -> Doc {
+    if nullable {
+        union_doc(Vec::from([doc, text("null")]), is_object, position)
+    } else {
+        doc
+    }
+}
+
+/// `A | B`, or one member per line (with a leading `|`) if that doesn't fit.
+fn union_doc(members: Vec<Doc>, hug: bool, position: Position) -> Doc {
+    if hug {
+        let members = join(text(" | "), members);
+        return if position == Position::ArrayElement {
+            concat(Vec::from([text("("), members, text(")")]))
+        } else {
+            members
+        };
+    }
+
+    let code = concat(Vec::from([
+        if_break(
+            concat(Vec::from([
+                if position == Position::TypeArgument {
+                    Doc::empty()
+                } else {
+                    Doc::Line
+                },
+                text("| "),
+            ])),
+            Doc::empty(),
+        ),
+        join(
+            concat(Vec::from([Doc::Line, text("| ")])),
+            members.into_iter().map(|member| align(2, member)).collect(),
+        ),
+    ]));
+
+    match position {
+        Position::Plain => group(indent(code)),
+        Position::TypeArgument => group(code),
+        Position::ArrayElement => concat(Vec::from([
+            text("("),
+            group(concat(Vec::from([indent(code), Doc::SoftLine]))),
+            text(")"),
+        ])),
+    }
+}
+
+pub fn type_arguments_doc(name: &str, arguments: Vec<Doc>) -> Doc {
+    group(concat(Vec::from([
+        text(format!("{name}<")),
+        indent(concat(Vec::from([
+            Doc::SoftLine,
+            join(concat(Vec::from([text(","), Doc::Line])), arguments),
+        ]))),
+        Doc::SoftLine,
+        text(">"),
+    ])))
+}
+
+/// `(a, b)`, or one parameter per line if that doesn't fit. This doesn't get a group of its own,
+/// since prettier breaks the parameters whenever the whole signature doesn't fit.
+pub fn parameters_doc(params: Vec<Doc>) -> Doc {
+    concat(Vec::from([
+        text("("),
+        indent(concat(Vec::from([
+            Doc::SoftLine,
+            join(concat(Vec::from([text(","), Doc::Line])), params),
+            if_break(text(","), Doc::empty()),
+        ]))),
+        Doc::SoftLine,
+        text(")"),
+    ]))
+}
+
+/// `[a, b]`, or one item per line if that doesn't fit.
+pub fn array_doc(items: Vec<Doc>) -> Doc {
+    group(concat(Vec::from([
+        text("["),
+        indent(concat(Vec::from([
+            Doc::SoftLine,
+            join(concat(Vec::from([text(","), Doc::Line])), items),
+            if_break(text(","), Doc::empty()),
+        ]))),
+        Doc::SoftLine,
+        text("]"),
+    ])))
+}
+
+/// The members of an object type, without a group (so callers can decide whether it should
+/// always be expanded.)
+pub fn object_members_doc(members: Vec<Doc>) -> Doc {
+    if members.is_empty() {
+        return text("{}");
+    }
+
+    concat(Vec::from([
+        text("{"),
+        indent(concat(Vec::from([
+            Doc::Line,
+            join(concat(Vec::from([text(";"), Doc::Line])), members),
+        ]))),
+        if_break(text(";"), Doc::empty()),
+        Doc::Line,
+        text("}"),
+    ]))
+}
+
+/// The same layout prettier uses for assignments: keep the right side on the same line if it can
+/// start there, otherwise indent it on the next one.
+pub fn assignment_doc(left: Doc, right: Doc) -> Doc {
+    group(concat(Vec::from([
+        left,
+        group_with_id(ASSIGNMENT_GROUP, indent(Doc::Line)),
+        indent_if_break(ASSIGNMENT_GROUP, right),
+    ])))
+}
+
+/// Declarations inside `{ ... }`, with a blank line between each.
+fn block_doc(head: String, members: &[TSType]) -> Result<Doc> {
+    if members.is_empty() {
+        return Ok(text(format!("{head} {{}}")));
+    }
+
+    let mut docs = Vec::with_capacity(members.len());
+    for member in members {
+        docs.push(member.to_doc(Position::Plain, false)?);
+    }
+
+    Ok(concat(Vec::from([
+        text(format!("{head} {{")),
+        indent(concat(Vec::from([
+            Doc::HardLine,
+            join(concat(Vec::from([Doc::HardLine, Doc::HardLine])), docs),
+        ]))),
+        Doc::HardLine,
+        text("}"),
+    ])))
+}
+
+fn comment_doc(comment: &str) -> Doc {
+    join(Doc::HardLine, comment.lines().map(text).collect())
+}
+
+/// The name a schema asks to be declared under (its `metadata.name`), if any. Refs never declare
+/// anything themselves, since they point to something that already has a name.
+pub fn declared_name(schema: &Schema) -> Option<&str> {
+    if matches!(schema, Schema::Ref { .. }) {
+        return None;
+    }
+
+    schema.metadata().get("name").and_then(|n| n.as_str())
+}
+
+/// How we write a declared name (from `metadata.name` or a definition key) as a type.
+pub fn type_name(name: &str) -> Result<String> {
+    InflectedString::from(name)
+        .to_pascal_case()
+        .wrap_err_with(|| format!("could not use `{name}` as a type name"))
+}
+
+/// What Elm calls the type a ref points to: the definition's `metadata.name` if it has one, and
+/// otherwise whatever refers to it (so a `newJwt` port that refers to `jwt` gets `NewJwt`.)
+pub fn ref_name(name_suggestion: Option<&str>, ref_: &str, schema: &Schema) -> Result<String> {
+    type_name(declared_name(schema).or(name_suggestion).unwrap_or(ref_))
+}
+
+pub fn is_nullable(schema: &Schema) -> bool {
+    match schema {
+        Schema::Empty { .. } => false,
+        Schema::Ref { nullable, .. }
+        | Schema::Type { nullable, .. }
+        | Schema::Enum { nullable, .. }
+        | Schema::Elements { nullable, .. }
+        | Schema::Properties { nullable, .. }
+        | Schema::Values { nullable, .. }
+        | Schema::Discriminator { nullable, .. } => *nullable,
+    }
+}
+
+/// What a ref points to.
+pub enum RefTarget<'a> {
+    /// A definition next to the ref, which has no name of its own, so we inline it.
+    Inline(&'a Schema),
+
+    /// A top-level definition, which we declare once (see `resolve_definition`) and refer to by
+    /// name.
+    Named,
+}
+
+pub fn resolve_ref<'a>(
+    ref_: &str,
+    definitions: &'a BTreeMap<String, Schema>,
+    globals: &'a BTreeMap<String, Schema>,
+) -> Result<RefTarget<'a>> {
+    if let Some(schema) = definitions.get(ref_) {
+        return Ok(RefTarget::Inline(schema));
+    }
+
+    if !globals.contains_key(ref_) {
+        bail!("could not find a definition for `{ref_}`")
+    }
+
+    Ok(RefTarget::Named)
+}
+
+/// Name a top-level definition like `ref_name` does, and find the schema we should declare
+/// under that name.
+pub fn resolve_definition<'a>(
+    ref_: &str,
+    name_suggestion: Option<&str>,
+    globals: &'a BTreeMap<String, Schema>,
+) -> Result<(String, &'a Schema)> {
+    let Some(mut schema) = globals.get(ref_) else {
+        bail!("could not find a definition for `{ref_}`")
+    };
+    let name = ref_name(name_suggestion, ref_, schema)?;
+
+    // when a definition is only a ref to another one, Elm gives both the same name, so we skip to
+    // the end of the chain instead of declaring that name twice.
+    let mut seen = BTreeSet::from([ref_]);
+    while let Schema::Ref {
+        ref_: next,
+        nullable: false,
+        ..
+    } = schema
+    {
+        match globals.get(next) {
+            Some(next_schema) if seen.insert(next) => schema = next_schema,
+            _ => break,
+        }
+    }
+
+    Ok((name, schema))
+}
+
+/// Types we declare once and refer to by name: definitions, and anything with `metadata.name`.
+/// We name them the same way the Elm side does, so both languages share a vocabulary.
+#[derive(Debug, Default)]
+pub struct NamedTypes {
+    types: BTreeMap<String, TSType>,
+
+    // names we're converting right now, so recursive types can refer to themselves.
+    in_progress: BTreeSet<String>,
+
+    // definitions we're converting right now, and what we called them. Refs are named after
+    // whatever refers to them, so a definition that refers to itself needs to find its name here.
+    refs_in_progress: BTreeMap<String, String>,
+}
+
+impl NamedTypes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn into_decls(self, style: &Style) -> Vec<TSType> {
+        self.types
+            .into_iter()
+            .map(|(name, type_)| type_.into_data(style).into_decl(&name, style))
+            .collect()
+    }
+}
+
 impl TSType {
-    pub fn from_schema(schema: Schema, globals: &BTreeMap<String, Schema>) -> Result<Self> {
+    /// Convert a schema. `name_suggestion` is what Elm would call it if it needs a name (like the
+    /// port or field it's for), which we use for any definitions it refers to.
+    pub fn from_schema(
+        schema: Schema,
+        name_suggestion: Option<&str>,
+        globals: &BTreeMap<String, Schema>,
+        named: &mut NamedTypes,
+    ) -> Result<Self> {
+        if let Some(name) = declared_name(&schema) {
+            let name = type_name(name)?;
+            return Self::from_named_schema(name, schema, name_suggestion, globals, named);
+        }
+
+        Self::from_unnamed_schema(schema, name_suggestion, globals, named)
+    }
+
+    /// Convert a schema, declare it under `name` (from `type_name`), and give back a reference to
+    /// it.
+    fn from_named_schema(
+        name: String,
+        schema: Schema,
+        name_suggestion: Option<&str>,
+        globals: &BTreeMap<String, Schema>,
+        named: &mut NamedTypes,
+    ) -> Result<Self> {
+        let nullable = is_nullable(&schema);
+
+        // we qualify the reference so that it still works inside module namespaces (which have
+        // their own `Flags` and `Ports`.)
+        let reference = Self::TypeRef(format!("Elm.{name}")).into_nullable(nullable);
+
+        if named.in_progress.contains(&name) {
+            return Ok(reference);
+        }
+
+        named.in_progress.insert(name.clone());
+        let mut type_ = Self::from_unnamed_schema(schema, name_suggestion, globals, named)
+            .wrap_err_with(|| format!("could not convert the `{name}` type"))?;
+        named.in_progress.remove(&name);
+        type_.set_nullable(false);
+
+        match named.types.get(&name) {
+            Some(existing) if *existing != type_ => bail!(
+                "there are two different types named `{name}`. Give one of them a different `metadata.name`"
+            ),
+            Some(_) => (),
+            None => {
+                named.types.insert(name, type_);
+            }
+        }
+
+        Ok(reference)
+    }
+
+    /// Declare a definition as a named type (if we haven't already) and give back a reference
+    /// to it. We name it like `ref_name` does.
+    pub fn from_definition(
+        ref_: &str,
+        name_suggestion: Option<&str>,
+        globals: &BTreeMap<String, Schema>,
+        named: &mut NamedTypes,
+    ) -> Result<Self> {
+        let (name, schema) = resolve_definition(ref_, name_suggestion, globals)?;
+        if let Some(name) = named.refs_in_progress.get(ref_) {
+            return Ok(Self::TypeRef(format!("Elm.{name}")).into_nullable(is_nullable(schema)));
+        }
+
+        named.refs_in_progress.insert(ref_.to_owned(), name.clone());
+        let type_ = Self::from_named_schema(
+            name,
+            schema.clone(),
+            Some(name_suggestion.unwrap_or(ref_)),
+            globals,
+            named,
+        );
+        named.refs_in_progress.remove(ref_);
+
+        type_.wrap_err_with(|| format!("could not convert the value of ref `{ref_}`"))
+    }
+
+    fn from_unnamed_schema(
+        schema: Schema,
+        name_suggestion: Option<&str>,
+        globals: &BTreeMap<String, Schema>,
+        named: &mut NamedTypes,
+    ) -> Result<Self> {
         match schema {
             Schema::Properties {
                 properties,
@@ -71,16 +549,26 @@ impl TSType {
             } => {
                 let mut converted_properties = BTreeMap::new();
                 for (name, value) in properties {
-                    let type_ = Self::from_schema(value, globals)
+                    let deprecation = metadata::deprecation(value.metadata());
+                    let mut type_ = Self::from_schema(value, Some(&name), globals, named)
                         .wrap_err_with(|| format!("could not convert the {name} key"))?;
 
+                    if let Some(reason) = deprecation {
+                        type_ = type_.into_deprecated(&reason);
+                    }
+
                     converted_properties.insert(name.into(), (type_, FieldPresence::Required));
                 }
 
                 for (name, value) in optional_properties {
-                    let type_ = Self::from_schema(value, globals)
+                    let deprecation = metadata::deprecation(value.metadata());
+                    let mut type_ = Self::from_schema(value, Some(&name), globals, named)
                         .wrap_err_with(|| format!("could not convert the {name} key"))?;
 
+                    if let Some(reason) = deprecation {
+                        type_ = type_.into_deprecated(&reason);
+                    }
+
                     converted_properties.insert(name.into(), (type_, FieldPresence::Optional));
                 }
 
@@ -107,32 +595,79 @@ impl TSType {
                 nullable,
             }),
             Schema::Enum {
-                enum_, nullable, ..
-            } => Ok(Self::Union {
-                members: enum_.into_iter().map(Self::StringScalar).collect(),
+                metadata,
+                enum_,
                 nullable,
-            }),
+                ..
+            } => {
+                let values = metadata::enum_values(&metadata)?;
+
+                let mut members = Vec::with_capacity(enum_.len());
+                for value in enum_ {
+                    let mut member = Self::StringScalar(value.clone());
+
+                    if let Some(value_metadata) = values.get(&value) {
+                        if let Some(description) =
+                            value_metadata.get("description").and_then(|d| d.as_str())
+                        {
+                            member = member.into_described(description);
+                        }
+
+                        if let Some(reason) = metadata::deprecation(value_metadata) {
+                            member = member.into_deprecated(&reason);
+                        }
+                    }
+
+                    members.push(member);
+                }
+
+                if metadata::is_open(&metadata) {
+                    // `string & {}` accepts any string, but keeps editors suggesting the known
+                    // values (a bare `string` would swallow them.)
+                    members.push(Self::Scalar {
+                        value: "(string & {})",
+                        nullable: false,
+                    });
+                }
+
+                Ok(Self::Union { members, nullable })
+            }
             Schema::Empty { .. } => Ok(Self::NeverObject),
             Schema::Ref {
                 ref_,
                 nullable,
                 definitions,
                 ..
-            } => match definitions.get(&ref_).or_else(|| globals.get(&ref_)) {
-                Some(schema) => {
-                    let mut tstype = Self::from_schema(schema.clone(), globals)
-                        .wrap_err_with(|| format!("could not convert the value of ref `{ref_}`"))?;
+            } => match resolve_ref(&ref_, &definitions, globals)? {
+                RefTarget::Inline(schema) => {
+                    let mut tstype = Self::from_schema(
+                        schema.clone(),
+                        Some(name_suggestion.unwrap_or(&ref_)),
+                        globals,
+                        named,
+                    )
+                    .wrap_err_with(|| format!("could not convert the value of ref `{ref_}`"))?;
                     tstype.set_nullable(nullable);
                     Ok(tstype)
                 }
-                None => bail!("could not find a definition for `{ref_}`"),
+                RefTarget::Named => {
+                    Ok(
+                        Self::from_definition(&ref_, name_suggestion, globals, named)?
+                            .into_nullable(nullable),
+                    )
+                }
             },
             Schema::Elements {
                 elements, nullable, ..
             } => Ok(Self::List {
                 elements: Box::new(
-                    Self::from_schema(*elements, globals)
-                        .wrap_err("could not convert the elements type")?,
+                    Self::from_schema(
+                        *elements,
+                        name_suggestion.map(|n| format!("{n}Elements")).as_deref(),
+                        globals,
+                        named,
+                    )
+                    .wrap_err("could not convert the elements type")?,
                 ),
                 nullable,
             }),
@@ -140,12 +675,18 @@ impl TSType {
                 values, nullable, ..
             } => Ok(Self::Record {
                 values: Box::new(
-                    Self::from_schema(*values, globals)
-                        .wrap_err("could not convert the values type")?,
+                    Self::from_schema(
+                        *values,
+                        name_suggestion.map(|n| format!("{n}Values")).as_deref(),
+                        globals,
+                        named,
+                    )
+                    .wrap_err("could not convert the values type")?,
                 ),
                 nullable,
             }),
             Schema::Discriminator {
+                metadata,
                 discriminator,
                 mapping,
                 nullable,
@@ -154,8 +695,10 @@ impl TSType {
                 let mut members = Vec::with_capacity(mapping.len());
 
                 for (tag, value) in mapping {
-                    let mut value_type = Self::from_schema(value, globals)
-                        .wrap_err_with(|| format!("could not convert the {tag} tag"))?;
+                    // the tag has to go in the object, so we can't refer to it by name.
+                    let mut value_type =
+                        Self::from_unnamed_schema(value, Some(&tag), globals, named)
+                            .wrap_err_with(|| format!("could not convert the {tag} tag"))?;
 
                     // This happens if the payload is empty in a mapping field.
                     if value_type == Self::NeverObject {
@@ -169,22 +712,59 @@ impl TSType {
                     members.push(value_type);
                 }
 
+                if metadata::is_open(&metadata) {
+                    // matches what the zod schema and runtime guards let through, so code
+                    // handling these has to deal with tags it doesn't know about yet.
+                    members.push(Self::new_singleton_object(
+                        &discriminator,
+                        Self::Scalar {
+                            value: "string",
+                            nullable: false,
+                        },
+                        FieldPresence::Required,
+                    ));
+                }
+
                 Ok(Self::Union { members, nullable })
             }
         }
     }
 
-    fn set_nullable(&mut self, new_value: bool) {
+    /// Make a type nullable if it isn't already. (Unlike `set_nullable`, this never makes a type
+    /// non-nullable, and works on references.)
+    fn into_nullable(mut self, nullable: bool) -> Self {
+        if !nullable {
+            return self;
+        }
+
+        match self {
+            Self::TypeRef(_) => Self::Union {
+                members: Vec::from([self]),
+                nullable: true,
+            },
+            _ => {
+                self.set_nullable(true);
+                self
+            }
+        }
+    }
+
+    pub fn set_nullable(&mut self, new_value: bool) {
         match self {
             TSType::Object { nullable, .. } => *nullable = new_value,
             TSType::Record { nullable, .. } => *nullable = new_value,
             TSType::Scalar { nullable, .. } => *nullable = new_value,
             TSType::Union { nullable, .. } => *nullable = new_value,
             TSType::List { nullable, .. } => *nullable = new_value,
+            TSType::Deprecated { type_, .. }
+            | TSType::Described { type_, .. }
+            | TSType::Readonly { type_ } => type_.set_nullable(new_value),
             TSType::Function { .. }
             | TSType::TypeDecl { .. }
+            | TSType::InterfaceDecl { .. }
             | TSType::ModuleDecl { .. }
             | TSType::NamespaceDecl { .. }
+            | TSType::AmbientModuleDecl { .. }
             | TSType::NamedFunctionDecl { .. }
             | TSType::StringScalar(_)
             | TSType::TypeRef(_)
@@ -192,140 +772,245 @@ impl TSType {
         }
     }
 
-    pub fn to_source(&self, is_toplevel: bool) -> Result<String> {
-        let mut out = String::new();
+    pub fn to_source(&self) -> Result<String> {
+        Ok(self.to_type_doc()?.render())
+    }
 
-        match self {
+    /// Lay this out on its own, for building bigger documents around it.
+    pub fn to_type_doc(&self) -> Result<Doc> {
+        self.to_doc(Position::Plain, false)
+    }
+
+    /// `readonly` is true when we're inside a `Readonly` type, and applies to every object, list,
+    /// and record until we get to something that isn't data (like a function.)
+    fn to_doc(&self, position: Position, readonly: bool) -> Result<Doc> {
+        let doc = match self {
             Self::Object {
                 properties,
                 nullable,
-            } => {
-                out.push_str("{\n");
-                for (name, (value, presence)) in properties {
-                    out.push_str("  ");
-                    out.push_str(&name.to_camel_case()?);
-                    if *presence == FieldPresence::Optional {
-                        out.push('?');
-                    }
-                    out.push_str(": ");
-                    out.push_str(&value.to_source(false)?.replace('\n', "\n  "));
-                    out.push_str(";\n");
-                }
-                out.push('}');
-
-                if *nullable {
-                    out.push_str(" | null");
-                }
-            }
-            Self::NeverObject => out.push_str("Record<string, never>"),
+            } => nullable_doc(
+                Self::object_doc(properties, readonly, false)?,
+                *nullable,
+                true,
+                position,
+            ),
+            Self::NeverObject => text("Record<string, never>"),
             Self::Record { values, nullable } => {
-                out.push_str("Record<string, ");
-                out.push_str(&values.to_source(false)?);
-                out.push('>');
-
-                if *nullable {
-                    out.push_str(" | null");
+                let mut record = type_arguments_doc(
+                    "Record",
+                    Vec::from([
+                        text("string"),
+                        values.to_doc(Position::TypeArgument, readonly)?,
+                    ]),
+                );
+                if readonly {
+                    record = type_arguments_doc("Readonly", Vec::from([record]));
                 }
+
+                nullable_doc(record, *nullable, false, position)
             }
             Self::Scalar { value, nullable } => {
-                out.push_str(value);
-                if *nullable {
-                    out.push_str(" | null");
-                }
+                nullable_doc(text(*value), *nullable, false, position)
             }
-            Self::StringScalar(string) => {
-                out.push('"');
-                out.push_str(string);
-                out.push('"');
-            }
-            Self::TypeRef(ref_) => out.push_str(ref_),
+            Self::StringScalar(string) => text(serde_json::to_string(string)?),
+            Self::TypeRef(ref_) => text(ref_.as_str()),
             Self::Union { members, nullable } => {
-                for (i, type_) in members.iter().enumerate() {
-                    if i != 0 {
-                        out.push_str(" | ");
-                    }
-                    out.push_str(&type_.to_source(false)?)
+                let mut docs = Vec::with_capacity(members.len() + 1);
+                for member in members {
+                    let (comment, member) = member.split_docs();
+                    let member_doc = member.to_doc(Position::Plain, readonly)?;
+
+                    docs.push(match comment {
+                        Some(comment) => {
+                            concat(Vec::from([comment_doc(&comment), text(" "), member_doc]))
+                        }
+                        None => member_doc,
+                    });
                 }
-
                 if *nullable {
-                    out.push_str(" | null")
+                    docs.push(text("null"));
                 }
-            }
-            Self::List { elements, nullable } => {
-                let elements_source = elements.to_source(false)?;
 
-                if elements_source.contains(' ') {
-                    out.push('(');
-                    out.push_str(&elements_source);
-                    out.push_str(")[]");
-                } else {
-                    out.push_str(&elements_source);
-                    out.push_str("[]");
+                // a union of one thing is just that thing once it's parsed
+                if docs.len() == 1 {
+                    return Ok(docs.remove(0));
                 }
 
-                if *nullable {
-                    out.push_str(" | null");
-                }
+                // `{ ... } | null` stays together instead of getting a line per member
+                let hug = *nullable
+                    && members.len() == 1
+                    && members[0].is_object_literal()
+                    && members[0].split_docs().0.is_none();
+
+                union_doc(docs, hug, position)
             }
-            Self::Function { args, returning } => {
-                out.push('(');
-                for (i, (name, type_)) in args.iter().enumerate() {
-                    if i != 0 {
-                        out.push_str(", ");
+            Self::List { elements, nullable } => {
+                let mut list = concat(Vec::from([
+                    elements.to_doc(Position::ArrayElement, readonly)?,
+                    text("[]"),
+                ]));
+                if readonly {
+                    list = concat(Vec::from([text("readonly "), list]));
+
+                    // `readonly` binds looser than `[]`, so lists of read-only lists need parens
+                    if position == Position::ArrayElement && !*nullable {
+                        list = concat(Vec::from([text("("), list, text(")")]));
                     }
-                    out.push_str(&name.to_camel_case()?);
-                    out.push_str(": ");
-                    out.push_str(&type_.to_source(false)?);
                 }
-                if is_toplevel {
-                    out.push_str("): ");
+
+                nullable_doc(list, *nullable, false, position)
+            }
+            Self::Function { args, returning } => {
+                let function = Self::function_doc(args, returning, false)?;
+
+                if position == Position::ArrayElement {
+                    concat(Vec::from([text("("), function, text(")")]))
                 } else {
-                    out.push_str(") => ");
+                    function
                 }
-                out.push_str(&returning.to_source(false)?);
             }
-            Self::TypeDecl { name, definition } => {
-                out.push_str("type ");
-                out.push_str(&name.to_pascal_case()?);
-                out.push_str(" = ");
-                out.push_str(&definition.to_source(false)?);
-            }
-            Self::ModuleDecl { name, members } => {
-                out.push_str("declare module ");
-                out.push_str(&name.to_pascal_case()?);
-                out.push_str(" {\n");
-                for (i, member) in members.iter().enumerate() {
-                    if i > 0 {
-                        out.push('\n');
-                    }
-                    out.push_str("  ");
-                    out.push_str(&member.to_source(true)?.replace('\n', "\n  "));
-                    out.push('\n');
-                }
-                out.push('}');
+            Self::Deprecated { type_, .. } | Self::Described { type_, .. } => {
+                type_.to_doc(position, readonly)?
             }
+            Self::Readonly { type_ } => type_.to_doc(position, true)?,
+            Self::TypeDecl { name, definition } => concat(Vec::from([
+                assignment_doc(
+                    text(format!("type {} =", name.to_pascal_case()?)),
+                    definition.to_doc(Position::Plain, false)?,
+                ),
+                text(";"),
+            ])),
+            Self::InterfaceDecl { name, definition } => concat(Vec::from([
+                text(format!("interface {} ", name.to_pascal_case()?)),
+                definition.to_doc(Position::Plain, false)?,
+            ])),
+            Self::ModuleDecl { name, members } => block_doc(
+                format!("declare module {}", name.to_pascal_case()?),
+                members,
+            )?,
             Self::NamespaceDecl { name, members } => {
-                out.push_str("namespace ");
-                out.push_str(&name.to_pascal_case()?);
-                out.push_str(" {\n");
-                for (i, member) in members.iter().enumerate() {
-                    if i > 0 {
-                        out.push('\n');
-                    }
-                    out.push_str("  ");
-                    out.push_str(&member.to_source(true)?.replace('\n', "\n  "));
-                    out.push('\n');
-                }
-                out.push('}');
+                block_doc(format!("namespace {}", name.to_pascal_case()?), members)?
+            }
+            Self::AmbientModuleDecl { path, members } => block_doc(
+                format!("declare module {}", serde_json::to_string(path)?),
+                members,
+            )?,
+            Self::NamedFunctionDecl { name, function } => match function.as_ref() {
+                Self::Function { args, returning } => concat(Vec::from([
+                    text(format!("function {}", name.to_camel_case()?)),
+                    Self::function_doc(args, returning, true)?,
+                    text(";"),
+                ])),
+                _ => bail!("named functions have to be functions"),
+            },
+        };
+
+        Ok(doc)
+    }
+
+    fn object_doc(
+        properties: &BTreeMap<InflectedString, (TSType, FieldPresence)>,
+        readonly: bool,
+        hugged: bool,
+    ) -> Result<Doc> {
+        if properties.is_empty() {
+            return Ok(text("{}"));
+        }
+
+        let mut members = Vec::with_capacity(properties.len());
+        for (name, (value, presence)) in properties {
+            let (comment, value) = value.split_docs();
+
+            let mut member = Vec::with_capacity(4);
+            if let Some(comment) = comment {
+                member.push(comment_doc(&comment));
+                member.push(Doc::HardLine);
             }
-            Self::NamedFunctionDecl { name, function } => {
-                out.push_str("function ");
-                out.push_str(&name.to_camel_case()?);
-                out.push_str(&function.to_source(true)?);
+            member.push(text(format!(
+                "{}{}{}: ",
+                if readonly { "readonly " } else { "" },
+                name.to_camel_case()?,
+                if *presence == FieldPresence::Optional {
+                    "?"
+                } else {
+                    ""
+                },
+            )));
+            member.push(value.to_doc(Position::Plain, readonly)?);
+
+            members.push(concat(member));
+        }
+
+        let contents = object_members_doc(members);
+
+        // Prettier keeps objects expanded if they were written that way, so we get to choose. We
+        // always expand them, except when they're the only argument to a function (prettier
+        // doesn't give those a group of their own, so they collapse if the signature fits.)
+        if hugged {
+            Ok(contents)
+        } else {
+            Ok(broken_group(contents))
+        }
+    }
+
+    fn function_doc(
+        args: &BTreeMap<InflectedString, TSType>,
+        returning: &TSType,
+        is_declaration: bool,
+    ) -> Result<Doc> {
+        let hug = args.len() == 1 && args.values().all(|type_| type_.is_object_literal());
+
+        let mut params = Vec::with_capacity(args.len());
+        for (name, type_) in args {
+            params.push(concat(Vec::from([
+                text(format!("{}: ", name.to_camel_case()?)),
+                if hug {
+                    type_.hugged_doc(false)?
+                } else {
+                    type_.to_doc(Position::Plain, false)?
+                },
+            ])));
+        }
+
+        let params = if hug || params.is_empty() {
+            concat(Vec::from([text("("), concat(params), text(")")]))
+        } else {
+            parameters_doc(params)
+        };
+
+        let returns = concat(Vec::from([
+            text(if is_declaration { ": " } else { " => " }),
+            returning.to_doc(Position::Plain, false)?,
+        ]));
+
+        let group_params = hug && (returning.is_object_literal() || returns.will_break());
+
+        Ok(group(concat(Vec::from([
+            if group_params { group(params) } else { params },
+            returns,
+        ]))))
+    }
+
+    /// An object as the only argument to a function, without a group of its own.
+    fn hugged_doc(&self, readonly: bool) -> Result<Doc> {
+        match self {
+            Self::Object { properties, .. } => Self::object_doc(properties, readonly, true),
+            Self::Deprecated { type_, .. } | Self::Described { type_, .. } => {
+                type_.hugged_doc(readonly)
             }
+            Self::Readonly { type_ } => type_.hugged_doc(true),
+            _ => self.to_doc(Position::Plain, readonly),
         }
+    }
 
-        Ok(out)
+    fn is_object_literal(&self) -> bool {
+        match self {
+            Self::Object { nullable, .. } => !nullable,
+            Self::Deprecated { type_, .. }
+            | Self::Described { type_, .. }
+            | Self::Readonly { type_ } => type_.is_object_literal(),
+            _ => false,
+        }
     }
 
     pub fn new_object(properties: BTreeMap<&str, (TSType, FieldPresence)>) -> Self {
@@ -435,6 +1120,13 @@ impl TSType {
         }
     }
 
+    pub fn new_ambient_module(path: &str, members: Vec<Self>) -> Self {
+        Self::AmbientModuleDecl {
+            path: path.to_owned(),
+            members,
+        }
+    }
+
     pub fn new_named_function(name: &str, function: TSType) -> Self {
         Self::NamedFunctionDecl {
             name: name.into(),
@@ -446,18 +1138,114 @@ impl TSType {
         Self::new_init(self)
     }
 
+    pub fn into_deprecated(self, reason: &str) -> Self {
+        Self::Deprecated {
+            reason: reason.to_owned(),
+            type_: Box::new(self),
+        }
+    }
+
+    pub fn into_described(self, description: &str) -> Self {
+        Self::Described {
+            description: description.to_owned(),
+            type_: Box::new(self),
+        }
+    }
+
+    /// Take the documentation off a type (if there is any) and render it as a TSDoc comment, so
+    /// we can put it in front of the property or union member that has the type.
+    fn split_docs(&self) -> (Option<String>, &TSType) {
+        let mut lines = Vec::new();
+        let mut tags = Vec::new();
+        let mut type_ = self;
+
+        loop {
+            match type_ {
+                Self::Described {
+                    description,
+                    type_: inner,
+                } => {
+                    lines.push(description.clone());
+                    type_ = inner;
+                }
+                Self::Deprecated {
+                    reason,
+                    type_: inner,
+                } => {
+                    if reason.is_empty() {
+                        tags.push(String::from("@deprecated"));
+                    } else {
+                        tags.push(format!("@deprecated {reason}"));
+                    }
+                    type_ = inner;
+                }
+                _ => break,
+            }
+        }
+
+        // TSDoc wants the description before any tags
+        lines.extend(tags);
+
+        match lines.len() {
+            0 => (None, type_),
+            1 => (Some(format!("/** {} */", lines[0])), type_),
+            _ => (Some(format!("/**\n * {}\n */", lines.join("\n * "))), type_),
+        }
+    }
+
     pub fn into_typedecl(self, name: &str) -> Self {
         Self::TypeDecl {
             name: name.into(),
             definition: Box::from(self),
         }
     }
+
+    /// Declare this type under `name`, as an interface if the style asks for one and we can.
+    pub fn into_decl(self, name: &str, style: &Style) -> Self {
+        let is_object = match &self {
+            Self::Readonly { type_ } => matches!(
+                **type_,
+                Self::Object {
+                    nullable: false,
+                    ..
+                }
+            ),
+            type_ => matches!(
+                type_,
+                Self::Object {
+                    nullable: false,
+                    ..
+                }
+            ),
+        };
+
+        if style.declarations == DeclarationStyle::Interface && is_object {
+            Self::InterfaceDecl {
+                name: name.into(),
+                definition: Box::from(self),
+            }
+        } else {
+            self.into_typedecl(name)
+        }
+    }
+
+    /// Mark a type that holds data from the schema as readonly, if the style asks for it.
+    pub fn into_data(self, style: &Style) -> Self {
+        if style.readonly {
+            Self::Readonly {
+                type_: Box::new(self),
+            }
+        } else {
+            self
+        }
+    }
 }
 
 #[derive(Debug)]
 pub enum NamespaceBuilder {
     Root {
         name: String,
+        members: Vec<TSType>,
         below: BTreeMap<String, NamespaceBuilder>,
     },
     Branch {
@@ -471,6 +1259,7 @@ impl NamespaceBuilder {
     pub fn root(name: &str) -> Self {
         Self::Root {
             name: name.to_owned(),
+            members: Vec::new(),
             below: BTreeMap::new(),
         }
     }
@@ -493,39 +1282,65 @@ impl NamespaceBuilder {
         }
 
         match here {
-            Self::Root { .. } => {
-                eyre::bail!("path provided led to a Root namespace builder. Can't insert!")
-            }
-            Self::Branch { members, .. } => members.push(value),
+            Self::Root { members, .. } | Self::Branch { members, .. } => members.push(value),
         }
 
         Ok(())
     }
 
+    /// Like `into_tstype`, but the root becomes a plain namespace instead of a global module
+    /// declaration (for putting inside an ambient module.)
+    pub fn into_namespace(self) -> TSType {
+        match self {
+            Self::Root {
+                name,
+                members,
+                below,
+            } => TSType::new_namespace(&name, Self::members_and_below(members, below)),
+            branch => branch.into_tstype(),
+        }
+    }
+
     pub fn into_tstype(self) -> TSType {
         match self {
-            Self::Root { name, below } => TSType::new_module(
-                &name,
-                below.into_values().map(|v| v.into_tstype()).collect(),
-            ),
+            Self::Root {
+                name,
+                members,
+                below,
+            } => TSType::new_module(&name, Self::members_and_below(members, below)),
             Self::Branch {
                 name,
                 members,
                 below,
-            } => {
-                let mut ts_members = Vec::with_capacity(members.len() + below.len());
-                ts_members.extend(members);
-                ts_members.extend(
-                    below
-                        .into_values()
-                        .map(|v| v.into_tstype())
-                        .collect::<Vec<TSType>>(),
-                );
-
-                TSType::new_namespace(&name, ts_members)
-            }
+            } => TSType::new_namespace(&name, Self::members_and_below(members, below)),
         }
     }
+
+    fn members_and_below(
+        members: Vec<TSType>,
+        below: BTreeMap<String, NamespaceBuilder>,
+    ) -> Vec<TSType> {
+        let mut ts_members = Vec::with_capacity(members.len() + below.len());
+        ts_members.extend(members);
+        ts_members.extend(below.into_values().map(|v| v.into_tstype()));
+
+        ts_members
+    }
+}
+
+/// Object keys can go in bare if they're valid identifiers, but need quotes otherwise.
+pub fn object_key(name: &str) -> String {
+    let mut chars = name.chars();
+    let is_identifier = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+
+    if is_identifier {
+        name.to_owned()
+    } else {
+        serde_json::to_string(name).unwrap_or_else(|_| format!("{name:?}"))
+    }
 }
 
 #[cfg(test)]
@@ -539,8 +1354,13 @@ mod tests {
     }
 
     fn from_schema(value: Value) -> TSType {
-        TSType::from_schema(from_json(value), &BTreeMap::new())
-            .expect("valid schema from JSON value")
+        TSType::from_schema(
+            from_json(value),
+            None,
+            &BTreeMap::new(),
+            &mut NamedTypes::new(),
+        )
+        .expect("valid schema from JSON value")
     }
 
     #[test]
@@ -635,14 +1455,14 @@ mod tests {
     fn interprets_string() {
         let type_ = from_schema(json!({"type": "string"}));
 
-        assert_eq!(type_.to_source(true).unwrap(), "string".to_string())
+        assert_eq!(type_.to_source().unwrap(), "string".to_string())
     }
 
     #[test]
     fn interprets_boolean() {
         let type_ = from_schema(json!({"type": "boolean"}));
 
-        assert_eq!(type_.to_source(true).unwrap(), "boolean".to_string())
+        assert_eq!(type_.to_source().unwrap(), "boolean".to_string())
     }
 
     #[test]
@@ -653,10 +1473,7 @@ mod tests {
             }
         }));
 
-        assert_eq!(
-            type_.to_source(true).unwrap(),
-            "{\n  a: number;\n}".to_string()
-        )
+        assert_eq!(type_.to_source().unwrap(), "{\n  a: number;\n}".to_string())
     }
 
     #[test]
@@ -668,16 +1485,78 @@ mod tests {
         }));
 
         assert_eq!(
-            type_.to_source(true).unwrap(),
+            type_.to_source().unwrap(),
             "{\n  a?: number;\n}".to_string()
         )
     }
 
+    #[test]
+    fn interprets_deprecated_properties() {
+        let type_ = from_schema(json!({
+            "properties": {
+                "a": {
+                    "metadata": { "deprecated": "use b instead" },
+                    "type": "float32"
+                },
+                "b": { "type": "float32" }
+            }
+        }));
+
+        assert_eq!(
+            type_.to_source().unwrap(),
+            "{\n  /** @deprecated use b instead */\n  a: number;\n  b: number;\n}".to_string()
+        )
+    }
+
     #[test]
     fn interprets_enum() {
         let type_ = from_schema(json!({"enum": ["a", "b"]}));
 
-        assert_eq!(type_.to_source(true).unwrap(), "\"a\" | \"b\"".to_string())
+        assert_eq!(type_.to_source().unwrap(), "\"a\" | \"b\"".to_string())
+    }
+
+    #[test]
+    fn interprets_open_enum() {
+        let type_ = from_schema(json!({"metadata": {"open": true}, "enum": ["a", "b"]}));
+
+        assert_eq!(
+            type_.to_source().unwrap(),
+            "\"a\" | \"b\" | (string & {})".to_string()
+        )
+    }
+
+    #[test]
+    fn interprets_open_discriminator() {
+        let type_ = from_schema(json!({
+            "metadata": {"open": true},
+            "discriminator": "tag",
+            "mapping": {
+                "one": {"properties": {}},
+            }
+        }));
+
+        assert_eq!(
+            type_.to_source().unwrap(),
+            "\n  | {\n      tag: \"one\";\n    }\n  | {\n      tag: string;\n    }".to_string()
+        )
+    }
+
+    #[test]
+    fn interprets_enum_value_metadata() {
+        let type_ = from_schema(json!({
+            "metadata": {
+                "values": {
+                    "a": {"description": "the first one"},
+                    "b": {"description": "the second one", "deprecated": "use a"},
+                },
+            },
+            "enum": ["a", "b", "c"],
+        }));
+
+        assert_eq!(
+            type_.to_source().unwrap(),
+            "\n  | /** the first one */ \"a\"\n  | /**\n     * the second one\n     * @deprecated use a\n     */ \"b\"\n  | \"c\"".to_string()
+        )
     }
 
     #[test]
@@ -828,26 +1707,110 @@ mod tests {
         let ref_schema = from_json(json!({"ref": "foo"}));
         let def_schema = from_json(json!({"type": "string"}));
 
+        let mut named = NamedTypes::new();
         let type_ = TSType::from_schema(
             ref_schema,
+            None,
             &BTreeMap::from([("foo".to_string(), def_schema)]),
+            &mut named,
         )
         .unwrap();
 
+        assert_eq!(type_, TSType::TypeRef("Elm.Foo".to_string()));
         assert_eq!(
-            type_,
-            TSType::Scalar {
+            named.into_decls(&Style::default()),
+            Vec::from([TSType::Scalar {
                 value: "string",
                 nullable: false,
             }
+            .into_typedecl("Foo")])
+        );
+    }
+
+    #[test]
+    fn interprets_ref_global_nullable() {
+        let ref_schema = from_json(json!({"ref": "foo", "nullable": true}));
+        let def_schema = from_json(json!({"type": "string"}));
+
+        let type_ = TSType::from_schema(
+            ref_schema,
+            None,
+            &BTreeMap::from([("foo".to_string(), def_schema)]),
+            &mut NamedTypes::new(),
+        )
+        .unwrap();
+
+        assert_eq!(type_.to_source().unwrap(), "Elm.Foo | null");
+    }
+
+    #[test]
+    fn interprets_recursive_ref() {
+        let ref_schema = from_json(json!({"ref": "tree"}));
+        let def_schema = from_json(json!({
+            "properties": {"children": {"elements": {"ref": "tree"}}},
+        }));
+
+        let mut named = NamedTypes::new();
+        TSType::from_schema(
+            ref_schema,
+            None,
+            &BTreeMap::from([("tree".to_string(), def_schema)]),
+            &mut named,
+        )
+        .unwrap();
+
+        assert_eq!(
+            named.into_decls(&Style::default())[0].to_source().unwrap(),
+            "type Tree = {\n  children: Elm.Tree[];\n};"
+        );
+    }
+
+    #[test]
+    fn interprets_metadata_name() {
+        let mut named = NamedTypes::new();
+        let type_ = TSType::from_schema(
+            from_json(json!({
+                "metadata": {"name": "permission"},
+                "enum": ["a", "b"],
+                "nullable": true,
+            })),
+            None,
+            &BTreeMap::new(),
+            &mut named,
+        )
+        .unwrap();
+
+        assert_eq!(type_.to_source().unwrap(), "Elm.Permission | null");
+        assert_eq!(
+            named.into_decls(&Style::default())[0].to_source().unwrap(),
+            "type Permission = \"a\" | \"b\";"
         );
     }
 
+    #[test]
+    fn rejects_conflicting_names() {
+        let err = TSType::from_schema(
+            from_json(json!({
+                "properties": {
+                    "a": {"metadata": {"name": "Same"}, "enum": ["a"]},
+                    "b": {"metadata": {"name": "Same"}, "enum": ["b"]},
+                },
+            })),
+            None,
+            &BTreeMap::new(),
+            &mut NamedTypes::new(),
+        )
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "could not convert the b key");
+    }
+
     #[test]
     fn interprets_ref_missing_definition() {
         let ref_schema = from_json(json!({"ref": "foo"}));
 
-        let err = TSType::from_schema(ref_schema, &BTreeMap::new()).unwrap_err();
+        let err = TSType::from_schema(ref_schema, None, &BTreeMap::new(), &mut NamedTypes::new())
+            .unwrap_err();
 
         assert_eq!(
             err.to_string(),
@@ -859,44 +1822,150 @@ mod tests {
     fn scalar_to_source() {
         let type_ = from_schema(json!({"type": "string"}));
 
-        assert_eq!(type_.to_source(true).unwrap(), "string".to_string());
+        assert_eq!(type_.to_source().unwrap(), "string".to_string());
     }
 
     #[test]
     fn nullable_scalar_to_source() {
         let type_ = from_schema(json!({"type": "string", "nullable": true}));
 
-        assert_eq!(type_.to_source(true).unwrap(), "string | null".to_string());
+        assert_eq!(type_.to_source().unwrap(), "string | null".to_string());
+    }
+
+    #[test]
+    fn readonly_to_source() {
+        let style = Style {
+            readonly: true,
+            ..Style::default()
+        };
+        let type_ = from_schema(json!({
+            "properties": {
+                "tags": {"elements": {"type": "string", "nullable": true}},
+                "scores": {"values": {"elements": {"type": "float64"}}},
+            },
+        }))
+        .into_data(&style);
+
+        assert_eq!(
+            type_.to_source().unwrap(),
+            "{\n  readonly scores: Readonly<Record<string, readonly number[]>>;\n  readonly tags: readonly (string | null)[];\n}"
+        );
+    }
+
+    #[test]
+    fn interface_decl_to_source() {
+        let style = Style {
+            declarations: DeclarationStyle::Interface,
+            ..Style::default()
+        };
+
+        assert_eq!(
+            from_schema(json!({"properties": {"a": {"type": "string"}}}))
+                .into_decl("Flags", &style)
+                .to_source()
+                .unwrap(),
+            "interface Flags {\n  a: string;\n}"
+        );
+
+        // interfaces can only describe objects
+        assert_eq!(
+            from_schema(json!({"properties": {}, "nullable": true}))
+                .into_decl("Flags", &style)
+                .to_source()
+                .unwrap(),
+            "type Flags = {} | null;"
+        );
+    }
+
+    #[test]
+    fn named_function_to_source() {
+        let type_ = TSType::new_named_function(
+            "go",
+            TSType::new_function(
+                BTreeMap::from([
+                    (
+                        "one",
+                        TSType::Scalar {
+                            value: "number",
+                            nullable: false,
+                        },
+                    ),
+                    (
+                        "two",
+                        TSType::Scalar {
+                            value: "string",
+                            nullable: false,
+                        },
+                    ),
+                ]),
+                TSType::Scalar {
+                    value: "string",
+                    nullable: false,
+                },
+            ),
+        );
+
+        assert_eq!(
+            type_.to_source().unwrap(),
+            "function go(one: number, two: string): string;".to_string()
+        )
     }
 
     #[test]
-    fn function_to_source_toplevel() {
+    fn function_hugs_its_only_object_argument() {
         let type_ = TSType::new_function(
-            BTreeMap::from([
-                (
-                    "one",
-                    TSType::Scalar {
-                        value: "number",
-                        nullable: false,
-                    },
-                ),
-                (
-                    "two",
+            BTreeMap::from([(
+                "value",
+                TSType::new_singleton_object(
+                    "a",
                     TSType::Scalar {
                         value: "string",
                         nullable: false,
                     },
+                    FieldPresence::Required,
                 ),
+            )]),
+            TSType::new_void(),
+        );
+
+        assert_eq!(
+            type_.to_source().unwrap(),
+            "(value: { a: string }) => void".to_string()
+        )
+    }
+
+    #[test]
+    fn function_breaks_long_arguments() {
+        let long_name = "Elm.Some.Very.Long.Module.Name.TypeName";
+        let type_ = TSType::new_function(
+            BTreeMap::from([
+                ("one", TSType::TypeRef(long_name.to_string())),
+                ("two", TSType::TypeRef(long_name.to_string())),
             ]),
-            TSType::Scalar {
-                value: "string",
-                nullable: false,
-            },
+            TSType::new_void(),
         );
 
         assert_eq!(
-            type_.to_source(true).unwrap(),
-            "(one: number, two: string): string".to_string()
+            type_.to_source().unwrap(),
+            format!("(\n  one: {long_name},\n  two: {long_name},\n) => void")
+        )
+    }
+
+    #[test]
+    fn list_of_unions_to_source() {
+        let type_ = from_schema(json!({
+            "elements": {
+                "discriminator": "tag",
+                "mapping": {
+                    "a": {"properties": {}},
+                    "b": {"properties": {}},
+                },
+            },
+        }));
+
+        assert_eq!(
+            type_.to_source().unwrap(),
+            "(\n  | {\n      tag: \"a\";\n    }\n  | {\n      tag: \"b\";\n    }\n)[]".to_string()
         )
     }
 
@@ -926,7 +1995,7 @@ mod tests {
         );
 
         assert_eq!(
-            type_.to_source(false).unwrap(),
+            type_.to_source().unwrap(),
             "(one: number, two: string) => string".to_string()
         )
     }
@@ -937,8 +2006,8 @@ mod tests {
             from_schema(json!({"properties": {"a": {"type": "string"}}})).into_typedecl("Flags");
 
         assert_eq!(
-            type_.to_source(true).unwrap(),
-            "type Flags = {\n  a: string;\n}".to_string(),
+            type_.to_source().unwrap(),
+            "type Flags = {\n  a: string;\n};".to_string(),
         )
     }
 
@@ -956,8 +2025,8 @@ mod tests {
         );
 
         assert_eq!(
-            method.to_source(true).unwrap(),
-            "function init(): void".to_string()
+            method.to_source().unwrap(),
+            "function init(): void;".to_string()
         );
     }
 
@@ -969,8 +2038,34 @@ mod tests {
         );
 
         assert_eq!(
-            namespace.to_source(true).unwrap(),
-            "declare module Elm {\n  namespace Main {\n  }\n}".to_string()
+            namespace.to_source().unwrap(),
+            "declare module Elm {\n  namespace Main {}\n}".to_string()
+        );
+    }
+
+    #[test]
+    fn ambient_module_to_source() {
+        let module = TSType::new_ambient_module(
+            "*/Main.elm",
+            Vec::from([TSType::new_namespace("Elm", Vec::new())]),
+        );
+
+        assert_eq!(
+            module.to_source().unwrap(),
+            "declare module \"*/Main.elm\" {\n  namespace Elm {}\n}".to_string()
+        );
+    }
+
+    #[test]
+    fn namespace_builder_into_namespace() {
+        let mut builder = NamespaceBuilder::root("Elm");
+        builder
+            .insert(&["Main"], TSType::new_void().into_typedecl("Flags"))
+            .unwrap();
+
+        assert_eq!(
+            builder.into_namespace().to_source().unwrap(),
+            "namespace Elm {\n  namespace Main {\n    type Flags = void;\n  }\n}".to_string()
         );
     }
 
@@ -979,8 +2074,8 @@ mod tests {
         let namespace = TSType::new_namespace("Main", Vec::from([]));
 
         assert_eq!(
-            namespace.to_source(true).unwrap(),
-            "namespace Main {\n}".to_string()
+            namespace.to_source().unwrap(),
+            "namespace Main {}".to_string()
         );
     }
 
@@ -988,17 +2083,14 @@ mod tests {
     fn list_to_source() {
         let type_ = from_schema(json!({"elements": {"type": "string"}}));
 
-        assert_eq!(type_.to_source(true).unwrap(), "string[]".to_string());
+        assert_eq!(type_.to_source().unwrap(), "string[]".to_string());
     }
 
     #[test]
     fn list_to_source_space() {
         let type_ = from_schema(json!({"elements": {"enum": ["a", "b"]}}));
 
-        assert_eq!(
-            type_.to_source(true).unwrap(),
-            "(\"a\" | \"b\")[]".to_string()
-        );
+        assert_eq!(type_.to_source().unwrap(), "(\"a\" | \"b\")[]".to_string());
     }
 
     #[test]
@@ -1006,7 +2098,7 @@ mod tests {
         let type_ = from_schema(json!({"values": {"type": "string"}}));
 
         assert_eq!(
-            type_.to_source(true).unwrap(),
+            type_.to_source().unwrap(),
             "Record<string, string>".to_string()
         );
     }
@@ -1034,8 +2126,9 @@ mod tests {
         }));
 
         assert_eq!(
-            type_.to_source(true).unwrap(),
-            "{\n  a: number;\n  tag: \"one\";\n} | {\n  b: string;\n  tag: \"two\";\n}".to_string()
+            type_.to_source().unwrap(),
+            "\n  | {\n      a: number;\n      tag: \"one\";\n    }\n  | {\n      b: string;\n      tag: \"two\";\n    }"
+                .to_string()
         );
     }
 }
@@ -0,0 +1,356 @@
+//! A document printer modelled on prettier's. Instead of writing strings, we describe the layout
+//! we want (groups, indentation, and the places a line could break) and the printer decides which
+//! groups fit in 80 columns. That way generated TypeScript looks the same as if prettier had
+//! formatted it, whether or not prettier is installed.
+
+const PRINT_WIDTH: isize = 80;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Doc {
+    Text(String),
+    Concat(Vec<Doc>),
+
+    /// Print the contents on one line if they fit, otherwise break every `Line` directly inside.
+    Group {
+        contents: Box<Doc>,
+        should_break: bool,
+        id: Option<usize>,
+    },
+    Indent(Box<Doc>),
+    Align(usize, Box<Doc>),
+
+    /// Indent the contents, but only if the group with the given id broke.
+    IndentIfBreak {
+        group_id: usize,
+        contents: Box<Doc>,
+    },
+
+    /// A space, or a newline if the enclosing group breaks.
+    Line,
+
+    /// Nothing, or a newline if the enclosing group breaks.
+    SoftLine,
+
+    /// Always a newline, which also breaks every enclosing group.
+    HardLine,
+    IfBreak {
+        broken: Box<Doc>,
+        flat: Box<Doc>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Flat,
+    Break,
+}
+
+pub fn text(text: impl Into<String>) -> Doc {
+    Doc::Text(text.into())
+}
+
+pub fn concat(docs: Vec<Doc>) -> Doc {
+    Doc::Concat(docs)
+}
+
+pub fn join(separator: Doc, docs: Vec<Doc>) -> Doc {
+    let mut out = Vec::with_capacity(docs.len() * 2);
+    for (i, doc) in docs.into_iter().enumerate() {
+        if i > 0 {
+            out.push(separator.clone());
+        }
+        out.push(doc);
+    }
+    Doc::Concat(out)
+}
+
+/// A group, which breaks if anything inside it has to (like prettier's `propagateBreaks`.)
+pub fn group(contents: Doc) -> Doc {
+    let should_break = contents.will_break();
+    Doc::Group {
+        contents: Box::new(contents),
+        should_break,
+        id: None,
+    }
+}
+
+/// A group that always breaks, for things we want spread over several lines (prettier keeps
+/// objects expanded if they were written that way, so this is how we choose for it.)
+pub fn broken_group(contents: Doc) -> Doc {
+    Doc::Group {
+        contents: Box::new(contents),
+        should_break: true,
+        id: None,
+    }
+}
+
+pub fn group_with_id(id: usize, contents: Doc) -> Doc {
+    let should_break = contents.will_break();
+    Doc::Group {
+        contents: Box::new(contents),
+        should_break,
+        id: Some(id),
+    }
+}
+
+pub fn indent(contents: Doc) -> Doc {
+    Doc::Indent(Box::new(contents))
+}
+
+pub fn align(width: usize, contents: Doc) -> Doc {
+    Doc::Align(width, Box::new(contents))
+}
+
+pub fn indent_if_break(group_id: usize, contents: Doc) -> Doc {
+    Doc::IndentIfBreak {
+        group_id,
+        contents: Box::new(contents),
+    }
+}
+
+pub fn if_break(broken: Doc, flat: Doc) -> Doc {
+    Doc::IfBreak {
+        broken: Box::new(broken),
+        flat: Box::new(flat),
+    }
+}
+
+impl Doc {
+    pub fn empty() -> Self {
+        Doc::Concat(Vec::new())
+    }
+
+    /// Does this document contain something that forces a line break?
+    pub fn will_break(&self) -> bool {
+        match self {
+            Doc::HardLine => true,
+            Doc::Group { should_break, .. } if *should_break => true,
+            Doc::Group { contents, .. }
+            | Doc::Indent(contents)
+            | Doc::Align(_, contents)
+            | Doc::IndentIfBreak { contents, .. } => contents.will_break(),
+            Doc::Concat(docs) => docs.iter().any(|doc| doc.will_break()),
+            Doc::IfBreak { broken, flat } => broken.will_break() || flat.will_break(),
+            Doc::Text(_) | Doc::Line | Doc::SoftLine => false,
+        }
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let mut column: isize = 0;
+        let mut group_modes: Vec<(usize, Mode)> = Vec::new();
+        let mut commands: Vec<(usize, Mode, &Doc)> = Vec::from([(0, Mode::Break, self)]);
+
+        while let Some((indentation, mode, doc)) = commands.pop() {
+            match doc {
+                Doc::Text(text) => {
+                    out.push_str(text);
+                    column += text.chars().count() as isize;
+                }
+                Doc::Concat(docs) => {
+                    for doc in docs.iter().rev() {
+                        commands.push((indentation, mode, doc));
+                    }
+                }
+                Doc::Group {
+                    contents,
+                    should_break,
+                    id,
+                } => {
+                    let group_mode = if *should_break {
+                        Mode::Break
+                    } else if mode == Mode::Flat
+                        || fits((Mode::Flat, contents), &commands, PRINT_WIDTH - column)
+                    {
+                        Mode::Flat
+                    } else {
+                        Mode::Break
+                    };
+
+                    if let Some(id) = id {
+                        group_modes.push((*id, group_mode));
+                    }
+                    commands.push((indentation, group_mode, contents));
+                }
+                Doc::Indent(contents) => commands.push((indentation + 2, mode, contents)),
+                Doc::Align(width, contents) => commands.push((indentation + width, mode, contents)),
+                Doc::IndentIfBreak { group_id, contents } => {
+                    if group_mode(&group_modes, *group_id) == Mode::Break {
+                        commands.push((indentation + 2, mode, contents))
+                    } else {
+                        commands.push((indentation, mode, contents))
+                    }
+                }
+                Doc::IfBreak { broken, flat } => {
+                    if mode == Mode::Break {
+                        commands.push((indentation, mode, broken))
+                    } else {
+                        commands.push((indentation, mode, flat))
+                    }
+                }
+                Doc::Line if mode == Mode::Flat => {
+                    out.push(' ');
+                    column += 1;
+                }
+                Doc::SoftLine if mode == Mode::Flat => (),
+                Doc::Line | Doc::SoftLine | Doc::HardLine => {
+                    let trimmed = out.trim_end_matches([' ', '\t']).len();
+                    out.truncate(trimmed);
+                    out.push('\n');
+                    out.push_str(&" ".repeat(indentation));
+                    column = indentation as isize;
+                }
+            }
+        }
+
+        out
+    }
+}
+
+fn group_mode(group_modes: &[(usize, Mode)], id: usize) -> Mode {
+    group_modes
+        .iter()
+        .rev()
+        .find(|(group_id, _)| *group_id == id)
+        .map_or(Mode::Flat, |(_, mode)| *mode)
+}
+
+/// Would `next` fit in `width` columns, along with whatever comes after it up to the next
+/// possible line break?
+fn fits(next: (Mode, &Doc), rest: &[(usize, Mode, &Doc)], mut width: isize) -> bool {
+    let mut rest_index = rest.len();
+    let mut commands = Vec::from([next]);
+
+    while width >= 0 {
+        let (mode, doc) = match commands.pop() {
+            Some(command) => command,
+            None => {
+                if rest_index == 0 {
+                    return true;
+                }
+                rest_index -= 1;
+                let (_, mode, doc) = rest[rest_index];
+                (mode, doc)
+            }
+        };
+
+        match doc {
+            Doc::Text(text) => width -= text.chars().count() as isize,
+            Doc::Concat(docs) => {
+                for doc in docs.iter().rev() {
+                    commands.push((mode, doc));
+                }
+            }
+            Doc::Group {
+                contents,
+                should_break,
+                ..
+            } => commands.push((if *should_break { Mode::Break } else { mode }, contents)),
+            Doc::Indent(contents)
+            | Doc::Align(_, contents)
+            | Doc::IndentIfBreak { contents, .. } => commands.push((mode, contents)),
+            Doc::IfBreak { broken, flat } => {
+                commands.push((mode, if mode == Mode::Break { broken } else { flat }))
+            }
+            Doc::Line => {
+                if mode == Mode::Break {
+                    return true;
+                }
+                width -= 1;
+            }
+            Doc::SoftLine => {
+                if mode == Mode::Break {
+                    return true;
+                }
+            }
+            Doc::HardLine => return true,
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(names: &[&str]) -> Doc {
+        group(concat(Vec::from([
+            text("("),
+            indent(concat(Vec::from([
+                Doc::SoftLine,
+                join(
+                    concat(Vec::from([text(","), Doc::Line])),
+                    names.iter().map(|name| text(*name)).collect(),
+                ),
+                if_break(text(","), Doc::empty()),
+            ]))),
+            Doc::SoftLine,
+            text(")"),
+        ])))
+    }
+
+    #[test]
+    fn groups_stay_flat_when_they_fit() {
+        assert_eq!(
+            params(&["a: string", "b: number"]).render(),
+            "(a: string, b: number)"
+        );
+    }
+
+    #[test]
+    fn groups_break_when_they_do_not_fit() {
+        let long = "x".repeat(40);
+        assert_eq!(
+            params(&[&long, &long]).render(),
+            format!("(\n  {long},\n  {long},\n)")
+        );
+    }
+
+    #[test]
+    fn hard_lines_break_enclosing_groups() {
+        assert_eq!(
+            group(concat(Vec::from([
+                text("{"),
+                indent(concat(Vec::from([
+                    Doc::Line,
+                    text("a"),
+                    Doc::HardLine,
+                    text("b")
+                ]))),
+                Doc::Line,
+                text("}"),
+            ])))
+            .render(),
+            "{\n  a\n  b\n}"
+        );
+    }
+
+    #[test]
+    fn text_after_a_group_counts_towards_fitting() {
+        let doc = concat(Vec::from([
+            text("x".repeat(70)),
+            params(&["a"]),
+            text(" => void;"),
+        ]));
+
+        assert_eq!(
+            doc.render(),
+            format!("{}(\n  a,\n) => void;", "x".repeat(70))
+        );
+    }
+
+    #[test]
+    fn blank_lines_have_no_trailing_whitespace() {
+        assert_eq!(
+            indent(concat(Vec::from([
+                text("a"),
+                Doc::HardLine,
+                Doc::HardLine,
+                text("b")
+            ])))
+            .render(),
+            "a\n\n  b"
+        );
+    }
+}
@@ -0,0 +1,856 @@
+//! zod schemas, for people who validate with zod instead of (or in addition to) using the
+//! declarations in `typescript`. Types come from the schemas with `z.infer`, so there's only one
+//! source of truth on the TypeScript side.
+
+use crate::metadata;
+use crate::typescript::doc::{broken_group, concat, group, if_break, indent, join, text, Doc};
+use crate::typescript::{
+    array_doc, assignment_doc, declared_name, is_nullable, object_key, parameters_doc,
+    resolve_definition, resolve_ref, type_arguments_doc, type_name, RefTarget,
+};
+use color_eyre::Result;
+use eyre::{bail, WrapErr};
+use jtd::{Schema, Type};
+use std::collections::BTreeMap;
+
+/// We name schemas the same way as the TypeScript declarations (so a definition or anything with
+/// `metadata.name` called `Notification` gets a `NotificationSchema` and a `Notification` type.)
+#[derive(Debug, Default)]
+pub struct Schemas {
+    // (name, expression) pairs, in the order we need to declare them. zod schemas are plain
+    // values, so anything we refer to has to be declared first.
+    decls: Vec<(String, Expr)>,
+
+    // definitions we're converting right now, and what we called them, so that a definition that
+    // refers to itself gets caught by `insert_named` no matter what refers to it.
+    refs_in_progress: BTreeMap<String, String>,
+}
+
+impl Schemas {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declare a schema for every definition. Refs to these from anywhere else point to the
+    /// declared schema instead of repeating it.
+    pub fn insert_definitions(&mut self, globals: &BTreeMap<String, Schema>) -> Result<()> {
+        for ref_ in globals.keys() {
+            self.insert_definition(ref_, None, globals, &mut Vec::new())
+                .wrap_err_with(|| format!("could not convert the `{ref_}` definition"))?;
+        }
+
+        Ok(())
+    }
+
+    /// Declare a definition (named like `TSType::from_definition` names it) and give back a
+    /// reference to it, plus whether the reference should accept `null`.
+    fn insert_definition(
+        &mut self,
+        ref_: &str,
+        name_suggestion: Option<&str>,
+        globals: &BTreeMap<String, Schema>,
+        visiting: &mut Vec<String>,
+    ) -> Result<(Expr, bool)> {
+        let (name, schema) = resolve_definition(ref_, name_suggestion, globals)?;
+        let expression = match self.refs_in_progress.get(ref_) {
+            Some(name) => {
+                let name = name.clone();
+                self.insert_named(name, schema, name_suggestion, globals, visiting)
+            }
+            None => {
+                self.refs_in_progress.insert(ref_.to_owned(), name.clone());
+                let expression = self.insert_named(
+                    name,
+                    schema,
+                    Some(name_suggestion.unwrap_or(ref_)),
+                    globals,
+                    visiting,
+                );
+                self.refs_in_progress.remove(ref_);
+                expression
+            }
+        };
+
+        Ok((expression?, is_nullable(schema)))
+    }
+
+    /// Declare `schema` under `name`. Like `TSType::from_schema`, `name_suggestion` is what Elm
+    /// would call it, which we use to name any definitions it refers to.
+    pub fn insert(
+        &mut self,
+        name: &str,
+        schema: &Schema,
+        name_suggestion: Option<&str>,
+        globals: &BTreeMap<String, Schema>,
+    ) -> Result<()> {
+        let name = type_name(name)?;
+        let expression = self.expression(schema, name_suggestion, globals, &mut Vec::new())?;
+        self.declare(name, expression)
+    }
+
+    /// Declare `schema` under `name` (from `type_name`) and give back a reference to it. We don't
+    /// include the schema's own nullability: that goes on the reference instead, same as the
+    /// TypeScript declarations.
+    fn insert_named(
+        &mut self,
+        name: String,
+        schema: &Schema,
+        name_suggestion: Option<&str>,
+        globals: &BTreeMap<String, Schema>,
+        visiting: &mut Vec<String>,
+    ) -> Result<Expr> {
+        // zod can't infer a type for a schema that refers to itself, so those need a `z.lazy`
+        // schema and a type written by hand.
+        if visiting.contains(&name) {
+            bail!(
+                "`{name}` refers to itself (through {} -> {name}), and zod can't infer types for recursive schemas. Leave out `--zod-dest`, or write this schema by hand with `z.lazy`",
+                visiting.join(" -> ")
+            )
+        }
+
+        visiting.push(name.clone());
+        let (expression, _) = self
+            .unnamed_expression(schema, name_suggestion, globals, visiting)
+            .wrap_err_with(|| format!("could not convert the `{name}` schema"))?;
+        visiting.pop();
+
+        self.declare(name.clone(), expression)?;
+
+        Ok(Expr::Name(format!("{name}Schema")))
+    }
+
+    fn declare(&mut self, name: String, expression: Expr) -> Result<()> {
+        match self.decls.iter().find(|(existing, _)| *existing == name) {
+            Some((_, existing)) if *existing != expression => bail!(
+                "there are two different zod schemas named `{name}Schema`. Give one of them a different `metadata.name`"
+            ),
+            Some(_) => (),
+            None => self.decls.push((name, expression)),
+        }
+
+        Ok(())
+    }
+
+    fn expression(
+        &mut self,
+        schema: &Schema,
+        name_suggestion: Option<&str>,
+        globals: &BTreeMap<String, Schema>,
+        visiting: &mut Vec<String>,
+    ) -> Result<Expr> {
+        let (expression, nullable) =
+            self.expression_without_null(schema, name_suggestion, globals, visiting)?;
+
+        if nullable {
+            Ok(expression.call("nullable", Vec::new()))
+        } else {
+            Ok(expression)
+        }
+    }
+
+    /// The expression for a schema, and whether it should also accept `null`.
+    fn expression_without_null(
+        &mut self,
+        schema: &Schema,
+        name_suggestion: Option<&str>,
+        globals: &BTreeMap<String, Schema>,
+        visiting: &mut Vec<String>,
+    ) -> Result<(Expr, bool)> {
+        match declared_name(schema) {
+            Some(name) => Ok((
+                self.insert_named(type_name(name)?, schema, name_suggestion, globals, visiting)?,
+                is_nullable(schema),
+            )),
+            None => self.unnamed_expression(schema, name_suggestion, globals, visiting),
+        }
+    }
+
+    /// The expression for a schema, ignoring its `metadata.name`, and whether it's nullable.
+    fn unnamed_expression(
+        &mut self,
+        schema: &Schema,
+        name_suggestion: Option<&str>,
+        globals: &BTreeMap<String, Schema>,
+        visiting: &mut Vec<String>,
+    ) -> Result<(Expr, bool)> {
+        Ok(match schema {
+            Schema::Empty { .. } => (z("unknown", Vec::new()), false),
+            Schema::Ref {
+                ref_,
+                nullable,
+                definitions,
+                ..
+            } => match resolve_ref(ref_, definitions, globals)? {
+                RefTarget::Inline(local) => {
+                    let (expression, local_nullable) = self
+                        .expression_without_null(
+                            local,
+                            Some(name_suggestion.unwrap_or(ref_)),
+                            globals,
+                            visiting,
+                        )
+                        .wrap_err_with(|| format!("could not convert the value of ref `{ref_}`"))?;
+                    (expression, *nullable || local_nullable)
+                }
+                RefTarget::Named => {
+                    let (expression, definition_nullable) = self
+                        .insert_definition(ref_, name_suggestion, globals, visiting)
+                        .wrap_err_with(|| format!("could not convert the value of ref `{ref_}`"))?;
+                    (expression, *nullable || definition_nullable)
+                }
+            },
+            Schema::Type {
+                type_, nullable, ..
+            } => (
+                match type_ {
+                    Type::Boolean => z("boolean", Vec::new()),
+                    Type::String => z("string", Vec::new()),
+                    Type::Timestamp => z("string", Vec::new()).call(
+                        "datetime",
+                        Vec::from([Expr::Object {
+                            members: Vec::from([(
+                                String::from("offset"),
+                                Expr::Literal(String::from("true")),
+                            )]),
+                            expanded: false,
+                        }]),
+                    ),
+                    Type::Float32 | Type::Float64 => z("number", Vec::new()),
+                    Type::Int8 => int(i8::MIN.into(), i8::MAX.into()),
+                    Type::Uint8 => int(u8::MIN.into(), u8::MAX.into()),
+                    Type::Int16 => int(i16::MIN.into(), i16::MAX.into()),
+                    Type::Uint16 => int(u16::MIN.into(), u16::MAX.into()),
+                    Type::Int32 => int(i32::MIN.into(), i32::MAX.into()),
+                    Type::Uint32 => int(u32::MIN.into(), u32::MAX.into()),
+                },
+                *nullable,
+            ),
+            Schema::Enum {
+                metadata,
+                enum_,
+                nullable,
+                ..
+            } => {
+                let mut values = Vec::with_capacity(enum_.len());
+                for value in enum_ {
+                    values.push(Expr::Literal(serde_json::to_string(value)?));
+                }
+
+                let mut expression = z("enum", Vec::from([Expr::Array(values)]));
+                if metadata::is_open(metadata) {
+                    expression = z(
+                        "union",
+                        Vec::from([Expr::Array(Vec::from([
+                            expression,
+                            z("string", Vec::new()),
+                        ]))]),
+                    );
+                }
+
+                (expression, *nullable)
+            }
+            Schema::Elements {
+                elements, nullable, ..
+            } => (
+                z(
+                    "array",
+                    Vec::from([self
+                        .expression(
+                            elements,
+                            name_suggestion.map(|n| format!("{n}Elements")).as_deref(),
+                            globals,
+                            visiting,
+                        )
+                        .wrap_err("could not convert the elements type")?]),
+                ),
+                *nullable,
+            ),
+            Schema::Values {
+                values, nullable, ..
+            } => (
+                z(
+                    "record",
+                    Vec::from([
+                        z("string", Vec::new()),
+                        self.expression(
+                            values,
+                            name_suggestion.map(|n| format!("{n}Values")).as_deref(),
+                            globals,
+                            visiting,
+                        )
+                        .wrap_err("could not convert the values type")?,
+                    ]),
+                ),
+                *nullable,
+            ),
+            Schema::Properties {
+                properties,
+                optional_properties,
+                nullable,
+                ..
+            } => (
+                self.object(None, properties, optional_properties, globals, visiting)?,
+                *nullable,
+            ),
+            Schema::Discriminator {
+                metadata,
+                discriminator,
+                mapping,
+                nullable,
+                ..
+            } => {
+                let mut members = Vec::with_capacity(mapping.len());
+                for (tag, value) in mapping {
+                    members.push(match value {
+                        Schema::Properties {
+                            properties,
+                            optional_properties,
+                            ..
+                        } => self
+                            .object(
+                                Some((discriminator, tag)),
+                                properties,
+                                optional_properties,
+                                globals,
+                                visiting,
+                            )
+                            .wrap_err_with(|| format!("could not convert the {tag} tag"))?,
+                        _ => bail!("jtd discriminator should have enforced that the value type must be an object"),
+                    });
+                }
+
+                let mut expression = z(
+                    "discriminatedUnion",
+                    Vec::from([
+                        Expr::Literal(serde_json::to_string(discriminator)?),
+                        Expr::Array(members),
+                    ]),
+                );
+
+                if metadata::is_open(metadata) {
+                    let unknown = z(
+                        "object",
+                        Vec::from([Expr::Object {
+                            members: Vec::from([(discriminator.clone(), z("string", Vec::new()))]),
+                            expanded: false,
+                        }]),
+                    )
+                    .call("passthrough", Vec::new());
+
+                    expression = z(
+                        "union",
+                        Vec::from([Expr::Array(Vec::from([expression, unknown]))]),
+                    );
+                }
+
+                (expression, *nullable)
+            }
+        })
+    }
+
+    fn object(
+        &mut self,
+        tag: Option<(&str, &str)>,
+        properties: &BTreeMap<String, Schema>,
+        optional_properties: &BTreeMap<String, Schema>,
+        globals: &BTreeMap<String, Schema>,
+        visiting: &mut Vec<String>,
+    ) -> Result<Expr> {
+        let mut fields = BTreeMap::new();
+
+        if let Some((discriminator, value)) = tag {
+            fields.insert(
+                discriminator.to_owned(),
+                z(
+                    "literal",
+                    Vec::from([Expr::Literal(serde_json::to_string(value)?)]),
+                ),
+            );
+        }
+
+        for (name, value) in properties {
+            fields.insert(
+                name.clone(),
+                self.expression(value, Some(name), globals, visiting)
+                    .wrap_err_with(|| format!("could not convert the {name} key"))?,
+            );
+        }
+
+        for (name, value) in optional_properties {
+            fields.insert(
+                name.clone(),
+                self.expression(value, Some(name), globals, visiting)
+                    .wrap_err_with(|| format!("could not convert the {name} key"))?
+                    .call("optional", Vec::new()),
+            );
+        }
+
+        // prettier keeps objects on several lines once they're written that way, so we always
+        // write them like that for consistency.
+        Ok(z(
+            "object",
+            Vec::from([Expr::Object {
+                members: fields.into_iter().collect(),
+                expanded: true,
+            }]),
+        ))
+    }
+
+    pub fn to_source(&self) -> String {
+        let mut out = String::from(
+            "// Warning: this file is automatically generated. Don't edit by hand!\n\nimport { z } from \"zod\";\n",
+        );
+
+        for (name, expression) in &self.decls {
+            let decl = concat(Vec::from([
+                assignment_doc(
+                    text(format!("export const {name}Schema =")),
+                    expression.to_doc(),
+                ),
+                text(";"),
+                Doc::HardLine,
+                assignment_doc(
+                    text(format!("export type {name} =")),
+                    type_arguments_doc(
+                        "z.infer",
+                        Vec::from([text(format!("typeof {name}Schema"))]),
+                    ),
+                ),
+                text(";"),
+            ]));
+
+            out.push('\n');
+            out.push_str(&decl.render());
+            out.push('\n');
+        }
+
+        out
+    }
+}
+
+/// A zod expression. We keep these as a tree until we print them so that we can lay out method
+/// chains and arguments the way prettier does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Expr {
+    /// Something we refer to by name, like `z` or `NotificationSchema`.
+    Name(String),
+
+    /// A string, number, or boolean, already written out.
+    Literal(String),
+
+    /// `object.method(args)`
+    Call {
+        object: Box<Expr>,
+        method: String,
+        args: Vec<Expr>,
+    },
+
+    Array(Vec<Expr>),
+
+    /// `{ key: value }`. Expanded objects always go over several lines.
+    Object {
+        members: Vec<(String, Expr)>,
+        expanded: bool,
+    },
+}
+
+/// `z.method(args)`
+fn z(method: &str, args: Vec<Expr>) -> Expr {
+    Expr::Name(String::from("z")).call(method, args)
+}
+
+fn int(min: i64, max: i64) -> Expr {
+    z("number", Vec::new())
+        .call("int", Vec::new())
+        .call("min", Vec::from([Expr::Literal(min.to_string())]))
+        .call("max", Vec::from([Expr::Literal(max.to_string())]))
+}
+
+impl Expr {
+    fn call(self, method: &str, args: Vec<Expr>) -> Self {
+        Expr::Call {
+            object: Box::new(self),
+            method: method.to_owned(),
+            args,
+        }
+    }
+
+    fn to_doc(&self) -> Doc {
+        match self {
+            Expr::Name(name) | Expr::Literal(name) => text(name.clone()),
+            Expr::Call { .. } => self.chain_doc(),
+            Expr::Array(items) => array_doc(items.iter().map(Expr::to_doc).collect()),
+            Expr::Object { members, expanded } => {
+                if members.is_empty() {
+                    return text("{}");
+                }
+
+                let members = members
+                    .iter()
+                    .map(|(key, value)| {
+                        concat(Vec::from([
+                            text(format!("{}: ", object_key(key))),
+                            value.to_doc(),
+                        ]))
+                    })
+                    .collect();
+
+                let contents = concat(Vec::from([
+                    text("{"),
+                    indent(concat(Vec::from([
+                        Doc::Line,
+                        join(concat(Vec::from([text(","), Doc::Line])), members),
+                    ]))),
+                    if_break(text(","), Doc::empty()),
+                    Doc::Line,
+                    text("}"),
+                ]));
+
+                if *expanded {
+                    broken_group(contents)
+                } else {
+                    group(contents)
+                }
+            }
+        }
+    }
+
+    /// `z.object({ ... }).nullable().optional()`. Like prettier, we keep short chains on one line
+    /// and otherwise put each call on its own line.
+    fn chain_doc(&self) -> Doc {
+        let mut calls = Vec::new();
+        let mut head = self;
+        while let Expr::Call {
+            object,
+            method,
+            args,
+        } = head
+        {
+            calls.push((method, args));
+            head = object;
+        }
+        calls.reverse();
+
+        let mut groups: Vec<Doc> = calls
+            .iter()
+            .map(|(method, args)| {
+                concat(Vec::from([text(format!(".{method}")), arguments_doc(args)]))
+            })
+            .collect();
+
+        // calls on something that looks like a class or factory (like `NotificationSchema`)
+        // start on the same line.
+        let merge =
+            matches!(head, Expr::Name(name) if name.starts_with(|c: char| c.is_ascii_uppercase()));
+        let mut first = Vec::from([head.to_doc()]);
+        if merge {
+            first.push(groups.remove(0));
+        }
+
+        if groups.len() <= 1 {
+            first.extend(groups);
+            return concat(first);
+        }
+
+        let some_argument_is_complex = calls.len() > 2
+            && calls
+                .iter()
+                .any(|(_, args)| args.iter().any(|arg| !arg.is_simple(0)));
+        let breaks_before_the_end = first.iter().any(Doc::will_break)
+            || groups[..groups.len() - 1].iter().any(Doc::will_break);
+
+        if !some_argument_is_complex
+            && !breaks_before_the_end
+            && groups.last().is_some_and(Doc::will_break)
+        {
+            first.extend(groups);
+            return concat(first);
+        }
+
+        let mut rest = Vec::with_capacity(groups.len() * 2);
+        for group in groups {
+            rest.push(Doc::SoftLine);
+            rest.push(group);
+        }
+
+        let chain = concat(Vec::from([concat(first), indent(concat(rest))]));
+        if some_argument_is_complex || breaks_before_the_end {
+            broken_group(chain)
+        } else {
+            group(chain)
+        }
+    }
+
+    /// Whether prettier considers this a simple argument, which decides whether long chains
+    /// have to go on several lines.
+    fn is_simple(&self, depth: usize) -> bool {
+        match self {
+            Expr::Name(_) | Expr::Literal(_) => true,
+            Expr::Array(items) => items.iter().all(|item| item.is_simple(depth + 1)),
+            Expr::Object { members, .. } => {
+                members.iter().all(|(_, value)| value.is_simple(depth + 1))
+            }
+            Expr::Call { object, args, .. } => {
+                object.is_simple(depth)
+                    && args.len() <= depth
+                    && args.iter().all(|arg| arg.is_simple(depth + 1))
+            }
+        }
+    }
+}
+
+/// `(a, b)`. A last argument that's an object or array hugs the parentheses (like
+/// `z.enum([` ... `])`), and anything else goes one per line if it doesn't fit.
+fn arguments_doc(args: &[Expr]) -> Doc {
+    let docs: Vec<Doc> = args.iter().map(Expr::to_doc).collect();
+
+    let hug = match args.split_last() {
+        Some((Expr::Array(items), others)) => {
+            !items.is_empty() && !matches!(others.last(), Some(Expr::Array(_)))
+        }
+        Some((Expr::Object { members, .. }, others)) => {
+            !members.is_empty() && !matches!(others.last(), Some(Expr::Object { .. }))
+        }
+        _ => false,
+    } && !docs[..docs.len() - 1].iter().any(Doc::will_break);
+
+    if hug {
+        concat(Vec::from([text("("), join(text(", "), docs), text(")")]))
+    } else {
+        group(parameters_doc(docs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    fn from_json(value: Value) -> Schema {
+        let json = serde_json::from_value(value).unwrap();
+        Schema::from_serde_schema(json).unwrap()
+    }
+
+    fn expression(value: Value) -> String {
+        Schemas::new()
+            .expression(&from_json(value), None, &BTreeMap::new(), &mut Vec::new())
+            .unwrap()
+            .to_doc()
+            .render()
+    }
+
+    fn decls(schemas: Schemas) -> Vec<(String, String)> {
+        schemas
+            .decls
+            .into_iter()
+            .map(|(name, expression)| (name, expression.to_doc().render()))
+            .collect()
+    }
+
+    #[test]
+    fn interprets_ints_with_ranges() {
+        assert_eq!(
+            expression(json!({"type": "uint8"})),
+            "z.number().int().min(0).max(255)"
+        );
+    }
+
+    #[test]
+    fn interprets_nullable() {
+        assert_eq!(
+            expression(json!({"type": "string", "nullable": true})),
+            "z.string().nullable()"
+        );
+    }
+
+    #[test]
+    fn interprets_enum() {
+        assert_eq!(
+            expression(json!({"enum": ["b", "a"]})),
+            "z.enum([\"a\", \"b\"])"
+        );
+    }
+
+    #[test]
+    fn interprets_open_enum() {
+        assert_eq!(
+            expression(json!({"metadata": {"open": true}, "enum": ["a"]})),
+            "z.union([z.enum([\"a\"]), z.string()])"
+        );
+    }
+
+    #[test]
+    fn interprets_properties() {
+        assert_eq!(
+            expression(json!({
+                "properties": {"name": {"type": "string"}},
+                "optionalProperties": {"content-type": {"type": "string"}},
+            })),
+            "z.object({\n  \"content-type\": z.string().optional(),\n  name: z.string(),\n})"
+        );
+    }
+
+    #[test]
+    fn interprets_values_and_elements() {
+        assert_eq!(
+            expression(json!({"values": {"elements": {"type": "boolean"}}})),
+            "z.record(z.string(), z.array(z.boolean()))"
+        );
+    }
+
+    #[test]
+    fn interprets_discriminator() {
+        assert_eq!(
+            expression(json!({
+                "discriminator": "tag",
+                "mapping": {
+                    "a": {"properties": {"value": {"type": "string"}}},
+                    "b": {"properties": {}},
+                },
+            })),
+            "z.discriminatedUnion(\"tag\", [\n  z.object({\n    tag: z.literal(\"a\"),\n    value: z.string(),\n  }),\n  z.object({\n    tag: z.literal(\"b\"),\n  }),\n])"
+        );
+    }
+
+    #[test]
+    fn breaks_long_arguments_like_prettier() {
+        assert_eq!(
+            expression(json!({
+                "metadata": {"open": true},
+                "enum": ["notification", "permission", "subscription", "unsubscription"],
+            })),
+            "z.union([\n  z.enum([\"notification\", \"permission\", \"subscription\", \"unsubscription\"]),\n  z.string(),\n])"
+        );
+    }
+
+    #[test]
+    fn breaks_chains_on_multiline_objects() {
+        assert_eq!(
+            expression(json!({
+                "optionalProperties": {
+                    "a": {"properties": {"b": {"type": "string"}}, "nullable": true},
+                },
+            })),
+            "z.object({\n  a: z\n    .object({\n      b: z.string(),\n    })\n    .nullable()\n    .optional(),\n})"
+        );
+    }
+
+    #[test]
+    fn source_fits_in_80_columns() {
+        let mut schemas = Schemas::new();
+        schemas
+            .insert(
+                "Main_gotNewNotificationsPermission",
+                &from_json(json!({
+                    "metadata": {"open": true},
+                    "enum": ["default", "denied", "granted", "prompt", "provisional"],
+                })),
+                Some("gotNewNotificationsPermission"),
+                &BTreeMap::new(),
+            )
+            .unwrap();
+
+        let source = schemas.to_source();
+        assert!(
+            source.lines().all(|line| line.chars().count() <= 80),
+            "{source}"
+        );
+    }
+
+    #[test]
+    fn declares_definitions_before_use() {
+        let globals = BTreeMap::from([
+            (
+                "a".to_string(),
+                from_json(json!({"properties": {"b": {"ref": "b"}}})),
+            ),
+            ("b".to_string(), from_json(json!({"enum": ["x"]}))),
+        ]);
+
+        let mut schemas = Schemas::new();
+        schemas.insert_definitions(&globals).unwrap();
+
+        assert_eq!(
+            decls(schemas),
+            Vec::from([
+                ("B".to_string(), "z.enum([\"x\"])".to_string()),
+                ("A".to_string(), "z.object({\n  b: BSchema,\n})".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn names_schemas_like_the_declarations() {
+        let globals = BTreeMap::from([(
+            "notification".to_string(),
+            from_json(json!({
+                "metadata": {"name": "Alert"},
+                "properties": {
+                    "level": {"metadata": {"name": "Level"}, "enum": ["high"], "nullable": true},
+                },
+            })),
+        )]);
+
+        let mut schemas = Schemas::new();
+        schemas.insert_definitions(&globals).unwrap();
+        schemas
+            .insert(
+                "Main_flags",
+                &from_json(json!({"ref": "notification"})),
+                Some("Flags"),
+                &globals,
+            )
+            .unwrap();
+
+        assert_eq!(
+            decls(schemas),
+            Vec::from([
+                ("Level".to_string(), "z.enum([\"high\"])".to_string()),
+                (
+                    "Alert".to_string(),
+                    "z.object({\n  level: LevelSchema.nullable(),\n})".to_string()
+                ),
+                ("MainFlags".to_string(), "AlertSchema".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn rejects_different_schemas_with_the_same_name() {
+        let err = Schemas::new()
+            .expression(
+                &from_json(json!({
+                    "properties": {
+                        "a": {"metadata": {"name": "Same"}, "type": "string"},
+                        "b": {"metadata": {"name": "Same"}, "type": "boolean"},
+                    },
+                })),
+                None,
+                &BTreeMap::new(),
+                &mut Vec::new(),
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            err.root_cause().to_string(),
+            "there are two different zod schemas named `SameSchema`. Give one of them a different `metadata.name`"
+        );
+    }
+
+    #[test]
+    fn rejects_recursive_definitions() {
+        let globals = BTreeMap::from([(
+            "tree".to_string(),
+            from_json(json!({"elements": {"ref": "tree"}})),
+        )]);
+
+        assert_eq!(
+            Schemas::new()
+                .insert_definitions(&globals)
+                .unwrap_err()
+                .root_cause()
+                .to_string(),
+            "`Tree` refers to itself (through Tree -> Tree), and zod can't infer types for recursive schemas. Leave out `--zod-dest`, or write this schema by hand with `z.lazy`"
+        );
+    }
+}
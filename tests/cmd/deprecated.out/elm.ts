@@ -0,0 +1,39 @@
+// Warning: this file is automatically generated. Don't edit by hand!
+// elm-duet 0.1.0, schema 5a1c2ac7d7be4b97, content ef9bfb4f5f9708f7
+
+declare module Elm {
+  type CurrentJwt = string;
+
+  type Jwt = string;
+
+  type NewJwt = string;
+
+  type NewToken = string;
+
+  type Session = {
+    id: string;
+  };
+
+  namespace Main {
+    type Flags = {
+      currentJwt: Elm.CurrentJwt | null;
+      /** @deprecated use currentJwt instead */
+      legacyToken: string;
+      session: Elm.Session;
+    };
+
+    type Ports = {
+      newJwt?: {
+        subscribe: (callback: (value: Elm.NewJwt) => void) => void;
+      };
+      /** @deprecated use newJwt instead */
+      newToken?: {
+        subscribe: (callback: (value: Elm.NewToken) => void) => void;
+      };
+    };
+
+    function init(config: { flags: Flags; node: HTMLElement }): {
+      ports?: Ports;
+    };
+  }
+}
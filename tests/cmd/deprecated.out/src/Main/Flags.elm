@@ -0,0 +1,63 @@
+module Main.Flags exposing (..)
+
+{-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema 5a1c2ac7d7be4b97, content 668ac9a33e5f82dd
+-}
+
+import Dict exposing (Dict)
+import Json.Decode
+import Json.Decode.Pipeline
+import Json.Encode
+
+
+{-| **Deprecated**: sessions are tracked in the JWT now
+-}
+type alias Session =
+    { id : String
+    }
+
+
+sessionDecoder : Json.Decode.Decoder Session
+sessionDecoder =
+    Json.Decode.succeed Session
+        |> Json.Decode.Pipeline.required "id" Json.Decode.string
+
+
+encodeSession : Session -> Json.Encode.Value
+encodeSession session_ =
+    Json.Encode.object
+        [ ( "id", Json.Encode.string session_.id )
+        ]
+
+
+{-| `legacyToken` is deprecated: use currentJwt instead
+-}
+type alias Flags =
+    { currentJwt : Maybe String
+    , legacyToken : String
+    , session : Session
+    }
+
+
+flagsDecoder : Json.Decode.Decoder Flags
+flagsDecoder =
+    Json.Decode.succeed Flags
+        |> Json.Decode.Pipeline.required "currentJwt" (Json.Decode.nullable Json.Decode.string)
+        |> Json.Decode.Pipeline.required "legacyToken" Json.Decode.string
+        |> Json.Decode.Pipeline.required "session" sessionDecoder
+
+
+encodeFlags : Flags -> Json.Encode.Value
+encodeFlags flags_ =
+    Json.Encode.object
+        [ ( "currentJwt"
+          , case flags_.currentJwt of
+                Just value ->
+                    Json.Encode.string value
+
+                Nothing ->
+                    Json.Encode.null
+          )
+        , ( "legacyToken", Json.Encode.string flags_.legacyToken )
+        , ( "session", encodeSession flags_.session )
+        ]
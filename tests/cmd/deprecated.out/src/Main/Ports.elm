@@ -0,0 +1,56 @@
+port module Main.Ports exposing (..)
+
+{-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema 5a1c2ac7d7be4b97, content e2f5d1718cf93cf1
+-}
+
+import Dict exposing (Dict)
+import Json.Decode
+import Json.Decode.Pipeline
+import Json.Encode
+
+
+type alias NewJwt =
+    String
+
+
+newJwtDecoder : Json.Decode.Decoder NewJwt
+newJwtDecoder =
+    Json.Decode.string
+
+
+encodeNewJwt : NewJwt -> Json.Encode.Value
+encodeNewJwt newJwt_ =
+    Json.Encode.string newJwt_
+
+
+type alias NewToken =
+    String
+
+
+newTokenDecoder : Json.Decode.Decoder NewToken
+newTokenDecoder =
+    Json.Decode.string
+
+
+encodeNewToken : NewToken -> Json.Encode.Value
+encodeNewToken newToken_ =
+    Json.Encode.string newToken_
+
+
+port newJwt : Json.Decode.Value -> Cmd msg
+
+
+sendNewJwt : NewJwt -> Cmd msg
+sendNewJwt =
+    encodeNewJwt >> newJwt
+
+
+port newToken : Json.Decode.Value -> Cmd msg
+
+
+{-| **Deprecated**: use newJwt instead
+-}
+sendNewToken : NewToken -> Cmd msg
+sendNewToken =
+    encodeNewToken >> newToken
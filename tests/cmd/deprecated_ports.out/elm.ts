@@ -0,0 +1,22 @@
+// Warning: this file is automatically generated. Don't edit by hand!
+// elm-duet 0.1.0, schema da1d196ae005fc5a, content bc83acb0e37db789
+
+declare module Elm {
+  namespace Main {
+    type Flags = Record<string, never>;
+
+    type Ports = {
+      /** @deprecated */
+      ping?: {
+        subscribe: (callback: (value: string) => void) => void;
+      };
+      pong?: {
+        send: (value: string) => void;
+      };
+    };
+
+    function init(config: { flags: Flags; node: HTMLElement }): {
+      ports?: Ports;
+    };
+  }
+}
@@ -0,0 +1,56 @@
+port module Main.Ports exposing (..)
+
+{-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema da1d196ae005fc5a, content b284d7bab6d30c12
+-}
+
+import Dict exposing (Dict)
+import Json.Decode
+import Json.Decode.Pipeline
+import Json.Encode
+
+
+type alias Ping =
+    String
+
+
+pingDecoder : Json.Decode.Decoder Ping
+pingDecoder =
+    Json.Decode.string
+
+
+encodePing : Ping -> Json.Encode.Value
+encodePing ping_ =
+    Json.Encode.string ping_
+
+
+type alias Pong =
+    String
+
+
+pongDecoder : Json.Decode.Decoder Pong
+pongDecoder =
+    Json.Decode.string
+
+
+encodePong : Pong -> Json.Encode.Value
+encodePong pong_ =
+    Json.Encode.string pong_
+
+
+port ping : Json.Decode.Value -> Cmd msg
+
+
+{-| **Deprecated**.
+-}
+sendPing : Ping -> Cmd msg
+sendPing =
+    encodePing >> ping
+
+
+port pong : (Json.Decode.Value -> msg) -> Sub msg
+
+
+subscribeToPong : (Result Json.Decode.Error Pong -> msg) -> Sub msg
+subscribeToPong toMsg =
+    pong (Json.Decode.decodeValue pongDecoder >> toMsg)
@@ -0,0 +1,43 @@
+// Warning: this file is automatically generated. Don't edit by hand!
+// elm-duet 0.1.0, schema 9a3014c9e4039311, content 164e613d0bdc747d
+
+declare module Elm {
+  type Notification = {
+    priority: number;
+    tags?: string[];
+    title: string;
+  };
+
+  type NotificationPermission =
+    | "default"
+    | "denied"
+    | "granted"
+    | (string & {});
+
+  namespace Main {
+    type Flags = {
+      counts: Record<string, number>;
+      permission: Elm.NotificationPermission;
+      retries: number | null;
+    };
+
+    type Ports = {
+      fromWorld?: {
+        send: (
+          value:
+            | {
+                notification: Elm.Notification;
+                tag: "notify";
+              }
+            | {
+                tag: "refresh";
+              },
+        ) => void;
+      };
+    };
+
+    function init(config: { flags: Flags; node: HTMLElement }): {
+      ports?: Ports;
+    };
+  }
+}
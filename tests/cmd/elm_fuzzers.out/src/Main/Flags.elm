@@ -0,0 +1,124 @@
+module Main.Flags exposing (..)
+
+{-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema 9a3014c9e4039311, content 469269cf5220c469
+-}
+
+import Dict exposing (Dict)
+import Json.Decode
+import Json.Decode.Pipeline
+import Json.Encode
+
+
+type NotificationPermission
+    = NotificationPermissionDefault
+    | NotificationPermissionDenied
+    | NotificationPermissionGranted
+    | NotificationPermissionUnknown String
+
+
+notificationPermissionDecoder : Json.Decode.Decoder NotificationPermission
+notificationPermissionDecoder =
+    Json.Decode.andThen
+        (/tag ->
+            case tag of
+                "default" ->
+                    Json.Decode.succeed NotificationPermissionDefault
+
+                "denied" ->
+                    Json.Decode.succeed NotificationPermissionDenied
+
+                "granted" ->
+                    Json.Decode.succeed NotificationPermissionGranted
+
+                unknown ->
+                    Json.Decode.succeed (NotificationPermissionUnknown unknown)
+        )
+        Json.Decode.string
+
+
+encodeNotificationPermission : NotificationPermission -> Json.Encode.Value
+encodeNotificationPermission notificationPermission_ =
+    case notificationPermission_ of
+        NotificationPermissionDefault ->
+            Json.Encode.string "default"
+
+        NotificationPermissionDenied ->
+            Json.Encode.string "denied"
+
+        NotificationPermissionGranted ->
+            Json.Encode.string "granted"
+
+        NotificationPermissionUnknown unknown ->
+            Json.Encode.string unknown
+
+
+allNotificationPermission : List NotificationPermission
+allNotificationPermission =
+    [ NotificationPermissionDefault
+    , NotificationPermissionDenied
+    , NotificationPermissionGranted
+    ]
+
+
+notificationPermissionToString : NotificationPermission -> String
+notificationPermissionToString notificationPermission_ =
+    case notificationPermission_ of
+        NotificationPermissionDefault ->
+            "default"
+
+        NotificationPermissionDenied ->
+            "denied"
+
+        NotificationPermissionGranted ->
+            "granted"
+
+        NotificationPermissionUnknown unknown ->
+            unknown
+
+
+notificationPermissionFromString : String -> NotificationPermission
+notificationPermissionFromString string_ =
+    case string_ of
+        "default" ->
+            NotificationPermissionDefault
+
+        "denied" ->
+            NotificationPermissionDenied
+
+        "granted" ->
+            NotificationPermissionGranted
+
+        unknown ->
+            NotificationPermissionUnknown unknown
+
+
+type alias Flags =
+    { counts : Dict String Float
+    , permission : NotificationPermission
+    , retries : Maybe Int
+    }
+
+
+flagsDecoder : Json.Decode.Decoder Flags
+flagsDecoder =
+    Json.Decode.succeed Flags
+        |> Json.Decode.Pipeline.required "counts" (Json.Decode.dict Json.Decode.float)
+        |> Json.Decode.Pipeline.required "permission" notificationPermissionDecoder
+        |> Json.Decode.Pipeline.required "retries" (Json.Decode.nullable Json.Decode.int)
+
+
+encodeFlags : Flags -> Json.Encode.Value
+encodeFlags flags_ =
+    Json.Encode.object
+        [ ( "counts", Json.Encode.dict identity (/value -> Json.Encode.float value) flags_.counts )
+        , ( "permission", encodeNotificationPermission flags_.permission )
+        , ( "retries"
+          , case flags_.retries of
+                Just value ->
+                    Json.Encode.int value
+
+                Nothing ->
+                    Json.Encode.null
+          )
+        ]
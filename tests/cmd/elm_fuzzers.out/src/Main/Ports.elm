@@ -0,0 +1,143 @@
+port module Main.Ports exposing (Notification, makeNotification, notificationPriority, notificationTags, notificationTitle, notificationDecoder, encodeNotification, Notify, notifyDecoder, encodeNotify, Refresh, refreshDecoder, encodeRefresh, FromWorld(..), fromWorldDecoder, encodeFromWorld, fromWorld, subscribeToFromWorld)
+
+{-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema 9a3014c9e4039311, content 5dbc40ac3ae346f0
+-}
+
+import Dict exposing (Dict)
+import Json.Decode
+import Json.Decode.Pipeline
+import Json.Encode
+
+
+type Notification
+    = Notification
+        { priority : Int
+        , tags : Maybe (List String)
+        , title : String
+        }
+
+
+notificationDecoder : Json.Decode.Decoder Notification
+notificationDecoder =
+    Json.Decode.succeed
+        (/priority_ tags_ title_ ->
+            Notification
+                { priority = priority_
+                , tags = tags_
+                , title = title_
+                }
+        )
+        |> Json.Decode.Pipeline.required "priority" Json.Decode.int
+        |> Json.Decode.Pipeline.optional "tags" (Json.Decode.nullable (Json.Decode.list Json.Decode.string)) Nothing
+        |> Json.Decode.Pipeline.required "title" Json.Decode.string
+
+
+encodeNotification : Notification -> Json.Encode.Value
+encodeNotification (Notification notification_) =
+    List.filterMap identity
+        [ Just ( "priority", Json.Encode.int notification_.priority )
+        , Maybe.map (/tags_ -> ( "tags", Json.Encode.list (/value -> Json.Encode.string value) tags_ )) notification_.tags
+        , Just ( "title", Json.Encode.string notification_.title )
+        ]
+        |> Json.Encode.object
+
+
+makeNotification :
+    { priority : Int
+    , tags : Maybe (List String)
+    , title : String
+    }
+    -> Notification
+makeNotification =
+    Notification
+
+
+notificationPriority : Notification -> Int
+notificationPriority (Notification notification_) =
+    notification_.priority
+
+
+notificationTags : Notification -> Maybe (List String)
+notificationTags (Notification notification_) =
+    notification_.tags
+
+
+notificationTitle : Notification -> String
+notificationTitle (Notification notification_) =
+    notification_.title
+
+
+type alias Notify =
+    { notification : Notification
+    }
+
+
+notifyDecoder : Json.Decode.Decoder Notify
+notifyDecoder =
+    Json.Decode.succeed Notify
+        |> Json.Decode.Pipeline.required "notification" notificationDecoder
+
+
+encodeNotify : Notify -> Json.Encode.Value
+encodeNotify notify_ =
+    Json.Encode.object
+        [ ( "notification", encodeNotification notify_.notification )
+        , ( "tag", Json.Encode.string "notify" )
+        ]
+
+
+type alias Refresh =
+    {}
+
+
+refreshDecoder : Json.Decode.Decoder Refresh
+refreshDecoder =
+    Json.Decode.succeed Refresh
+
+
+encodeRefresh : Refresh -> Json.Encode.Value
+encodeRefresh refresh_ =
+    Json.Encode.object
+        [ ( "tag", Json.Encode.string "refresh" )
+        ]
+
+
+type FromWorld
+    = FromWorldNotify Notify
+    | FromWorldRefresh Refresh
+
+
+fromWorldDecoder : Json.Decode.Decoder FromWorld
+fromWorldDecoder =
+    Json.Decode.andThen
+        (/tag ->
+            case tag of
+                "notify" ->
+                    Json.Decode.map FromWorldNotify notifyDecoder
+
+                "refresh" ->
+                    Json.Decode.map FromWorldRefresh refreshDecoder
+
+                unknown ->
+                    Json.Decode.fail ("Unknown value `" ++ unknown ++ "`")
+        )
+        (Json.Decode.field "tag" Json.Decode.string)
+
+
+encodeFromWorld : FromWorld -> Json.Encode.Value
+encodeFromWorld fromWorld_ =
+    case fromWorld_ of
+        FromWorldNotify fromWorldNotify ->
+            encodeNotify fromWorldNotify
+
+        FromWorldRefresh fromWorldRefresh ->
+            encodeRefresh fromWorldRefresh
+
+
+port fromWorld : (Json.Decode.Value -> msg) -> Sub msg
+
+
+subscribeToFromWorld : (Result Json.Decode.Error FromWorld -> msg) -> Sub msg
+subscribeToFromWorld toMsg =
+    fromWorld (Json.Decode.decodeValue fromWorldDecoder >> toMsg)
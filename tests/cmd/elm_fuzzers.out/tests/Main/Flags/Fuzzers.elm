@@ -0,0 +1,34 @@
+module Main.Flags.Fuzzers exposing (..)
+
+{-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema 9a3014c9e4039311, content 3cd43448646e1afa
+-}
+
+import Dict
+import Fuzz exposing (Fuzzer)
+import Json.Encode
+import Main.Flags exposing (..)
+
+
+notificationPermissionFuzzer : Fuzzer NotificationPermission
+notificationPermissionFuzzer =
+    Fuzz.oneOfValues
+        [ NotificationPermissionDefault
+        , NotificationPermissionDenied
+        , NotificationPermissionGranted
+        , NotificationPermissionUnknown "unknown"
+        ]
+
+
+flagsFuzzer : Fuzzer Flags
+flagsFuzzer =
+    Fuzz.constant
+        (/counts_ permission_ retries_ ->
+            { counts = counts_
+            , permission = permission_
+            , retries = retries_
+            }
+        )
+        |> Fuzz.andMap (Fuzz.map Dict.fromList (Fuzz.list (Fuzz.pair Fuzz.string Fuzz.niceFloat)))
+        |> Fuzz.andMap notificationPermissionFuzzer
+        |> Fuzz.andMap (Fuzz.maybe (Fuzz.intRange (-128) 127))
@@ -0,0 +1,48 @@
+module Main.Ports.Fuzzers exposing (..)
+
+{-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema 9a3014c9e4039311, content 25412f496b68eb08
+-}
+
+import Dict
+import Fuzz exposing (Fuzzer)
+import Json.Encode
+import Main.Ports exposing (..)
+
+
+notificationFuzzer : Fuzzer Notification
+notificationFuzzer =
+    Fuzz.constant
+        (/priority_ tags_ title_ ->
+            makeNotification
+                { priority = priority_
+                , tags = tags_
+                , title = title_
+                }
+        )
+        |> Fuzz.andMap (Fuzz.intRange 0 255)
+        |> Fuzz.andMap (Fuzz.maybe (Fuzz.list Fuzz.string))
+        |> Fuzz.andMap Fuzz.string
+
+
+notifyFuzzer : Fuzzer Notify
+notifyFuzzer =
+    Fuzz.constant
+        (/notification_ ->
+            { notification = notification_
+            }
+        )
+        |> Fuzz.andMap notificationFuzzer
+
+
+refreshFuzzer : Fuzzer Refresh
+refreshFuzzer =
+    Fuzz.constant {}
+
+
+fromWorldFuzzer : Fuzzer FromWorld
+fromWorldFuzzer =
+    Fuzz.oneOf
+        [ Fuzz.map FromWorldNotify notifyFuzzer
+        , Fuzz.map FromWorldRefresh refreshFuzzer
+        ]
@@ -0,0 +1,22 @@
+// Warning: this file is automatically generated. Don't edit by hand!
+// elm-duet 0.1.0, schema b8f1b3ffb31d02fa, content 1205755998ab60d9
+
+declare module Elm {
+  type NotificationPermission = "default" | "denied" | "granted";
+
+  namespace Main {
+    type Flags = {
+      theme: "dark" | "light";
+    };
+
+    type Ports = {
+      gotNewNotificationsPermission?: {
+        send: (value: Elm.NotificationPermission) => void;
+      };
+    };
+
+    function init(config: { flags: Flags; node: HTMLElement }): {
+      ports?: Ports;
+    };
+  }
+}
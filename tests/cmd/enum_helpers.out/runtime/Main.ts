@@ -0,0 +1,117 @@
+// Warning: this file is automatically generated. Don't edit by hand!
+// elm-duet 0.1.0, schema b8f1b3ffb31d02fa, content 4e722da11ab376f9
+
+export const allNotificationPermission = [
+  "default",
+  "denied",
+  "granted",
+] as const;
+
+export const allTheme = ["dark", "light"] as const;
+
+export function isFlags(value: unknown): value is Elm.Main.Flags {
+  return checkFlags(value, "$") === null;
+}
+
+export function assertFlags(value: unknown): asserts value is Elm.Main.Flags {
+  const problem = checkFlags(value, "$");
+  if (problem !== null) {
+    throw new TypeError(problem);
+  }
+}
+
+export function isGotNewNotificationsPermission(
+  value: unknown,
+): value is Parameters<
+  NonNullable<Elm.Main.Ports["gotNewNotificationsPermission"]>["send"]
+>[0] {
+  return checkGotNewNotificationsPermission(value, "$") === null;
+}
+
+export function assertGotNewNotificationsPermission(
+  value: unknown,
+): asserts value is Parameters<
+  NonNullable<Elm.Main.Ports["gotNewNotificationsPermission"]>["send"]
+>[0] {
+  const problem = checkGotNewNotificationsPermission(value, "$");
+  if (problem !== null) {
+    throw new TypeError(problem);
+  }
+}
+
+/**
+ * Subscribe to every port Elm sends values through, and get functions for sending values to
+ * Elm. Elm removes ports that are never used, so we warn about missing ports (or throw, or
+ * ignore them, depending on `options.missingPorts`.)
+ */
+export function connectMain(
+  app: { ports?: Elm.Main.Ports },
+  handlers: {},
+  options: { missingPorts?: "throw" | "warn" | "ignore" } = {},
+): {
+  gotNewNotificationsPermission: (
+    value: Parameters<
+      NonNullable<Elm.Main.Ports["gotNewNotificationsPermission"]>["send"]
+    >[0],
+  ) => void;
+} {
+  const ports: Elm.Main.Ports = app.ports ?? {};
+  const missing = (port: string) => {
+    const message = `the Main Elm module does not have a "${port}" port. Is it used in Elm?`;
+    if (options.missingPorts === "throw") {
+      throw new Error(message);
+    } else if (options.missingPorts !== "ignore") {
+      console.warn(message);
+    }
+  };
+
+  if (!ports.gotNewNotificationsPermission) {
+    missing("gotNewNotificationsPermission");
+  }
+
+  return {
+    gotNewNotificationsPermission: (value) => {
+      ports.gotNewNotificationsPermission?.send(value);
+    },
+  };
+}
+
+function checkFlags(value: unknown, path: string): string | null {
+  if (typeof value !== "object" || value === null || Array.isArray(value)) {
+    return `${path}: expected an object`;
+  }
+  const object0 = value as Record<string, unknown>;
+  if (!("theme" in object0)) {
+    return `${path}: missing "theme"`;
+  }
+  {
+    const value1 = object0["theme"];
+    if (!["dark", "light"].includes(value1 as string)) {
+      return `${path}.theme: expected one of "dark", "light"`;
+    }
+  }
+  return null;
+}
+
+function checkGotNewNotificationsPermission(
+  value: unknown,
+  path: string,
+): string | null {
+  {
+    const problem = checkNotificationPermissionRef(value, `${path}`);
+    if (problem !== null) {
+      return problem;
+    }
+  }
+  return null;
+}
+
+function checkNotificationPermissionRef(
+  value: unknown,
+  path: string,
+): string | null {
+  if (!["default", "denied", "granted"].includes(value as string)) {
+    return `${path}: expected one of "default", "denied", "granted"`;
+  }
+  return null;
+}
@@ -0,0 +1,90 @@
+module Main.Flags exposing (..)
+
+{-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema b8f1b3ffb31d02fa, content cb2dc24d127f78a4
+-}
+
+import Dict exposing (Dict)
+import Json.Decode
+import Json.Decode.Pipeline
+import Json.Encode
+
+
+type Theme
+    = ThemeDark
+    | ThemeLight
+
+
+themeDecoder : Json.Decode.Decoder Theme
+themeDecoder =
+    Json.Decode.andThen
+        (/tag ->
+            case tag of
+                "dark" ->
+                    Json.Decode.succeed ThemeDark
+
+                "light" ->
+                    Json.Decode.succeed ThemeLight
+
+                unknown ->
+                    Json.Decode.fail ("Unknown value `" ++ unknown ++ "`")
+        )
+        Json.Decode.string
+
+
+encodeTheme : Theme -> Json.Encode.Value
+encodeTheme theme_ =
+    case theme_ of
+        ThemeDark ->
+            Json.Encode.string "dark"
+
+        ThemeLight ->
+            Json.Encode.string "light"
+
+
+allTheme : List Theme
+allTheme =
+    [ ThemeDark
+    , ThemeLight
+    ]
+
+
+themeToString : Theme -> String
+themeToString theme_ =
+    case theme_ of
+        ThemeDark ->
+            "dark"
+
+        ThemeLight ->
+            "light"
+
+
+themeFromString : String -> Maybe Theme
+themeFromString string_ =
+    case string_ of
+        "dark" ->
+            Just ThemeDark
+
+        "light" ->
+            Just ThemeLight
+
+        _ ->
+            Nothing
+
+
+type alias Flags =
+    { theme : Theme
+    }
+
+
+flagsDecoder : Json.Decode.Decoder Flags
+flagsDecoder =
+    Json.Decode.succeed Flags
+        |> Json.Decode.Pipeline.required "theme" themeDecoder
+
+
+encodeFlags : Flags -> Json.Encode.Value
+encodeFlags flags_ =
+    Json.Encode.object
+        [ ( "theme", encodeTheme flags_.theme )
+        ]
@@ -0,0 +1,94 @@
+port module Main.Ports exposing (..)
+
+{-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema b8f1b3ffb31d02fa, content 7d86f297b1cf0330
+-}
+
+import Dict exposing (Dict)
+import Json.Decode
+import Json.Decode.Pipeline
+import Json.Encode
+
+
+type NotificationPermission
+    = NotificationPermissionDefault
+    | NotificationPermissionDenied
+    | NotificationPermissionGranted
+
+
+notificationPermissionDecoder : Json.Decode.Decoder NotificationPermission
+notificationPermissionDecoder =
+    Json.Decode.andThen
+        (/tag ->
+            case tag of
+                "default" ->
+                    Json.Decode.succeed NotificationPermissionDefault
+
+                "denied" ->
+                    Json.Decode.succeed NotificationPermissionDenied
+
+                "granted" ->
+                    Json.Decode.succeed NotificationPermissionGranted
+
+                unknown ->
+                    Json.Decode.fail ("Unknown value `" ++ unknown ++ "`")
+        )
+        Json.Decode.string
+
+
+encodeNotificationPermission : NotificationPermission -> Json.Encode.Value
+encodeNotificationPermission notificationPermission_ =
+    case notificationPermission_ of
+        NotificationPermissionDefault ->
+            Json.Encode.string "default"
+
+        NotificationPermissionDenied ->
+            Json.Encode.string "denied"
+
+        NotificationPermissionGranted ->
+            Json.Encode.string "granted"
+
+
+allNotificationPermission : List NotificationPermission
+allNotificationPermission =
+    [ NotificationPermissionDefault
+    , NotificationPermissionDenied
+    , NotificationPermissionGranted
+    ]
+
+
+notificationPermissionToString : NotificationPermission -> String
+notificationPermissionToString notificationPermission_ =
+    case notificationPermission_ of
+        NotificationPermissionDefault ->
+            "default"
+
+        NotificationPermissionDenied ->
+            "denied"
+
+        NotificationPermissionGranted ->
+            "granted"
+
+
+notificationPermissionFromString : String -> Maybe NotificationPermission
+notificationPermissionFromString string_ =
+    case string_ of
+        "default" ->
+            Just NotificationPermissionDefault
+
+        "denied" ->
+            Just NotificationPermissionDenied
+
+        "granted" ->
+            Just NotificationPermissionGranted
+
+        _ ->
+            Nothing
+
+
+port gotNewNotificationsPermission : (Json.Decode.Value -> msg) -> Sub msg
+
+
+subscribeToGotNewNotificationsPermission : (Result Json.Decode.Error NotificationPermission -> msg) -> Sub msg
+subscribeToGotNewNotificationsPermission toMsg =
+    gotNewNotificationsPermission (Json.Decode.decodeValue notificationPermissionDecoder >> toMsg)
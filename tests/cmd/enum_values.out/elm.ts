@@ -0,0 +1,23 @@
+// Warning: this file is automatically generated. Don't edit by hand!
+// elm-duet 0.1.0, schema c875d4ba41a5a532, content e35bc61505f09b1c
+
+declare module Elm {
+  type RefreshInterval =
+    | /** Refresh once an hour. */ "1h"
+    | /** @deprecated use 1h instead */ "60m"
+    | /** Refresh whenever the server says something changed. */ "ACTIVE_v2";
+
+  namespace Main {
+    type Flags = Record<string, never>;
+
+    type Ports = {
+      setRefreshInterval?: {
+        subscribe: (callback: (value: Elm.RefreshInterval) => void) => void;
+      };
+    };
+
+    function init(config: { flags: Flags; node: HTMLElement }): {
+      ports?: Ports;
+    };
+  }
+}
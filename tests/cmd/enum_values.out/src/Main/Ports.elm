@@ -0,0 +1,100 @@
+port module Main.Ports exposing (..)
+
+{-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema c875d4ba41a5a532, content db440a362362ad9e
+-}
+
+import Dict exposing (Dict)
+import Json.Decode
+import Json.Decode.Pipeline
+import Json.Encode
+
+
+{-| `Continuously`: Refresh whenever the server says something changed.
+
+`EveryHour`: Refresh once an hour.
+
+`EverySixtyMinutes` is deprecated: use 1h instead
+-}
+type RefreshInterval
+    = EveryHour
+    | EverySixtyMinutes
+    | Continuously
+
+
+refreshIntervalDecoder : Json.Decode.Decoder RefreshInterval
+refreshIntervalDecoder =
+    Json.Decode.andThen
+        (/tag ->
+            case tag of
+                "1h" ->
+                    Json.Decode.succeed EveryHour
+
+                "60m" ->
+                    Json.Decode.succeed EverySixtyMinutes
+
+                "ACTIVE_v2" ->
+                    Json.Decode.succeed Continuously
+
+                unknown ->
+                    Json.Decode.fail ("Unknown value `" ++ unknown ++ "`")
+        )
+        Json.Decode.string
+
+
+encodeRefreshInterval : RefreshInterval -> Json.Encode.Value
+encodeRefreshInterval refreshInterval_ =
+    case refreshInterval_ of
+        EveryHour ->
+            Json.Encode.string "1h"
+
+        EverySixtyMinutes ->
+            Json.Encode.string "60m"
+
+        Continuously ->
+            Json.Encode.string "ACTIVE_v2"
+
+
+allRefreshInterval : List RefreshInterval
+allRefreshInterval =
+    [ EveryHour
+    , EverySixtyMinutes
+    , Continuously
+    ]
+
+
+refreshIntervalToString : RefreshInterval -> String
+refreshIntervalToString refreshInterval_ =
+    case refreshInterval_ of
+        EveryHour ->
+            "1h"
+
+        EverySixtyMinutes ->
+            "60m"
+
+        Continuously ->
+            "ACTIVE_v2"
+
+
+refreshIntervalFromString : String -> Maybe RefreshInterval
+refreshIntervalFromString string_ =
+    case string_ of
+        "1h" ->
+            Just EveryHour
+
+        "60m" ->
+            Just EverySixtyMinutes
+
+        "ACTIVE_v2" ->
+            Just Continuously
+
+        _ ->
+            Nothing
+
+
+port setRefreshInterval : Json.Decode.Value -> Cmd msg
+
+
+sendSetRefreshInterval : RefreshInterval -> Cmd msg
+sendSetRefreshInterval =
+    encodeRefreshInterval >> setRefreshInterval
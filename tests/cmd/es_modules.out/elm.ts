@@ -0,0 +1,42 @@
+// Warning: this file is automatically generated. Don't edit by hand!
+// elm-duet 0.1.0, schema 0a1a07a1a2b67b16, content 0fcf53e545712fbd
+
+declare module "*/Admin/Users.elm" {
+  namespace Elm {
+    namespace Admin {
+      namespace Users {
+        type Flags = Record<string, never>;
+
+        type Ports = {
+          selectUser?: {
+            send: (value: { id: number }) => void;
+          };
+        };
+
+        function init(config: { flags: Flags; node: HTMLElement }): {
+          ports?: Ports;
+        };
+      }
+    }
+  }
+}
+
+declare module "*/Main.elm" {
+  namespace Elm {
+    namespace Main {
+      type Flags = {
+        apiUrl: string;
+      };
+
+      type Ports = {
+        notify?: {
+          subscribe: (callback: (value: string) => void) => void;
+        };
+      };
+
+      function init(config: { flags: Flags; node: HTMLElement }): {
+        ports?: Ports;
+      };
+    }
+  }
+}
@@ -0,0 +1,82 @@
+// Warning: this file is automatically generated. Don't edit by hand!
+// elm-duet 0.1.0, schema 0a1a07a1a2b67b16, content 935c2e231f378476
+
+import type { Elm } from "./Admin/Users.elm";
+
+export function isSelectUser(
+  value: unknown,
+): value is Parameters<
+  NonNullable<Elm.Admin.Users.Ports["selectUser"]>["send"]
+>[0] {
+  return checkSelectUser(value, "$") === null;
+}
+
+export function assertSelectUser(
+  value: unknown,
+): asserts value is Parameters<
+  NonNullable<Elm.Admin.Users.Ports["selectUser"]>["send"]
+>[0] {
+  const problem = checkSelectUser(value, "$");
+  if (problem !== null) {
+    throw new TypeError(problem);
+  }
+}
+
+/**
+ * Subscribe to every port Elm sends values through, and get functions for sending values to
+ * Elm. Elm removes ports that are never used, so we warn about missing ports (or throw, or
+ * ignore them, depending on `options.missingPorts`.)
+ */
+export function connectAdminUsers(
+  app: { ports?: Elm.Admin.Users.Ports },
+  handlers: {},
+  options: { missingPorts?: "throw" | "warn" | "ignore" } = {},
+): {
+  selectUser: (
+    value: Parameters<
+      NonNullable<Elm.Admin.Users.Ports["selectUser"]>["send"]
+    >[0],
+  ) => void;
+} {
+  const ports: Elm.Admin.Users.Ports = app.ports ?? {};
+  const missing = (port: string) => {
+    const message = `the Admin.Users Elm module does not have a "${port}" port. Is it used in Elm?`;
+    if (options.missingPorts === "throw") {
+      throw new Error(message);
+    } else if (options.missingPorts !== "ignore") {
+      console.warn(message);
+    }
+  };
+
+  if (!ports.selectUser) {
+    missing("selectUser");
+  }
+
+  return {
+    selectUser: (value) => {
+      ports.selectUser?.send(value);
+    },
+  };
+}
+
+function checkSelectUser(value: unknown, path: string): string | null {
+  if (typeof value !== "object" || value === null || Array.isArray(value)) {
+    return `${path}: expected an object`;
+  }
+  const object0 = value as Record<string, unknown>;
+  if (!("id" in object0)) {
+    return `${path}: missing "id"`;
+  }
+  {
+    const value1 = object0["id"];
+    if (
+      typeof value1 !== "number" ||
+      !Number.isInteger(value1) ||
+      value1 < 0 ||
+      value1 > 4294967295
+    ) {
+      return `${path}.id: expected an integer between 0 and 4294967295`;
+    }
+  }
+  return null;
+}
@@ -0,0 +1,93 @@
+// Warning: this file is automatically generated. Don't edit by hand!
+// elm-duet 0.1.0, schema 0a1a07a1a2b67b16, content 7a2d6c7b8f773137
+
+import type { Elm } from "./Main.elm";
+
+export function isFlags(value: unknown): value is Elm.Main.Flags {
+  return checkFlags(value, "$") === null;
+}
+
+export function assertFlags(value: unknown): asserts value is Elm.Main.Flags {
+  const problem = checkFlags(value, "$");
+  if (problem !== null) {
+    throw new TypeError(problem);
+  }
+}
+
+export function isNotify(
+  value: unknown,
+): value is Parameters<
+  Parameters<NonNullable<Elm.Main.Ports["notify"]>["subscribe"]>[0]
+>[0] {
+  return checkNotify(value, "$") === null;
+}
+
+export function assertNotify(
+  value: unknown,
+): asserts value is Parameters<
+  Parameters<NonNullable<Elm.Main.Ports["notify"]>["subscribe"]>[0]
+>[0] {
+  const problem = checkNotify(value, "$");
+  if (problem !== null) {
+    throw new TypeError(problem);
+  }
+}
+
+/**
+ * Subscribe to every port Elm sends values through, and get functions for sending values to
+ * Elm. Elm removes ports that are never used, so we warn about missing ports (or throw, or
+ * ignore them, depending on `options.missingPorts`.)
+ */
+export function connectMain(
+  app: { ports?: Elm.Main.Ports },
+  handlers: {
+    notify: (
+      value: Parameters<
+        Parameters<NonNullable<Elm.Main.Ports["notify"]>["subscribe"]>[0]
+      >[0],
+    ) => void;
+  },
+  options: { missingPorts?: "throw" | "warn" | "ignore" } = {},
+): {} {
+  const ports: Elm.Main.Ports = app.ports ?? {};
+  const missing = (port: string) => {
+    const message = `the Main Elm module does not have a "${port}" port. Is it used in Elm?`;
+    if (options.missingPorts === "throw") {
+      throw new Error(message);
+    } else if (options.missingPorts !== "ignore") {
+      console.warn(message);
+    }
+  };
+
+  if (ports.notify) {
+    ports.notify.subscribe(handlers.notify);
+  } else {
+    missing("notify");
+  }
+
+  return {};
+}
+
+function checkFlags(value: unknown, path: string): string | null {
+  if (typeof value !== "object" || value === null || Array.isArray(value)) {
+    return `${path}: expected an object`;
+  }
+  const object0 = value as Record<string, unknown>;
+  if (!("apiUrl" in object0)) {
+    return `${path}: missing "apiUrl"`;
+  }
+  {
+    const value1 = object0["apiUrl"];
+    if (typeof value1 !== "string") {
+      return `${path}.apiUrl: expected a string`;
+    }
+  }
+  return null;
+}
+
+function checkNotify(value: unknown, path: string): string | null {
+  if (typeof value !== "string") {
+    return `${path}: expected a string`;
+  }
+  return null;
+}
@@ -0,0 +1,36 @@
+port module Admin.Users.Ports exposing (..)
+
+{-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema 0a1a07a1a2b67b16, content a732121572a11805
+-}
+
+import Dict exposing (Dict)
+import Json.Decode
+import Json.Decode.Pipeline
+import Json.Encode
+
+
+type alias SelectUser =
+    { id : Int
+    }
+
+
+selectUserDecoder : Json.Decode.Decoder SelectUser
+selectUserDecoder =
+    Json.Decode.succeed SelectUser
+        |> Json.Decode.Pipeline.required "id" Json.Decode.int
+
+
+encodeSelectUser : SelectUser -> Json.Encode.Value
+encodeSelectUser selectUser_ =
+    Json.Encode.object
+        [ ( "id", Json.Encode.int selectUser_.id )
+        ]
+
+
+port selectUser : (Json.Decode.Value -> msg) -> Sub msg
+
+
+subscribeToSelectUser : (Result Json.Decode.Error SelectUser -> msg) -> Sub msg
+subscribeToSelectUser toMsg =
+    selectUser (Json.Decode.decodeValue selectUserDecoder >> toMsg)
@@ -0,0 +1,28 @@
+module Main.Flags exposing (..)
+
+{-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema 0a1a07a1a2b67b16, content 17cbc7f417b03021
+-}
+
+import Dict exposing (Dict)
+import Json.Decode
+import Json.Decode.Pipeline
+import Json.Encode
+
+
+type alias Flags =
+    { apiUrl : String
+    }
+
+
+flagsDecoder : Json.Decode.Decoder Flags
+flagsDecoder =
+    Json.Decode.succeed Flags
+        |> Json.Decode.Pipeline.required "apiUrl" Json.Decode.string
+
+
+encodeFlags : Flags -> Json.Encode.Value
+encodeFlags flags_ =
+    Json.Encode.object
+        [ ( "apiUrl", Json.Encode.string flags_.apiUrl )
+        ]
@@ -0,0 +1,32 @@
+port module Main.Ports exposing (..)
+
+{-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema 0a1a07a1a2b67b16, content 9b51e9cd4b62c460
+-}
+
+import Dict exposing (Dict)
+import Json.Decode
+import Json.Decode.Pipeline
+import Json.Encode
+
+
+type alias Notify =
+    String
+
+
+notifyDecoder : Json.Decode.Decoder Notify
+notifyDecoder =
+    Json.Decode.string
+
+
+encodeNotify : Notify -> Json.Encode.Value
+encodeNotify notify_ =
+    Json.Encode.string notify_
+
+
+port notify : Json.Decode.Value -> Cmd msg
+
+
+sendNotify : Notify -> Cmd msg
+sendNotify =
+    encodeNotify >> notify
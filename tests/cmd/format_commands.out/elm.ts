@@ -0,0 +1,16 @@
+// Warning: this file is automatically generated. Don't edit by hand!
+// elm-duet 0.1.0, schema 5542e389b0730024, content e1e4080cc0c0851b
+
+declare module Elm {
+  namespace Main {
+    type Flags = {
+      name: string
+    }
+
+    type Ports = Record<string, never>
+
+    function init(config: { flags: Flags; node: HTMLElement }): {
+      ports?: Ports
+    }
+  }
+}
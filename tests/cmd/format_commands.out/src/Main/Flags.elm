@@ -0,0 +1,28 @@
+module Main.Flags exposing (..)
+
+{-| WARNING: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema 5542e389b0730024, content f99821223827ebc9
+-}
+
+import Dict exposing (Dict)
+import Json.Decode
+import Json.Decode.Pipeline
+import Json.Encode
+
+
+type alias Flags =
+    { name : String
+    }
+
+
+flagsDecoder : Json.Decode.Decoder Flags
+flagsDecoder =
+    Json.Decode.succeed Flags
+        |> Json.Decode.Pipeline.required "name" Json.Decode.string
+
+
+encodeFlags : Flags -> Json.Encode.Value
+encodeFlags flags_ =
+    Json.Encode.object
+        [ ( "name", Json.Encode.string flags_.name )
+        ]
@@ -0,0 +1,16 @@
+// Warning: this file is automatically generated. Don't edit by hand!
+// elm-duet 0.1.0, schema 212898360dd4773b, content 69a8047cc0a43229
+
+declare module Elm {
+  namespace Main {
+    type Flags = {
+      currentTimeMillis: number;
+    };
+
+    type Ports = Record<string, never>;
+
+    function init(config: { flags: Flags; node: HTMLElement }): {
+      ports?: Ports;
+    };
+  }
+}
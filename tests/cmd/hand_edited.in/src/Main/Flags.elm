@@ -0,0 +1,29 @@
+module Main.Flags exposing (..)
+
+{-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema 212898360dd4773b, content ca3cb1cfb22ecafd
+-}
+
+import Dict exposing (Dict)
+import Json.Decode
+import Json.Decode.Pipeline
+import Json.Encode
+
+
+type alias Flags =
+    { currentTimeMillis : Int
+    , editedByHand : Bool
+    }
+
+
+flagsDecoder : Json.Decode.Decoder Flags
+flagsDecoder =
+    Json.Decode.succeed Flags
+        |> Json.Decode.Pipeline.required "currentTimeMillis" Json.Decode.int
+
+
+encodeFlags : Flags -> Json.Encode.Value
+encodeFlags flags_ =
+    Json.Encode.object
+        [ ( "currentTimeMillis", Json.Encode.int flags_.currentTimeMillis )
+        ]
@@ -1,4 +1,5 @@
 // Warning: this file is automatically generated. Don't edit by hand!
+// elm-duet 0.1.0, schema a226e57b6b188318, content 0f979b583223e68a
 
 declare module Elm {
   namespace Main {
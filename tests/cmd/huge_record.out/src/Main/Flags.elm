@@ -1,6 +1,7 @@
 module Main.Flags exposing (..)
 
 {-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema a226e57b6b188318, content 478adf48065a0870
 -}
 
 import Dict exposing (Dict)
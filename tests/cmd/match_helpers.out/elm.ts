@@ -0,0 +1,82 @@
+// Warning: this file is automatically generated. Don't edit by hand!
+// elm-duet 0.1.0, schema a8f7d02fbfa3b266, content 3814053895f5f1ad
+
+declare module Elm {
+  type FromServer =
+    | {
+        type: "ping";
+      }
+    | {
+        type: "update";
+        version: number;
+      }
+    | {
+        type: string;
+      };
+
+  type ServerEvent =
+    | {
+        type: "ping";
+      }
+    | {
+        type: "update";
+        version: number;
+      }
+    | {
+        type: string;
+      };
+
+  type Shape =
+    | {
+        kind: "circle";
+        radius: number;
+      }
+    | {
+        kind: "square";
+        side: number;
+      };
+
+  namespace Main {
+    type Flags = Record<string, never>;
+
+    type Ports = {
+      fromServer?: {
+        send: (value: Elm.FromServer) => void;
+      };
+      fromWorld?: {
+        send: (
+          value:
+            | {
+                data: string;
+                tag: "message-received";
+              }
+            | {
+                tag: "opened";
+              },
+        ) => void;
+      };
+      toWorld?: {
+        subscribe: (
+          callback: (
+            value:
+              | {
+                  tag: "close";
+                }
+              | {
+                  tag: "connect";
+                  url: string;
+                }
+              | {
+                  shape: Elm.Shape;
+                  tag: "draw";
+                },
+          ) => void,
+        ) => void;
+      };
+    };
+
+    function init(config: { flags: Flags; node: HTMLElement }): {
+      ports?: Ports;
+    };
+  }
+}
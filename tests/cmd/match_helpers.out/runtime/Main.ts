@@ -0,0 +1,385 @@
+// Warning: this file is automatically generated. Don't edit by hand!
+// elm-duet 0.1.0, schema a8f7d02fbfa3b266, content b62d4b049e22eac1
+
+export function isFromServer(
+  value: unknown,
+): value is Parameters<NonNullable<Elm.Main.Ports["fromServer"]>["send"]>[0] {
+  return checkFromServer(value, "$") === null;
+}
+
+export function assertFromServer(
+  value: unknown,
+): asserts value is Parameters<
+  NonNullable<Elm.Main.Ports["fromServer"]>["send"]
+>[0] {
+  const problem = checkFromServer(value, "$");
+  if (problem !== null) {
+    throw new TypeError(problem);
+  }
+}
+
+export function isFromWorld(
+  value: unknown,
+): value is Parameters<NonNullable<Elm.Main.Ports["fromWorld"]>["send"]>[0] {
+  return checkFromWorld(value, "$") === null;
+}
+
+export function assertFromWorld(
+  value: unknown,
+): asserts value is Parameters<
+  NonNullable<Elm.Main.Ports["fromWorld"]>["send"]
+>[0] {
+  const problem = checkFromWorld(value, "$");
+  if (problem !== null) {
+    throw new TypeError(problem);
+  }
+}
+
+export function isToWorld(
+  value: unknown,
+): value is Parameters<
+  Parameters<NonNullable<Elm.Main.Ports["toWorld"]>["subscribe"]>[0]
+>[0] {
+  return checkToWorld(value, "$") === null;
+}
+
+export function assertToWorld(
+  value: unknown,
+): asserts value is Parameters<
+  Parameters<NonNullable<Elm.Main.Ports["toWorld"]>["subscribe"]>[0]
+>[0] {
+  const problem = checkToWorld(value, "$");
+  if (problem !== null) {
+    throw new TypeError(problem);
+  }
+}
+
+export function matchFromServer<R>(
+  value: Elm.FromServer,
+  handlers: {
+    ping: (payload: Extract<typeof value, { type: "ping" }>) => R;
+    update: (payload: Extract<typeof value, { type: "update" }>) => R;
+  },
+  otherwise: (payload: { type: string }) => R,
+): R {
+  switch (value.type) {
+    case "ping":
+      return handlers.ping(value as Extract<typeof value, { type: "ping" }>);
+    case "update":
+      return handlers.update(
+        value as Extract<typeof value, { type: "update" }>,
+      );
+  }
+
+  return otherwise(value as { type: string });
+}
+
+export function matchFromWorld<R>(
+  value:
+    | {
+        data: string;
+        tag: "message-received";
+      }
+    | {
+        tag: "opened";
+      },
+  handlers: {
+    "message-received": (
+      payload: Extract<typeof value, { tag: "message-received" }>,
+    ) => R;
+    opened: (payload: Extract<typeof value, { tag: "opened" }>) => R;
+  },
+): R {
+  switch (value.tag) {
+    case "message-received":
+      return handlers["message-received"](value);
+    case "opened":
+      return handlers.opened(value);
+  }
+
+  throw new TypeError(
+    `unexpected tag: ${JSON.stringify((value as { tag: unknown }).tag)}`,
+  );
+}
+
+export function matchShape<R>(
+  value: Elm.Shape,
+  handlers: {
+    circle: (payload: Extract<typeof value, { kind: "circle" }>) => R;
+    square: (payload: Extract<typeof value, { kind: "square" }>) => R;
+  },
+): R {
+  switch (value.kind) {
+    case "circle":
+      return handlers.circle(value);
+    case "square":
+      return handlers.square(value);
+  }
+
+  throw new TypeError(
+    `unexpected kind: ${JSON.stringify((value as { kind: unknown }).kind)}`,
+  );
+}
+
+export function matchToWorld<R>(
+  value:
+    | {
+        tag: "close";
+      }
+    | {
+        tag: "connect";
+        url: string;
+      }
+    | {
+        shape: Elm.Shape;
+        tag: "draw";
+      },
+  handlers: {
+    close: (payload: Extract<typeof value, { tag: "close" }>) => R;
+    connect: (payload: Extract<typeof value, { tag: "connect" }>) => R;
+    draw: (payload: Extract<typeof value, { tag: "draw" }>) => R;
+  },
+): R {
+  switch (value.tag) {
+    case "close":
+      return handlers.close(value);
+    case "connect":
+      return handlers.connect(value);
+    case "draw":
+      return handlers.draw(value);
+  }
+
+  throw new TypeError(
+    `unexpected tag: ${JSON.stringify((value as { tag: unknown }).tag)}`,
+  );
+}
+
+/**
+ * Subscribe to every port Elm sends values through, and get functions for sending values to
+ * Elm. Elm removes ports that are never used, so we warn about missing ports (or throw, or
+ * ignore them, depending on `options.missingPorts`.)
+ */
+export function connectMain(
+  app: { ports?: Elm.Main.Ports },
+  handlers: {
+    toWorld: (
+      value: Parameters<
+        Parameters<NonNullable<Elm.Main.Ports["toWorld"]>["subscribe"]>[0]
+      >[0],
+    ) => void;
+  },
+  options: { missingPorts?: "throw" | "warn" | "ignore" } = {},
+): {
+  fromServer: (
+    value: Parameters<NonNullable<Elm.Main.Ports["fromServer"]>["send"]>[0],
+  ) => void;
+  fromWorld: (
+    value: Parameters<NonNullable<Elm.Main.Ports["fromWorld"]>["send"]>[0],
+  ) => void;
+} {
+  const ports: Elm.Main.Ports = app.ports ?? {};
+  const missing = (port: string) => {
+    const message = `the Main Elm module does not have a "${port}" port. Is it used in Elm?`;
+    if (options.missingPorts === "throw") {
+      throw new Error(message);
+    } else if (options.missingPorts !== "ignore") {
+      console.warn(message);
+    }
+  };
+
+  if (!ports.fromServer) {
+    missing("fromServer");
+  }
+
+  if (!ports.fromWorld) {
+    missing("fromWorld");
+  }
+
+  if (ports.toWorld) {
+    ports.toWorld.subscribe(handlers.toWorld);
+  } else {
+    missing("toWorld");
+  }
+
+  return {
+    fromServer: (value) => {
+      ports.fromServer?.send(value);
+    },
+    fromWorld: (value) => {
+      ports.fromWorld?.send(value);
+    },
+  };
+}
+
+function checkFromServer(value: unknown, path: string): string | null {
+  {
+    const problem = checkServerEventRef(value, `${path}`);
+    if (problem !== null) {
+      return problem;
+    }
+  }
+  return null;
+}
+
+function checkFromWorld(value: unknown, path: string): string | null {
+  if (typeof value !== "object" || value === null || Array.isArray(value)) {
+    return `${path}: expected an object`;
+  }
+  const object0 = value as Record<string, unknown>;
+  switch (object0["tag"]) {
+    case "message-received": {
+      if (typeof value !== "object" || value === null || Array.isArray(value)) {
+        return `${path}: expected an object`;
+      }
+      const object1 = value as Record<string, unknown>;
+      if (!("data" in object1)) {
+        return `${path}: missing "data"`;
+      }
+      {
+        const value2 = object1["data"];
+        if (typeof value2 !== "string") {
+          return `${path}.data: expected a string`;
+        }
+      }
+      break;
+    }
+    case "opened": {
+      break;
+    }
+    default:
+      return `${path}.tag: expected one of "message-received", "opened"`;
+  }
+  return null;
+}
+
+function checkServerEventRef(value: unknown, path: string): string | null {
+  if (typeof value !== "object" || value === null || Array.isArray(value)) {
+    return `${path}: expected an object`;
+  }
+  const object0 = value as Record<string, unknown>;
+  switch (object0["type"]) {
+    case "ping": {
+      break;
+    }
+    case "update": {
+      if (typeof value !== "object" || value === null || Array.isArray(value)) {
+        return `${path}: expected an object`;
+      }
+      const object1 = value as Record<string, unknown>;
+      if (!("version" in object1)) {
+        return `${path}: missing "version"`;
+      }
+      {
+        const value2 = object1["version"];
+        if (
+          typeof value2 !== "number" ||
+          !Number.isInteger(value2) ||
+          value2 < 0 ||
+          value2 > 4294967295
+        ) {
+          return `${path}.version: expected an integer between 0 and 4294967295`;
+        }
+      }
+      break;
+    }
+    default:
+      if (typeof object0["type"] !== "string") {
+        return `${path}.type: expected a string`;
+      }
+  }
+  return null;
+}
+
+function checkShapeRef(value: unknown, path: string): string | null {
+  if (typeof value !== "object" || value === null || Array.isArray(value)) {
+    return `${path}: expected an object`;
+  }
+  const object0 = value as Record<string, unknown>;
+  switch (object0["kind"]) {
+    case "circle": {
+      if (typeof value !== "object" || value === null || Array.isArray(value)) {
+        return `${path}: expected an object`;
+      }
+      const object1 = value as Record<string, unknown>;
+      if (!("radius" in object1)) {
+        return `${path}: missing "radius"`;
+      }
+      {
+        const value2 = object1["radius"];
+        if (typeof value2 !== "number") {
+          return `${path}.radius: expected a number`;
+        }
+      }
+      break;
+    }
+    case "square": {
+      if (typeof value !== "object" || value === null || Array.isArray(value)) {
+        return `${path}: expected an object`;
+      }
+      const object1 = value as Record<string, unknown>;
+      if (!("side" in object1)) {
+        return `${path}: missing "side"`;
+      }
+      {
+        const value2 = object1["side"];
+        if (typeof value2 !== "number") {
+          return `${path}.side: expected a number`;
+        }
+      }
+      break;
+    }
+    default:
+      return `${path}.kind: expected one of "circle", "square"`;
+  }
+  return null;
+}
+
+function checkToWorld(value: unknown, path: string): string | null {
+  if (typeof value !== "object" || value === null || Array.isArray(value)) {
+    return `${path}: expected an object`;
+  }
+  const object0 = value as Record<string, unknown>;
+  switch (object0["tag"]) {
+    case "close": {
+      break;
+    }
+    case "connect": {
+      if (typeof value !== "object" || value === null || Array.isArray(value)) {
+        return `${path}: expected an object`;
+      }
+      const object1 = value as Record<string, unknown>;
+      if (!("url" in object1)) {
+        return `${path}: missing "url"`;
+      }
+      {
+        const value2 = object1["url"];
+        if (typeof value2 !== "string") {
+          return `${path}.url: expected a string`;
+        }
+      }
+      break;
+    }
+    case "draw": {
+      if (typeof value !== "object" || value === null || Array.isArray(value)) {
+        return `${path}: expected an object`;
+      }
+      const object1 = value as Record<string, unknown>;
+      if (!("shape" in object1)) {
+        return `${path}: missing "shape"`;
+      }
+      {
+        const value2 = object1["shape"];
+        {
+          const problem = checkShapeRef(value2, `${path}.shape`);
+          if (problem !== null) {
+            return problem;
+          }
+        }
+      }
+      break;
+    }
+    default:
+      return `${path}.tag: expected one of "close", "connect", "draw"`;
+  }
+  return null;
+}
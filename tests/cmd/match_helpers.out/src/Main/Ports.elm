@@ -0,0 +1,335 @@
+port module Main.Ports exposing (..)
+
+{-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema a8f7d02fbfa3b266, content b3e0ee796852fe30
+-}
+
+import Dict exposing (Dict)
+import Json.Decode
+import Json.Decode.Pipeline
+import Json.Encode
+
+
+type alias TypePing =
+    {}
+
+
+typePingDecoder : Json.Decode.Decoder TypePing
+typePingDecoder =
+    Json.Decode.succeed TypePing
+
+
+encodeTypePing : TypePing -> Json.Encode.Value
+encodeTypePing typePing_ =
+    Json.Encode.object
+        [ ( "type", Json.Encode.string "ping" )
+        ]
+
+
+type alias Update =
+    { version : Int
+    }
+
+
+updateDecoder : Json.Decode.Decoder Update
+updateDecoder =
+    Json.Decode.succeed Update
+        |> Json.Decode.Pipeline.required "version" Json.Decode.int
+
+
+encodeUpdate : Update -> Json.Encode.Value
+encodeUpdate update_ =
+    Json.Encode.object
+        [ ( "version", Json.Encode.int update_.version )
+        , ( "type", Json.Encode.string "update" )
+        ]
+
+
+type FromServer
+    = FromServerPing TypePing
+    | FromServerUpdate Update
+    | FromServerUnknown String Json.Decode.Value
+
+
+fromServerDecoder : Json.Decode.Decoder FromServer
+fromServerDecoder =
+    Json.Decode.andThen
+        (/tag ->
+            case tag of
+                "ping" ->
+                    Json.Decode.map FromServerPing typePingDecoder
+
+                "update" ->
+                    Json.Decode.map FromServerUpdate updateDecoder
+
+                unknown ->
+                    Json.Decode.map (FromServerUnknown unknown) Json.Decode.value
+        )
+        (Json.Decode.field "type" Json.Decode.string)
+
+
+encodeFromServer : FromServer -> Json.Encode.Value
+encodeFromServer fromServer_ =
+    case fromServer_ of
+        FromServerPing fromServerPing ->
+            encodeTypePing fromServerPing
+
+        FromServerUpdate fromServerUpdate ->
+            encodeUpdate fromServerUpdate
+
+        FromServerUnknown _ raw ->
+            raw
+
+
+type alias MessageReceived =
+    { data : String
+    }
+
+
+messageReceivedDecoder : Json.Decode.Decoder MessageReceived
+messageReceivedDecoder =
+    Json.Decode.succeed MessageReceived
+        |> Json.Decode.Pipeline.required "data" Json.Decode.string
+
+
+encodeMessageReceived : MessageReceived -> Json.Encode.Value
+encodeMessageReceived messageReceived_ =
+    Json.Encode.object
+        [ ( "data", Json.Encode.string messageReceived_.data )
+        , ( "tag", Json.Encode.string "message-received" )
+        ]
+
+
+type alias TagOpened =
+    {}
+
+
+tagOpenedDecoder : Json.Decode.Decoder TagOpened
+tagOpenedDecoder =
+    Json.Decode.succeed TagOpened
+
+
+encodeTagOpened : TagOpened -> Json.Encode.Value
+encodeTagOpened tagOpened_ =
+    Json.Encode.object
+        [ ( "tag", Json.Encode.string "opened" )
+        ]
+
+
+type FromWorld
+    = FromWorldMessageReceived MessageReceived
+    | FromWorldOpened TagOpened
+
+
+fromWorldDecoder : Json.Decode.Decoder FromWorld
+fromWorldDecoder =
+    Json.Decode.andThen
+        (/tag ->
+            case tag of
+                "message-received" ->
+                    Json.Decode.map FromWorldMessageReceived messageReceivedDecoder
+
+                "opened" ->
+                    Json.Decode.map FromWorldOpened tagOpenedDecoder
+
+                unknown ->
+                    Json.Decode.fail ("Unknown value `" ++ unknown ++ "`")
+        )
+        (Json.Decode.field "tag" Json.Decode.string)
+
+
+encodeFromWorld : FromWorld -> Json.Encode.Value
+encodeFromWorld fromWorld_ =
+    case fromWorld_ of
+        FromWorldMessageReceived fromWorldMessageReceived ->
+            encodeMessageReceived fromWorldMessageReceived
+
+        FromWorldOpened fromWorldOpened ->
+            encodeTagOpened fromWorldOpened
+
+
+type alias TagClose =
+    {}
+
+
+tagCloseDecoder : Json.Decode.Decoder TagClose
+tagCloseDecoder =
+    Json.Decode.succeed TagClose
+
+
+encodeTagClose : TagClose -> Json.Encode.Value
+encodeTagClose tagClose_ =
+    Json.Encode.object
+        [ ( "tag", Json.Encode.string "close" )
+        ]
+
+
+type alias Connect =
+    { url : String
+    }
+
+
+connectDecoder : Json.Decode.Decoder Connect
+connectDecoder =
+    Json.Decode.succeed Connect
+        |> Json.Decode.Pipeline.required "url" Json.Decode.string
+
+
+encodeConnect : Connect -> Json.Encode.Value
+encodeConnect connect_ =
+    Json.Encode.object
+        [ ( "url", Json.Encode.string connect_.url )
+        , ( "tag", Json.Encode.string "connect" )
+        ]
+
+
+type alias Circle =
+    { radius : Float
+    }
+
+
+circleDecoder : Json.Decode.Decoder Circle
+circleDecoder =
+    Json.Decode.succeed Circle
+        |> Json.Decode.Pipeline.required "radius" Json.Decode.float
+
+
+encodeCircle : Circle -> Json.Encode.Value
+encodeCircle circle_ =
+    Json.Encode.object
+        [ ( "radius", Json.Encode.float circle_.radius )
+        , ( "kind", Json.Encode.string "circle" )
+        ]
+
+
+type alias Square =
+    { side : Float
+    }
+
+
+squareDecoder : Json.Decode.Decoder Square
+squareDecoder =
+    Json.Decode.succeed Square
+        |> Json.Decode.Pipeline.required "side" Json.Decode.float
+
+
+encodeSquare : Square -> Json.Encode.Value
+encodeSquare square_ =
+    Json.Encode.object
+        [ ( "side", Json.Encode.float square_.side )
+        , ( "kind", Json.Encode.string "square" )
+        ]
+
+
+type Shape
+    = ShapeCircle Circle
+    | ShapeSquare Square
+
+
+shapeDecoder : Json.Decode.Decoder Shape
+shapeDecoder =
+    Json.Decode.andThen
+        (/tag ->
+            case tag of
+                "circle" ->
+                    Json.Decode.map ShapeCircle circleDecoder
+
+                "square" ->
+                    Json.Decode.map ShapeSquare squareDecoder
+
+                unknown ->
+                    Json.Decode.fail ("Unknown value `" ++ unknown ++ "`")
+        )
+        (Json.Decode.field "kind" Json.Decode.string)
+
+
+encodeShape : Shape -> Json.Encode.Value
+encodeShape shape_ =
+    case shape_ of
+        ShapeCircle shapeCircle ->
+            encodeCircle shapeCircle
+
+        ShapeSquare shapeSquare ->
+            encodeSquare shapeSquare
+
+
+type alias Draw =
+    { shape : Shape
+    }
+
+
+drawDecoder : Json.Decode.Decoder Draw
+drawDecoder =
+    Json.Decode.succeed Draw
+        |> Json.Decode.Pipeline.required "shape" shapeDecoder
+
+
+encodeDraw : Draw -> Json.Encode.Value
+encodeDraw draw_ =
+    Json.Encode.object
+        [ ( "shape", encodeShape draw_.shape )
+        , ( "tag", Json.Encode.string "draw" )
+        ]
+
+
+type ToWorld
+    = ToWorldClose TagClose
+    | ToWorldConnect Connect
+    | ToWorldDraw Draw
+
+
+toWorldDecoder : Json.Decode.Decoder ToWorld
+toWorldDecoder =
+    Json.Decode.andThen
+        (/tag ->
+            case tag of
+                "close" ->
+                    Json.Decode.map ToWorldClose tagCloseDecoder
+
+                "connect" ->
+                    Json.Decode.map ToWorldConnect connectDecoder
+
+                "draw" ->
+                    Json.Decode.map ToWorldDraw drawDecoder
+
+                unknown ->
+                    Json.Decode.fail ("Unknown value `" ++ unknown ++ "`")
+        )
+        (Json.Decode.field "tag" Json.Decode.string)
+
+
+encodeToWorld : ToWorld -> Json.Encode.Value
+encodeToWorld toWorld_ =
+    case toWorld_ of
+        ToWorldClose toWorldClose ->
+            encodeTagClose toWorldClose
+
+        ToWorldConnect toWorldConnect ->
+            encodeConnect toWorldConnect
+
+        ToWorldDraw toWorldDraw ->
+            encodeDraw toWorldDraw
+
+
+port fromServer : (Json.Decode.Value -> msg) -> Sub msg
+
+
+subscribeToFromServer : (Result Json.Decode.Error FromServer -> msg) -> Sub msg
+subscribeToFromServer toMsg =
+    fromServer (Json.Decode.decodeValue fromServerDecoder >> toMsg)
+
+
+port fromWorld : (Json.Decode.Value -> msg) -> Sub msg
+
+
+subscribeToFromWorld : (Result Json.Decode.Error FromWorld -> msg) -> Sub msg
+subscribeToFromWorld toMsg =
+    fromWorld (Json.Decode.decodeValue fromWorldDecoder >> toMsg)
+
+
+port toWorld : Json.Decode.Value -> Cmd msg
+
+
+sendToWorld : ToWorld -> Cmd msg
+sendToWorld =
+    encodeToWorld >> toWorld
@@ -1,4 +1,5 @@
 // Warning: this file is automatically generated. Don't edit by hand!
+// elm-duet 0.1.0, schema e739c1a51b7cefbd, content f58ace8432300482
 
 declare module Elm {
   namespace A {
@@ -1,6 +1,7 @@
 module A.Flags exposing (..)
 
 {-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema e739c1a51b7cefbd, content 81d99257e0278b5b
 -}
 
 import Dict exposing (Dict)
@@ -1,6 +1,7 @@
 module B.B2.Flags exposing (..)
 
 {-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema e739c1a51b7cefbd, content 4eef3ccf3de925be
 -}
 
 import Dict exposing (Dict)
@@ -1,6 +1,7 @@
 module B.Flags exposing (..)
 
 {-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema e739c1a51b7cefbd, content 453146278085d3b2
 -}
 
 import Dict exposing (Dict)
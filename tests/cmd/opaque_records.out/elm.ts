@@ -0,0 +1,36 @@
+// Warning: this file is automatically generated. Don't edit by hand!
+// elm-duet 0.1.0, schema f9ced0fc36db90b3, content 0c7ca4dc325c2b5f
+
+declare module Elm {
+  type Notification = {
+    body?: string;
+    tags: string[];
+    title: string;
+  };
+
+  namespace Main {
+    type Flags = {
+      empty: {};
+    };
+
+    type Ports = {
+      notify?: {
+        subscribe: (callback: (value: Elm.Notification) => void) => void;
+      };
+      toWorld?: {
+        subscribe: (
+          callback: (
+            value: {
+              message: string;
+              tag: "send";
+            },
+          ) => void,
+        ) => void;
+      };
+    };
+
+    function init(config: { flags: Flags; node: HTMLElement }): {
+      ports?: Ports;
+    };
+  }
+}
@@ -0,0 +1,47 @@
+module Main.Flags exposing (Empty, makeEmpty, emptyDecoder, encodeEmpty, Flags, flagsDecoder, encodeFlags)
+
+{-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema f9ced0fc36db90b3, content e550ddec31a49616
+-}
+
+import Dict exposing (Dict)
+import Json.Decode
+import Json.Decode.Pipeline
+import Json.Encode
+
+
+type Empty
+    = Empty {}
+
+
+emptyDecoder : Json.Decode.Decoder Empty
+emptyDecoder =
+    Json.Decode.succeed (Empty {})
+
+
+encodeEmpty : Empty -> Json.Encode.Value
+encodeEmpty (Empty empty_) =
+    Json.Encode.object []
+
+
+makeEmpty : {} -> Empty
+makeEmpty =
+    Empty
+
+
+type alias Flags =
+    { empty : Empty
+    }
+
+
+flagsDecoder : Json.Decode.Decoder Flags
+flagsDecoder =
+    Json.Decode.succeed Flags
+        |> Json.Decode.Pipeline.required "empty" emptyDecoder
+
+
+encodeFlags : Flags -> Json.Encode.Value
+encodeFlags flags_ =
+    Json.Encode.object
+        [ ( "empty", encodeEmpty flags_.empty )
+        ]
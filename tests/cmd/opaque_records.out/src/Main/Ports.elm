@@ -0,0 +1,147 @@
+port module Main.Ports exposing (Notification, makeNotification, notificationBody, notificationTags, notificationTitle, notificationDecoder, encodeNotification, Send, makeSend, sendMessage, sendDecoder, encodeSend, ToWorld(..), toWorldDecoder, encodeToWorld, notify, sendNotify, toWorld, sendToWorld)
+
+{-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema f9ced0fc36db90b3, content 74e880e4ab69a492
+-}
+
+import Dict exposing (Dict)
+import Json.Decode
+import Json.Decode.Pipeline
+import Json.Encode
+
+
+type Notification
+    = Notification
+        { body : Maybe String
+        , tags : List String
+        , title : String
+        }
+
+
+notificationDecoder : Json.Decode.Decoder Notification
+notificationDecoder =
+    Json.Decode.succeed
+        (/body_ tags_ title_ ->
+            Notification
+                { body = body_
+                , tags = tags_
+                , title = title_
+                }
+        )
+        |> Json.Decode.Pipeline.optional "body" (Json.Decode.nullable Json.Decode.string) Nothing
+        |> Json.Decode.Pipeline.required "tags" (Json.Decode.list Json.Decode.string)
+        |> Json.Decode.Pipeline.required "title" Json.Decode.string
+
+
+encodeNotification : Notification -> Json.Encode.Value
+encodeNotification (Notification notification_) =
+    List.filterMap identity
+        [ Maybe.map (/body_ -> ( "body", Json.Encode.string body_ )) notification_.body
+        , Just ( "tags", Json.Encode.list (/value -> Json.Encode.string value) notification_.tags )
+        , Just ( "title", Json.Encode.string notification_.title )
+        ]
+        |> Json.Encode.object
+
+
+makeNotification :
+    { body : Maybe String
+    , tags : List String
+    , title : String
+    }
+    -> Notification
+makeNotification =
+    Notification
+
+
+notificationBody : Notification -> Maybe String
+notificationBody (Notification notification_) =
+    notification_.body
+
+
+notificationTags : Notification -> List String
+notificationTags (Notification notification_) =
+    notification_.tags
+
+
+notificationTitle : Notification -> String
+notificationTitle (Notification notification_) =
+    notification_.title
+
+
+type Send
+    = Send
+        { message : String
+        }
+
+
+sendDecoder : Json.Decode.Decoder Send
+sendDecoder =
+    Json.Decode.succeed
+        (/message_ ->
+            Send
+                { message = message_
+                }
+        )
+        |> Json.Decode.Pipeline.required "message" Json.Decode.string
+
+
+encodeSend : Send -> Json.Encode.Value
+encodeSend (Send send_) =
+    Json.Encode.object
+        [ ( "message", Json.Encode.string send_.message )
+        , ( "tag", Json.Encode.string "send" )
+        ]
+
+
+makeSend :
+    { message : String
+    }
+    -> Send
+makeSend =
+    Send
+
+
+sendMessage : Send -> String
+sendMessage (Send send_) =
+    send_.message
+
+
+type ToWorld
+    = ToWorldSend Send
+
+
+toWorldDecoder : Json.Decode.Decoder ToWorld
+toWorldDecoder =
+    Json.Decode.andThen
+        (/tag ->
+            case tag of
+                "send" ->
+                    Json.Decode.map ToWorldSend sendDecoder
+
+                unknown ->
+                    Json.Decode.fail ("Unknown value `" ++ unknown ++ "`")
+        )
+        (Json.Decode.field "tag" Json.Decode.string)
+
+
+encodeToWorld : ToWorld -> Json.Encode.Value
+encodeToWorld toWorld_ =
+    case toWorld_ of
+        ToWorldSend toWorldSend ->
+            encodeSend toWorldSend
+
+
+port notify : Json.Decode.Value -> Cmd msg
+
+
+sendNotify : Notification -> Cmd msg
+sendNotify =
+    encodeNotification >> notify
+
+
+port toWorld : Json.Decode.Value -> Cmd msg
+
+
+sendToWorld : ToWorld -> Cmd msg
+sendToWorld =
+    encodeToWorld >> toWorld
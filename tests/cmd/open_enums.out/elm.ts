@@ -0,0 +1,35 @@
+// Warning: this file is automatically generated. Don't edit by hand!
+// elm-duet 0.1.0, schema 36196a46faa181ce, content f88a9fcd1d834866
+
+declare module Elm {
+  type FromWorld =
+    | {
+        tag: "refreshed";
+      }
+    | {
+        tag: string;
+      };
+
+  type NotificationPermission =
+    | "default"
+    | "denied"
+    | "granted"
+    | (string & {});
+
+  namespace Main {
+    type Flags = Record<string, never>;
+
+    type Ports = {
+      fromWorld?: {
+        send: (value: Elm.FromWorld) => void;
+      };
+      gotNewNotificationsPermission?: {
+        send: (value: Elm.NotificationPermission) => void;
+      };
+    };
+
+    function init(config: { flags: Flags; node: HTMLElement }): {
+      ports?: Ports;
+    };
+  }
+}
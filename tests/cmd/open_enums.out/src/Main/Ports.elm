@@ -0,0 +1,154 @@
+port module Main.Ports exposing (..)
+
+{-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema 36196a46faa181ce, content 44eca1baddc8face
+-}
+
+import Dict exposing (Dict)
+import Json.Decode
+import Json.Decode.Pipeline
+import Json.Encode
+
+
+type alias Refreshed =
+    {}
+
+
+refreshedDecoder : Json.Decode.Decoder Refreshed
+refreshedDecoder =
+    Json.Decode.succeed Refreshed
+
+
+encodeRefreshed : Refreshed -> Json.Encode.Value
+encodeRefreshed refreshed_ =
+    Json.Encode.object
+        [ ( "tag", Json.Encode.string "refreshed" )
+        ]
+
+
+type FromWorld
+    = FromWorldRefreshed Refreshed
+    | FromWorldUnknown String Json.Decode.Value
+
+
+fromWorldDecoder : Json.Decode.Decoder FromWorld
+fromWorldDecoder =
+    Json.Decode.andThen
+        (/tag ->
+            case tag of
+                "refreshed" ->
+                    Json.Decode.map FromWorldRefreshed refreshedDecoder
+
+                unknown ->
+                    Json.Decode.map (FromWorldUnknown unknown) Json.Decode.value
+        )
+        (Json.Decode.field "tag" Json.Decode.string)
+
+
+encodeFromWorld : FromWorld -> Json.Encode.Value
+encodeFromWorld fromWorld_ =
+    case fromWorld_ of
+        FromWorldRefreshed fromWorldRefreshed ->
+            encodeRefreshed fromWorldRefreshed
+
+        FromWorldUnknown _ raw ->
+            raw
+
+
+type NotificationPermission
+    = NotificationPermissionDefault
+    | NotificationPermissionDenied
+    | NotificationPermissionGranted
+    | NotificationPermissionUnknown String
+
+
+notificationPermissionDecoder : Json.Decode.Decoder NotificationPermission
+notificationPermissionDecoder =
+    Json.Decode.andThen
+        (/tag ->
+            case tag of
+                "default" ->
+                    Json.Decode.succeed NotificationPermissionDefault
+
+                "denied" ->
+                    Json.Decode.succeed NotificationPermissionDenied
+
+                "granted" ->
+                    Json.Decode.succeed NotificationPermissionGranted
+
+                unknown ->
+                    Json.Decode.succeed (NotificationPermissionUnknown unknown)
+        )
+        Json.Decode.string
+
+
+encodeNotificationPermission : NotificationPermission -> Json.Encode.Value
+encodeNotificationPermission notificationPermission_ =
+    case notificationPermission_ of
+        NotificationPermissionDefault ->
+            Json.Encode.string "default"
+
+        NotificationPermissionDenied ->
+            Json.Encode.string "denied"
+
+        NotificationPermissionGranted ->
+            Json.Encode.string "granted"
+
+        NotificationPermissionUnknown unknown ->
+            Json.Encode.string unknown
+
+
+allNotificationPermission : List NotificationPermission
+allNotificationPermission =
+    [ NotificationPermissionDefault
+    , NotificationPermissionDenied
+    , NotificationPermissionGranted
+    ]
+
+
+notificationPermissionToString : NotificationPermission -> String
+notificationPermissionToString notificationPermission_ =
+    case notificationPermission_ of
+        NotificationPermissionDefault ->
+            "default"
+
+        NotificationPermissionDenied ->
+            "denied"
+
+        NotificationPermissionGranted ->
+            "granted"
+
+        NotificationPermissionUnknown unknown ->
+            unknown
+
+
+notificationPermissionFromString : String -> NotificationPermission
+notificationPermissionFromString string_ =
+    case string_ of
+        "default" ->
+            NotificationPermissionDefault
+
+        "denied" ->
+            NotificationPermissionDenied
+
+        "granted" ->
+            NotificationPermissionGranted
+
+        unknown ->
+            NotificationPermissionUnknown unknown
+
+
+port fromWorld : (Json.Decode.Value -> msg) -> Sub msg
+
+
+subscribeToFromWorld : (Result Json.Decode.Error FromWorld -> msg) -> Sub msg
+subscribeToFromWorld toMsg =
+    fromWorld (Json.Decode.decodeValue fromWorldDecoder >> toMsg)
+
+
+port gotNewNotificationsPermission : (Json.Decode.Value -> msg) -> Sub msg
+
+
+subscribeToGotNewNotificationsPermission : (Result Json.Decode.Error NotificationPermission -> msg) -> Sub msg
+subscribeToGotNewNotificationsPermission toMsg =
+    gotNewNotificationsPermission (Json.Decode.decodeValue notificationPermissionDecoder >> toMsg)
@@ -0,0 +1,55 @@
+// Warning: this file is automatically generated. Don't edit by hand!
+// elm-duet 0.1.0, schema 9bbc96495c7a61e7, content 474026c0a73e872d
+
+declare module Elm {
+  type Jwt = string;
+
+  type NewJwt = string;
+
+  type Notification = {
+    title: string;
+  };
+
+  type NotificationPermission = "default" | "denied" | "granted";
+
+  type Notify = {
+    title: string;
+  };
+
+  type ReportNotificationsElements = {
+    title: string;
+  };
+
+  namespace Main {
+    type Flags = Record<string, never>;
+
+    type Ports = {
+      gotNewNotificationsPermission?: {
+        send: (value: Elm.NotificationPermission) => void;
+      };
+      newJwt?: {
+        subscribe: (callback: (value: Elm.NewJwt) => void) => void;
+      };
+      notify?: {
+        subscribe: (callback: (value: Elm.Notify) => void) => void;
+      };
+      reportNotifications?: {
+        subscribe: (
+          callback: (value: Elm.ReportNotificationsElements[]) => void,
+        ) => void;
+      };
+      reportPermission?: {
+        subscribe: (
+          callback: (value: Elm.NotificationPermission) => void,
+        ) => void;
+      };
+      requestNotificationsPermission?: {
+        subscribe: (callback: (value: Record<string, never>) => void) => void;
+      };
+    };
+
+    function init(config: { flags: Flags; node: HTMLElement }): {
+      ports?: Ports;
+    };
+  }
+}
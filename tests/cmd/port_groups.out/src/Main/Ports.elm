@@ -0,0 +1,108 @@
+port module Main.Ports exposing (..)
+
+{-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema 9bbc96495c7a61e7, content c9fc4f2484006d5c
+-}
+
+import Dict exposing (Dict)
+import Json.Decode
+import Json.Decode.Pipeline
+import Json.Encode
+
+
+type alias NewJwt =
+    String
+
+
+newJwtDecoder : Json.Decode.Decoder NewJwt
+newJwtDecoder =
+    Json.Decode.string
+
+
+encodeNewJwt : NewJwt -> Json.Encode.Value
+encodeNewJwt newJwt_ =
+    Json.Encode.string newJwt_
+
+
+type NotificationPermission
+    = NotificationPermissionDefault
+    | NotificationPermissionDenied
+    | NotificationPermissionGranted
+
+
+notificationPermissionDecoder : Json.Decode.Decoder NotificationPermission
+notificationPermissionDecoder =
+    Json.Decode.andThen
+        (/tag ->
+            case tag of
+                "default" ->
+                    Json.Decode.succeed NotificationPermissionDefault
+
+                "denied" ->
+                    Json.Decode.succeed NotificationPermissionDenied
+
+                "granted" ->
+                    Json.Decode.succeed NotificationPermissionGranted
+
+                unknown ->
+                    Json.Decode.fail ("Unknown value `" ++ unknown ++ "`")
+        )
+        Json.Decode.string
+
+
+encodeNotificationPermission : NotificationPermission -> Json.Encode.Value
+encodeNotificationPermission notificationPermission_ =
+    case notificationPermission_ of
+        NotificationPermissionDefault ->
+            Json.Encode.string "default"
+
+        NotificationPermissionDenied ->
+            Json.Encode.string "denied"
+
+        NotificationPermissionGranted ->
+            Json.Encode.string "granted"
+
+
+allNotificationPermission : List NotificationPermission
+allNotificationPermission =
+    [ NotificationPermissionDefault
+    , NotificationPermissionDenied
+    , NotificationPermissionGranted
+    ]
+
+
+notificationPermissionToString : NotificationPermission -> String
+notificationPermissionToString notificationPermission_ =
+    case notificationPermission_ of
+        NotificationPermissionDefault ->
+            "default"
+
+        NotificationPermissionDenied ->
+            "denied"
+
+        NotificationPermissionGranted ->
+            "granted"
+
+
+notificationPermissionFromString : String -> Maybe NotificationPermission
+notificationPermissionFromString string_ =
+    case string_ of
+        "default" ->
+            Just NotificationPermissionDefault
+
+        "denied" ->
+            Just NotificationPermissionDenied
+
+        "granted" ->
+            Just NotificationPermissionGranted
+
+        _ ->
+            Nothing
+
+
+port newJwt : Json.Decode.Value -> Cmd msg
+
+
+sendNewJwt : NewJwt -> Cmd msg
+sendNewJwt =
+    encodeNewJwt >> newJwt
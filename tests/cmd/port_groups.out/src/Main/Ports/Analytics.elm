@@ -0,0 +1,59 @@
+port module Main.Ports.Analytics exposing (..)
+
+{-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema 9bbc96495c7a61e7, content cd1661cc95dbc03b
+-}
+
+import Dict exposing (Dict)
+import Json.Decode
+import Json.Decode.Pipeline
+import Json.Encode
+import Main.Ports exposing (..)
+
+
+type alias ReportNotificationsElements =
+    { title : String
+    }
+
+
+reportNotificationsElementsDecoder : Json.Decode.Decoder ReportNotificationsElements
+reportNotificationsElementsDecoder =
+    Json.Decode.succeed ReportNotificationsElements
+        |> Json.Decode.Pipeline.required "title" Json.Decode.string
+
+
+encodeReportNotificationsElements : ReportNotificationsElements -> Json.Encode.Value
+encodeReportNotificationsElements reportNotificationsElements_ =
+    Json.Encode.object
+        [ ( "title", Json.Encode.string reportNotificationsElements_.title )
+        ]
+
+
+type alias ReportNotifications =
+    List ReportNotificationsElements
+
+
+reportNotificationsDecoder : Json.Decode.Decoder ReportNotifications
+reportNotificationsDecoder =
+    Json.Decode.list reportNotificationsElementsDecoder
+
+
+encodeReportNotifications : ReportNotifications -> Json.Encode.Value
+encodeReportNotifications reportNotifications_ =
+    Json.Encode.list (/value -> encodeReportNotificationsElements value) reportNotifications_
+
+
+port reportNotifications : Json.Decode.Value -> Cmd msg
+
+
+sendReportNotifications : ReportNotifications -> Cmd msg
+sendReportNotifications =
+    encodeReportNotifications >> reportNotifications
+
+
+port reportPermission : Json.Decode.Value -> Cmd msg
+
+
+sendReportPermission : NotificationPermission -> Cmd msg
+sendReportPermission =
+    encodeNotificationPermission >> reportPermission
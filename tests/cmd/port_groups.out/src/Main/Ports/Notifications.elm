@@ -0,0 +1,67 @@
+port module Main.Ports.Notifications exposing (..)
+
+{-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema 9bbc96495c7a61e7, content 59b6eb307e989d3d
+-}
+
+import Dict exposing (Dict)
+import Json.Decode
+import Json.Decode.Pipeline
+import Json.Encode
+import Main.Ports exposing (..)
+
+
+type alias Notify =
+    { title : String
+    }
+
+
+notifyDecoder : Json.Decode.Decoder Notify
+notifyDecoder =
+    Json.Decode.succeed Notify
+        |> Json.Decode.Pipeline.required "title" Json.Decode.string
+
+
+encodeNotify : Notify -> Json.Encode.Value
+encodeNotify notify_ =
+    Json.Encode.object
+        [ ( "title", Json.Encode.string notify_.title )
+        ]
+
+
+type alias RequestNotificationsPermission =
+    ()
+
+
+requestNotificationsPermissionDecoder : Json.Decode.Decoder RequestNotificationsPermission
+requestNotificationsPermissionDecoder =
+    Json.Decode.null ()
+
+
+encodeRequestNotificationsPermission : RequestNotificationsPermission -> Json.Encode.Value
+encodeRequestNotificationsPermission requestNotificationsPermission_ =
+    Json.Encode.null
+
+
+port gotNewNotificationsPermission : (Json.Decode.Value -> msg) -> Sub msg
+
+
+subscribeToGotNewNotificationsPermission : (Result Json.Decode.Error NotificationPermission -> msg) -> Sub msg
+subscribeToGotNewNotificationsPermission toMsg =
+    gotNewNotificationsPermission (Json.Decode.decodeValue notificationPermissionDecoder >> toMsg)
+
+
+port notify : Json.Decode.Value -> Cmd msg
+
+
+sendNotify : Notify -> Cmd msg
+sendNotify =
+    encodeNotify >> notify
+
+
+port requestNotificationsPermission : Json.Decode.Value -> Cmd msg
+
+
+sendRequestNotificationsPermission : RequestNotificationsPermission -> Cmd msg
+sendRequestNotificationsPermission =
+    encodeRequestNotificationsPermission >> requestNotificationsPermission
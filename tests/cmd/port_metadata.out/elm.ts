@@ -0,0 +1,31 @@
+// Warning: this file is automatically generated. Don't edit by hand!
+// elm-duet 0.1.0, schema c88adc3d69b9e1a7, content 0cc0317b949d7c3c
+
+declare module Elm {
+  type NotificationPermission =
+    | "default"
+    | "denied"
+    | "granted"
+    | (string & {});
+
+  type Session = {
+    token: string;
+  };
+
+  namespace Main {
+    type Flags = Record<string, never>;
+
+    type Ports = {
+      gotNewNotificationsPermission?: {
+        send: (value: Elm.NotificationPermission) => void;
+      };
+      sessionChanged?: {
+        send: (value: Elm.Session) => void;
+      };
+    };
+
+    function init(config: { flags: Flags; node: HTMLElement }): {
+      ports?: Ports;
+    };
+  }
+}
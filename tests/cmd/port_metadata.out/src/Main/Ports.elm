@@ -0,0 +1,146 @@
+port module Main.Ports exposing (NotificationPermission(..), allNotificationPermission, notificationPermissionToString, notificationPermissionFromString, notificationPermissionDecoder, encodeNotificationPermission, Session, makeSession, sessionToken, sessionDecoder, encodeSession, gotNewNotificationsPermission, subscribeToGotNewNotificationsPermission, sessionChanged, subscribeToSessionChanged)
+
+{-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema c88adc3d69b9e1a7, content 8a1f49c13beb75c0
+-}
+
+import Dict exposing (Dict)
+import Json.Decode
+import Json.Decode.Pipeline
+import Json.Encode
+
+
+type NotificationPermission
+    = NotificationPermissionDefault
+    | NotificationPermissionDenied
+    | NotificationPermissionGranted
+    | NotificationPermissionUnknown String
+
+
+notificationPermissionDecoder : Json.Decode.Decoder NotificationPermission
+notificationPermissionDecoder =
+    Json.Decode.andThen
+        (/tag ->
+            case tag of
+                "default" ->
+                    Json.Decode.succeed NotificationPermissionDefault
+
+                "denied" ->
+                    Json.Decode.succeed NotificationPermissionDenied
+
+                "granted" ->
+                    Json.Decode.succeed NotificationPermissionGranted
+
+                unknown ->
+                    Json.Decode.succeed (NotificationPermissionUnknown unknown)
+        )
+        Json.Decode.string
+
+
+encodeNotificationPermission : NotificationPermission -> Json.Encode.Value
+encodeNotificationPermission notificationPermission_ =
+    case notificationPermission_ of
+        NotificationPermissionDefault ->
+            Json.Encode.string "default"
+
+        NotificationPermissionDenied ->
+            Json.Encode.string "denied"
+
+        NotificationPermissionGranted ->
+            Json.Encode.string "granted"
+
+        NotificationPermissionUnknown unknown ->
+            Json.Encode.string unknown
+
+
+allNotificationPermission : List NotificationPermission
+allNotificationPermission =
+    [ NotificationPermissionDefault
+    , NotificationPermissionDenied
+    , NotificationPermissionGranted
+    ]
+
+
+notificationPermissionToString : NotificationPermission -> String
+notificationPermissionToString notificationPermission_ =
+    case notificationPermission_ of
+        NotificationPermissionDefault ->
+            "default"
+
+        NotificationPermissionDenied ->
+            "denied"
+
+        NotificationPermissionGranted ->
+            "granted"
+
+        NotificationPermissionUnknown unknown ->
+            unknown
+
+
+notificationPermissionFromString : String -> NotificationPermission
+notificationPermissionFromString string_ =
+    case string_ of
+        "default" ->
+            NotificationPermissionDefault
+
+        "denied" ->
+            NotificationPermissionDenied
+
+        "granted" ->
+            NotificationPermissionGranted
+
+        unknown ->
+            NotificationPermissionUnknown unknown
+
+
+type Session
+    = Session
+        { token : String
+        }
+
+
+sessionDecoder : Json.Decode.Decoder Session
+sessionDecoder =
+    Json.Decode.succeed
+        (/token_ ->
+            Session
+                { token = token_
+                }
+        )
+        |> Json.Decode.Pipeline.required "token" Json.Decode.string
+
+
+encodeSession : Session -> Json.Encode.Value
+encodeSession (Session session_) =
+    Json.Encode.object
+        [ ( "token", Json.Encode.string session_.token )
+        ]
+
+
+makeSession :
+    { token : String
+    }
+    -> Session
+makeSession =
+    Session
+
+
+sessionToken : Session -> String
+sessionToken (Session session_) =
+    session_.token
+
+
+port gotNewNotificationsPermission : (Json.Decode.Value -> msg) -> Sub msg
+
+
+subscribeToGotNewNotificationsPermission : (Result Json.Decode.Error NotificationPermission -> msg) -> Sub msg
+subscribeToGotNewNotificationsPermission toMsg =
+    gotNewNotificationsPermission (Json.Decode.decodeValue notificationPermissionDecoder >> toMsg)
+
+
+port sessionChanged : (Json.Decode.Value -> msg) -> Sub msg
+
+
+subscribeToSessionChanged : (Result Json.Decode.Error Session -> msg) -> Sub msg
+subscribeToSessionChanged toMsg =
+    sessionChanged (Json.Decode.decodeValue sessionDecoder >> toMsg)
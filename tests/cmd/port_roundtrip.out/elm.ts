@@ -1,15 +1,28 @@
 // Warning: this file is automatically generated. Don't edit by hand!
+// elm-duet 0.1.0, schema 2a7ba25a16740f6a, content c7365cfc09fc1cdf
 
 declare module Elm {
+  type ElmToJs = {
+    a: string;
+  };
+
+  type JsToElm = {
+    a: string;
+  };
+
+  type RoundTripper = {
+    a: string;
+  };
+
   namespace Main {
     type Flags = Record<string, never>;
 
     type Ports = {
       elmToJs?: {
-        subscribe: (callback: (value: { a: string }) => void) => void;
+        subscribe: (callback: (value: Elm.ElmToJs) => void) => void;
       };
       jsToElm?: {
-        send: (value: { a: string }) => void;
+        send: (value: Elm.JsToElm) => void;
       };
     };
 
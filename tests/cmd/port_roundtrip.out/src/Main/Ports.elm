@@ -1,6 +1,7 @@
 port module Main.Ports exposing (..)
 
 {-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema 2a7ba25a16740f6a, content 885e7cca52394d6f
 -}
 
 import Dict exposing (Dict)
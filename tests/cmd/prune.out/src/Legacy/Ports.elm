@@ -0,0 +1,4 @@
+port module Legacy.Ports exposing (..)
+
+{-| We took this over by hand.
+-}
@@ -0,0 +1,32 @@
+// Warning: this file is automatically generated. Don't edit by hand!
+// elm-duet 0.1.0, schema b004f4ff275f3bb6, content 8e9f05caf65ad3e1
+
+declare module Elm {
+  type FromWorld =
+    | {
+        permission: Elm.NotificationPermission;
+        tag: "permissionChanged";
+      }
+    | {
+        tag: string;
+      };
+
+  type NotificationPermission = "default" | "denied" | "granted";
+
+  namespace Main {
+    type Flags = {
+      permission: Elm.NotificationPermission;
+      retries: number | null;
+    };
+
+    type Ports = {
+      worldEvents?: {
+        send: (value: { message: Elm.FromWorld }) => void;
+      };
+    };
+
+    function init(config: { flags: Flags; node: HTMLElement }): {
+      ports?: Ports;
+    };
+  }
+}
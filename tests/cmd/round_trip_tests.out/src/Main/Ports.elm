@@ -0,0 +1,160 @@
+port module Main.Ports exposing (..)
+
+{-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema b004f4ff275f3bb6, content 7a7acc0e80b032f6
+-}
+
+import Dict exposing (Dict)
+import Json.Decode
+import Json.Decode.Pipeline
+import Json.Encode
+
+
+type NotificationPermission
+    = NotificationPermissionDefault
+    | NotificationPermissionDenied
+    | NotificationPermissionGranted
+
+
+notificationPermissionDecoder : Json.Decode.Decoder NotificationPermission
+notificationPermissionDecoder =
+    Json.Decode.andThen
+        (/tag ->
+            case tag of
+                "default" ->
+                    Json.Decode.succeed NotificationPermissionDefault
+
+                "denied" ->
+                    Json.Decode.succeed NotificationPermissionDenied
+
+                "granted" ->
+                    Json.Decode.succeed NotificationPermissionGranted
+
+                unknown ->
+                    Json.Decode.fail ("Unknown value `" ++ unknown ++ "`")
+        )
+        Json.Decode.string
+
+
+encodeNotificationPermission : NotificationPermission -> Json.Encode.Value
+encodeNotificationPermission notificationPermission_ =
+    case notificationPermission_ of
+        NotificationPermissionDefault ->
+            Json.Encode.string "default"
+
+        NotificationPermissionDenied ->
+            Json.Encode.string "denied"
+
+        NotificationPermissionGranted ->
+            Json.Encode.string "granted"
+
+
+allNotificationPermission : List NotificationPermission
+allNotificationPermission =
+    [ NotificationPermissionDefault
+    , NotificationPermissionDenied
+    , NotificationPermissionGranted
+    ]
+
+
+notificationPermissionToString : NotificationPermission -> String
+notificationPermissionToString notificationPermission_ =
+    case notificationPermission_ of
+        NotificationPermissionDefault ->
+            "default"
+
+        NotificationPermissionDenied ->
+            "denied"
+
+        NotificationPermissionGranted ->
+            "granted"
+
+
+notificationPermissionFromString : String -> Maybe NotificationPermission
+notificationPermissionFromString string_ =
+    case string_ of
+        "default" ->
+            Just NotificationPermissionDefault
+
+        "denied" ->
+            Just NotificationPermissionDenied
+
+        "granted" ->
+            Just NotificationPermissionGranted
+
+        _ ->
+            Nothing
+
+
+type alias PermissionChanged =
+    { permission : NotificationPermission
+    }
+
+
+permissionChangedDecoder : Json.Decode.Decoder PermissionChanged
+permissionChangedDecoder =
+    Json.Decode.succeed PermissionChanged
+        |> Json.Decode.Pipeline.required "permission" notificationPermissionDecoder
+
+
+encodePermissionChanged : PermissionChanged -> Json.Encode.Value
+encodePermissionChanged permissionChanged_ =
+    Json.Encode.object
+        [ ( "permission", encodeNotificationPermission permissionChanged_.permission )
+        , ( "tag", Json.Encode.string "permissionChanged" )
+        ]
+
+
+type FromWorld
+    = FromWorldPermissionChanged PermissionChanged
+    | FromWorldUnknown String Json.Decode.Value
+
+
+fromWorldDecoder : Json.Decode.Decoder FromWorld
+fromWorldDecoder =
+    Json.Decode.andThen
+        (/tag ->
+            case tag of
+                "permissionChanged" ->
+                    Json.Decode.map FromWorldPermissionChanged permissionChangedDecoder
+
+                unknown ->
+                    Json.Decode.map (FromWorldUnknown unknown) Json.Decode.value
+        )
+        (Json.Decode.field "tag" Json.Decode.string)
+
+
+encodeFromWorld : FromWorld -> Json.Encode.Value
+encodeFromWorld fromWorld_ =
+    case fromWorld_ of
+        FromWorldPermissionChanged fromWorldPermissionChanged ->
+            encodePermissionChanged fromWorldPermissionChanged
+
+        FromWorldUnknown _ raw ->
+            raw
+
+
+type alias WorldEvents =
+    { message : FromWorld
+    }
+
+
+worldEventsDecoder : Json.Decode.Decoder WorldEvents
+worldEventsDecoder =
+    Json.Decode.succeed WorldEvents
+        |> Json.Decode.Pipeline.required "message" fromWorldDecoder
+
+
+encodeWorldEvents : WorldEvents -> Json.Encode.Value
+encodeWorldEvents worldEvents_ =
+    Json.Encode.object
+        [ ( "message", encodeFromWorld worldEvents_.message )
+        ]
+
+
+port worldEvents : (Json.Decode.Value -> msg) -> Sub msg
+
+
+subscribeToWorldEvents : (Result Json.Decode.Error WorldEvents -> msg) -> Sub msg
+subscribeToWorldEvents toMsg =
+    worldEvents (Json.Decode.decodeValue worldEventsDecoder >> toMsg)
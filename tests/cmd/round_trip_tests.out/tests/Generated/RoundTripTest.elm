@@ -0,0 +1,66 @@
+module Generated.RoundTripTest exposing (suite)
+
+{-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema b004f4ff275f3bb6, content d73c5022abae3539
+-}
+
+import Expect
+import Json.Decode
+import Json.Encode
+import Main.Flags
+import Main.Flags.Fuzzers
+import Main.Ports
+import Main.Ports.Fuzzers
+import Test exposing (Test, describe, fuzz)
+
+
+suite : Test
+suite =
+    describe "round trips"
+        [ describe "Main.Flags"
+            [ fuzz Main.Flags.Fuzzers.notificationPermissionFuzzer "NotificationPermission" <|
+                /value ->
+                    Main.Flags.encodeNotificationPermission value
+                        |> Json.Decode.decodeValue Main.Flags.notificationPermissionDecoder
+                        |> Expect.equal (Ok value)
+            , fuzz Main.Flags.Fuzzers.notificationPermissionFuzzer "NotificationPermission strings" <|
+                /value ->
+                    Main.Flags.notificationPermissionToString value
+                        |> Main.Flags.notificationPermissionFromString
+                        |> Expect.equal (Just value)
+            , fuzz Main.Flags.Fuzzers.flagsFuzzer "Flags" <|
+                /value ->
+                    Main.Flags.encodeFlags value
+                        |> Json.Decode.decodeValue Main.Flags.flagsDecoder
+                        |> Expect.equal (Ok value)
+            ]
+        , describe "Main.Ports"
+            [ fuzz Main.Ports.Fuzzers.notificationPermissionFuzzer "NotificationPermission" <|
+                /value ->
+                    Main.Ports.encodeNotificationPermission value
+                        |> Json.Decode.decodeValue Main.Ports.notificationPermissionDecoder
+                        |> Expect.equal (Ok value)
+            , fuzz Main.Ports.Fuzzers.notificationPermissionFuzzer "NotificationPermission strings" <|
+                /value ->
+                    Main.Ports.notificationPermissionToString value
+                        |> Main.Ports.notificationPermissionFromString
+                        |> Expect.equal (Just value)
+            , fuzz Main.Ports.Fuzzers.permissionChangedFuzzer "PermissionChanged" <|
+                /value ->
+                    Main.Ports.encodePermissionChanged value
+                        |> Json.Decode.decodeValue Main.Ports.permissionChangedDecoder
+                        |> Expect.equal (Ok value)
+            , fuzz Main.Ports.Fuzzers.fromWorldFuzzer "FromWorld" <|
+                /value ->
+                    Main.Ports.encodeFromWorld value
+                        |> Json.Decode.decodeValue Main.Ports.fromWorldDecoder
+                        |> Result.map (Main.Ports.encodeFromWorld >> Json.Encode.encode 0)
+                        |> Expect.equal (Ok (Json.Encode.encode 0 (Main.Ports.encodeFromWorld value)))
+            , fuzz Main.Ports.Fuzzers.worldEventsFuzzer "WorldEvents" <|
+                /value ->
+                    Main.Ports.encodeWorldEvents value
+                        |> Json.Decode.decodeValue Main.Ports.worldEventsDecoder
+                        |> Result.map (Main.Ports.encodeWorldEvents >> Json.Encode.encode 0)
+                        |> Expect.equal (Ok (Json.Encode.encode 0 (Main.Ports.encodeWorldEvents value)))
+            ]
+        ]
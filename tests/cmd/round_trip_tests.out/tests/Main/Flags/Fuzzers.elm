@@ -0,0 +1,31 @@
+module Main.Flags.Fuzzers exposing (..)
+
+{-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema b004f4ff275f3bb6, content 7c14d1fb7d71c307
+-}
+
+import Dict
+import Fuzz exposing (Fuzzer)
+import Json.Encode
+import Main.Flags exposing (..)
+
+
+notificationPermissionFuzzer : Fuzzer NotificationPermission
+notificationPermissionFuzzer =
+    Fuzz.oneOfValues
+        [ NotificationPermissionDefault
+        , NotificationPermissionDenied
+        , NotificationPermissionGranted
+        ]
+
+
+flagsFuzzer : Fuzzer Flags
+flagsFuzzer =
+    Fuzz.constant
+        (/permission_ retries_ ->
+            { permission = permission_
+            , retries = retries_
+            }
+        )
+        |> Fuzz.andMap notificationPermissionFuzzer
+        |> Fuzz.andMap (Fuzz.maybe (Fuzz.intRange (-128) 127))
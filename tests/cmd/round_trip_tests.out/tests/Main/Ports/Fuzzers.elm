@@ -0,0 +1,47 @@
+module Main.Ports.Fuzzers exposing (..)
+
+{-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema b004f4ff275f3bb6, content 572129e850053caa
+-}
+
+import Dict
+import Fuzz exposing (Fuzzer)
+import Json.Encode
+import Main.Ports exposing (..)
+
+
+notificationPermissionFuzzer : Fuzzer NotificationPermission
+notificationPermissionFuzzer =
+    Fuzz.oneOfValues
+        [ NotificationPermissionDefault
+        , NotificationPermissionDenied
+        , NotificationPermissionGranted
+        ]
+
+
+permissionChangedFuzzer : Fuzzer PermissionChanged
+permissionChangedFuzzer =
+    Fuzz.constant
+        (/permission_ ->
+            { permission = permission_
+            }
+        )
+        |> Fuzz.andMap notificationPermissionFuzzer
+
+
+fromWorldFuzzer : Fuzzer FromWorld
+fromWorldFuzzer =
+    Fuzz.oneOf
+        [ Fuzz.map FromWorldPermissionChanged permissionChangedFuzzer
+        , Fuzz.constant (FromWorldUnknown "unknown" (Json.Encode.object [ ( "tag", Json.Encode.string "unknown" ) ]))
+        ]
+
+
+worldEventsFuzzer : Fuzzer WorldEvents
+worldEventsFuzzer =
+    Fuzz.constant
+        (/message_ ->
+            { message = message_
+            }
+        )
+        |> Fuzz.andMap fromWorldFuzzer
@@ -0,0 +1,50 @@
+port module Admin.Users.Ports exposing (..)
+
+{-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema 5b90b541fdb60765, content ac71b08099a5a2d7
+-}
+
+import Dict exposing (Dict)
+import Json.Decode
+import Json.Decode.Pipeline
+import Json.Encode
+
+
+type alias GotUsersElements =
+    { name : String
+    }
+
+
+gotUsersElementsDecoder : Json.Decode.Decoder GotUsersElements
+gotUsersElementsDecoder =
+    Json.Decode.succeed GotUsersElements
+        |> Json.Decode.Pipeline.required "name" Json.Decode.string
+
+
+encodeGotUsersElements : GotUsersElements -> Json.Encode.Value
+encodeGotUsersElements gotUsersElements_ =
+    Json.Encode.object
+        [ ( "name", Json.Encode.string gotUsersElements_.name )
+        ]
+
+
+type alias GotUsers =
+    List GotUsersElements
+
+
+gotUsersDecoder : Json.Decode.Decoder GotUsers
+gotUsersDecoder =
+    Json.Decode.list gotUsersElementsDecoder
+
+
+encodeGotUsers : GotUsers -> Json.Encode.Value
+encodeGotUsers gotUsers_ =
+    Json.Encode.list (/value -> encodeGotUsersElements value) gotUsers_
+
+
+port gotUsers : (Json.Decode.Value -> msg) -> Sub msg
+
+
+subscribeToGotUsers : (Result Json.Decode.Error GotUsers -> msg) -> Sub msg
+subscribeToGotUsers toMsg =
+    gotUsers (Json.Decode.decodeValue gotUsersDecoder >> toMsg)
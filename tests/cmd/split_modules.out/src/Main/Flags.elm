@@ -0,0 +1,46 @@
+module Main.Flags exposing (..)
+
+{-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema 5b90b541fdb60765, content 0649599bdfda5925
+-}
+
+import Dict exposing (Dict)
+import Json.Decode
+import Json.Decode.Pipeline
+import Json.Encode
+
+
+type alias CurrentUser =
+    { name : String
+    }
+
+
+currentUserDecoder : Json.Decode.Decoder CurrentUser
+currentUserDecoder =
+    Json.Decode.succeed CurrentUser
+        |> Json.Decode.Pipeline.required "name" Json.Decode.string
+
+
+encodeCurrentUser : CurrentUser -> Json.Encode.Value
+encodeCurrentUser currentUser_ =
+    Json.Encode.object
+        [ ( "name", Json.Encode.string currentUser_.name )
+        ]
+
+
+type alias Flags =
+    { currentUser : CurrentUser
+    }
+
+
+flagsDecoder : Json.Decode.Decoder Flags
+flagsDecoder =
+    Json.Decode.succeed Flags
+        |> Json.Decode.Pipeline.required "currentUser" currentUserDecoder
+
+
+encodeFlags : Flags -> Json.Encode.Value
+encodeFlags flags_ =
+    Json.Encode.object
+        [ ( "currentUser", encodeCurrentUser flags_.currentUser )
+        ]
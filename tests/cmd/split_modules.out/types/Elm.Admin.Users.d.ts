@@ -0,0 +1,20 @@
+// Warning: this file is automatically generated. Don't edit by hand!
+// elm-duet 0.1.0, schema 5b90b541fdb60765, content 976441246ca033c7
+
+declare module Elm {
+  namespace Admin {
+    namespace Users {
+      type Flags = Record<string, never>;
+
+      type Ports = {
+        gotUsers?: {
+          send: (value: Elm.GotUsersElements[]) => void;
+        };
+      };
+
+      function init(config: { flags: Flags; node: HTMLElement }): {
+        ports?: Ports;
+      };
+    }
+  }
+}
@@ -0,0 +1,16 @@
+// Warning: this file is automatically generated. Don't edit by hand!
+// elm-duet 0.1.0, schema 5b90b541fdb60765, content fd4f64d86ba396a4
+
+declare module Elm {
+  namespace Main {
+    type Flags = {
+      currentUser: Elm.CurrentUser;
+    };
+
+    type Ports = Record<string, never>;
+
+    function init(config: { flags: Flags; node: HTMLElement }): {
+      ports?: Ports;
+    };
+  }
+}
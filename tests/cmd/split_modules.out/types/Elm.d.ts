@@ -0,0 +1,16 @@
+// Warning: this file is automatically generated. Don't edit by hand!
+// elm-duet 0.1.0, schema 5b90b541fdb60765, content a9d763147672efa3
+
+declare module Elm {
+  type CurrentUser = {
+    name: string;
+  };
+
+  type GotUsersElements = {
+    name: string;
+  };
+
+  type User = {
+    name: string;
+  };
+}
@@ -0,0 +1,6 @@
+// Warning: this file is automatically generated. Don't edit by hand!
+// elm-duet 0.1.0, schema 5b90b541fdb60765, content f77afa07cfdba5d3
+
+/// <reference path="./Elm.Admin.Users.d.ts" />
+/// <reference path="./Elm.Main.d.ts" />
+/// <reference path="./Elm.d.ts" />
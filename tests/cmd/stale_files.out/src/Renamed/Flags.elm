@@ -0,0 +1,4 @@
+module Renamed.Flags exposing (..)
+
+{-| Warning: this file is automatically generated. Don't edit by hand!
+-}
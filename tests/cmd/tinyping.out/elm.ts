@@ -1,10 +1,57 @@
 // Warning: this file is automatically generated. Don't edit by hand!
+// elm-duet 0.1.0, schema 5e3fdb2893675906, content 62eb181fc0730d88
 
 declare module Elm {
+  type Doc = {
+    pings: Elm.PingsElements[];
+    settings: Elm.Settings;
+    version: "v1";
+  };
+
+  type DocFromAutomerge = {
+    pings: Elm.PingsElements[];
+    settings: Elm.Settings;
+    version: "v1";
+  };
+
+  type Notification = {
+    options: {
+      badge?: string;
+      body?: string;
+      icon?: string;
+      lang?: string;
+      requireInteraction?: boolean;
+      silent?: boolean;
+      tag?: string;
+    };
+    title: string;
+  };
+
+  type NotificationPermission = "default" | "denied" | "granted";
+
+  type Ping = {
+    custom: Record<string, string>;
+    tag: string | null;
+    time: number;
+    version: "v1";
+  };
+
+  type PingsElements = {
+    custom: Record<string, string>;
+    tag: string | null;
+    time: number;
+    version: "v1";
+  };
+
+  type Settings = {
+    minutesPerPing: number;
+    version: "v1";
+  };
+
   namespace Main {
     type Flags = {
       currentTimeMillis: number;
-      notificationPermission: "default" | "denied" | "granted";
+      notificationPermission: Elm.NotificationPermission;
     };
 
     type Ports = {
@@ -30,38 +77,13 @@ declare module Elm {
         ) => void;
       };
       docFromAutomerge?: {
-        send: (value: {
-          pings: {
-            custom: Record<string, string>;
-            tag: string | null;
-            time: number;
-            version: "v1";
-          }[];
-          settings: {
-            minutesPerPing: number;
-            version: "v1";
-          };
-          version: "v1";
-        }) => void;
+        send: (value: Elm.DocFromAutomerge) => void;
       };
       gotNewNotificationsPermission?: {
-        send: (value: "default" | "denied" | "granted") => void;
+        send: (value: Elm.NotificationPermission) => void;
       };
       notify?: {
-        subscribe: (
-          callback: (value: {
-            options: {
-              badge?: string;
-              body?: string;
-              icon?: string;
-              lang?: string;
-              requireInteraction?: boolean;
-              silent?: boolean;
-              tag?: string;
-            };
-            title: string;
-          }) => void,
-        ) => void;
+        subscribe: (callback: (value: Elm.Notification) => void) => void;
       };
       requestNotificationsPermission?: {
         subscribe: (callback: (value: Record<string, never>) => void) => void;
@@ -1,6 +1,7 @@
 module Main.Flags exposing (..)
 
 {-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema 5e3fdb2893675906, content 4fe1d6de11b7ef2d
 -}
 
 import Dict exposing (Dict)
@@ -48,6 +49,43 @@ encodeNotificationPermission notificationPermission_ =
             Json.Encode.string "granted"
 
 
+allNotificationPermission : List NotificationPermission
+allNotificationPermission =
+    [ NotificationPermissionDefault
+    , NotificationPermissionDenied
+    , NotificationPermissionGranted
+    ]
+
+
+notificationPermissionToString : NotificationPermission -> String
+notificationPermissionToString notificationPermission_ =
+    case notificationPermission_ of
+        NotificationPermissionDefault ->
+            "default"
+
+        NotificationPermissionDenied ->
+            "denied"
+
+        NotificationPermissionGranted ->
+            "granted"
+
+
+notificationPermissionFromString : String -> Maybe NotificationPermission
+notificationPermissionFromString string_ =
+    case string_ of
+        "default" ->
+            Just NotificationPermissionDefault
+
+        "denied" ->
+            Just NotificationPermissionDenied
+
+        "granted" ->
+            Just NotificationPermissionGranted
+
+        _ ->
+            Nothing
+
+
 type alias Flags =
     { currentTimeMillis : Int
     , notificationPermission : NotificationPermission
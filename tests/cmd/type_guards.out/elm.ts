@@ -0,0 +1,51 @@
+// Warning: this file is automatically generated. Don't edit by hand!
+// elm-duet 0.1.0, schema 5d5c3ebb98ac2cd7, content 5f2ca4feaf751971
+
+declare module Elm {
+  type Notification = {
+    counts?: Record<string, number> | null;
+    priority: number;
+    sentAt: string;
+    tags?: string[];
+    title: string;
+  };
+
+  type Notify = {
+    counts?: Record<string, number> | null;
+    priority: number;
+    sentAt: string;
+    tags?: string[];
+    title: string;
+  };
+
+  namespace Main {
+    type Flags = {
+      retries: number | null;
+    };
+
+    type Ports = {
+      fromWorld?: {
+        send: (
+          value:
+            | {
+                notification: Elm.Notification;
+                tag: "notify";
+              }
+            | {
+                tag: "refresh";
+              },
+        ) => void;
+      };
+      log?: {
+        subscribe: (callback: (value: string) => void) => void;
+      };
+      notify?: {
+        send: (value: Elm.Notify) => void;
+      };
+    };
+
+    function init(config: { flags: Flags; node: HTMLElement }): {
+      ports?: Ports;
+    };
+  }
+}
@@ -0,0 +1,298 @@
+// Warning: this file is automatically generated. Don't edit by hand!
+// elm-duet 0.1.0, schema 5d5c3ebb98ac2cd7, content 0be32203bac19d28
+
+export function isFlags(value: unknown): value is Elm.Main.Flags {
+  return checkFlags(value, "$") === null;
+}
+
+export function assertFlags(value: unknown): asserts value is Elm.Main.Flags {
+  const problem = checkFlags(value, "$");
+  if (problem !== null) {
+    throw new TypeError(problem);
+  }
+}
+
+export function isFromWorld(
+  value: unknown,
+): value is Parameters<NonNullable<Elm.Main.Ports["fromWorld"]>["send"]>[0] {
+  return checkFromWorld(value, "$") === null;
+}
+
+export function assertFromWorld(
+  value: unknown,
+): asserts value is Parameters<
+  NonNullable<Elm.Main.Ports["fromWorld"]>["send"]
+>[0] {
+  const problem = checkFromWorld(value, "$");
+  if (problem !== null) {
+    throw new TypeError(problem);
+  }
+}
+
+export function isLog(
+  value: unknown,
+): value is Parameters<
+  Parameters<NonNullable<Elm.Main.Ports["log"]>["subscribe"]>[0]
+>[0] {
+  return checkLog(value, "$") === null;
+}
+
+export function assertLog(
+  value: unknown,
+): asserts value is Parameters<
+  Parameters<NonNullable<Elm.Main.Ports["log"]>["subscribe"]>[0]
+>[0] {
+  const problem = checkLog(value, "$");
+  if (problem !== null) {
+    throw new TypeError(problem);
+  }
+}
+
+export function isNotify(
+  value: unknown,
+): value is Parameters<NonNullable<Elm.Main.Ports["notify"]>["send"]>[0] {
+  return checkNotify(value, "$") === null;
+}
+
+export function assertNotify(
+  value: unknown,
+): asserts value is Parameters<
+  NonNullable<Elm.Main.Ports["notify"]>["send"]
+>[0] {
+  const problem = checkNotify(value, "$");
+  if (problem !== null) {
+    throw new TypeError(problem);
+  }
+}
+
+export function matchFromWorld<R>(
+  value:
+    | {
+        notification: Elm.Notification;
+        tag: "notify";
+      }
+    | {
+        tag: "refresh";
+      },
+  handlers: {
+    notify: (payload: Extract<typeof value, { tag: "notify" }>) => R;
+    refresh: (payload: Extract<typeof value, { tag: "refresh" }>) => R;
+  },
+): R {
+  switch (value.tag) {
+    case "notify":
+      return handlers.notify(value);
+    case "refresh":
+      return handlers.refresh(value);
+  }
+
+  throw new TypeError(
+    `unexpected tag: ${JSON.stringify((value as { tag: unknown }).tag)}`,
+  );
+}
+
+/**
+ * Subscribe to every port Elm sends values through, and get functions for sending values to
+ * Elm. Elm removes ports that are never used, so we warn about missing ports (or throw, or
+ * ignore them, depending on `options.missingPorts`.)
+ */
+export function connectMain(
+  app: { ports?: Elm.Main.Ports },
+  handlers: {
+    log: (
+      value: Parameters<
+        Parameters<NonNullable<Elm.Main.Ports["log"]>["subscribe"]>[0]
+      >[0],
+    ) => void;
+  },
+  options: { missingPorts?: "throw" | "warn" | "ignore" } = {},
+): {
+  fromWorld: (
+    value: Parameters<NonNullable<Elm.Main.Ports["fromWorld"]>["send"]>[0],
+  ) => void;
+  notify: (
+    value: Parameters<NonNullable<Elm.Main.Ports["notify"]>["send"]>[0],
+  ) => void;
+} {
+  const ports: Elm.Main.Ports = app.ports ?? {};
+  const missing = (port: string) => {
+    const message = `the Main Elm module does not have a "${port}" port. Is it used in Elm?`;
+    if (options.missingPorts === "throw") {
+      throw new Error(message);
+    } else if (options.missingPorts !== "ignore") {
+      console.warn(message);
+    }
+  };
+
+  if (!ports.fromWorld) {
+    missing("fromWorld");
+  }
+
+  if (ports.log) {
+    ports.log.subscribe(handlers.log);
+  } else {
+    missing("log");
+  }
+
+  if (!ports.notify) {
+    missing("notify");
+  }
+
+  return {
+    fromWorld: (value) => {
+      ports.fromWorld?.send(value);
+    },
+    notify: (value) => {
+      ports.notify?.send(value);
+    },
+  };
+}
+
+function checkFlags(value: unknown, path: string): string | null {
+  if (typeof value !== "object" || value === null || Array.isArray(value)) {
+    return `${path}: expected an object`;
+  }
+  const object0 = value as Record<string, unknown>;
+  if (!("retries" in object0)) {
+    return `${path}: missing "retries"`;
+  }
+  {
+    const value1 = object0["retries"];
+    if (value1 !== null) {
+      if (
+        typeof value1 !== "number" ||
+        !Number.isInteger(value1) ||
+        value1 < -128 ||
+        value1 > 127
+      ) {
+        return `${path}.retries: expected an integer between -128 and 127`;
+      }
+    }
+  }
+  return null;
+}
+
+function checkFromWorld(value: unknown, path: string): string | null {
+  if (typeof value !== "object" || value === null || Array.isArray(value)) {
+    return `${path}: expected an object`;
+  }
+  const object0 = value as Record<string, unknown>;
+  switch (object0["tag"]) {
+    case "notify": {
+      if (typeof value !== "object" || value === null || Array.isArray(value)) {
+        return `${path}: expected an object`;
+      }
+      const object1 = value as Record<string, unknown>;
+      if (!("notification" in object1)) {
+        return `${path}: missing "notification"`;
+      }
+      {
+        const value2 = object1["notification"];
+        {
+          const problem = checkNotificationRef(value2, `${path}.notification`);
+          if (problem !== null) {
+            return problem;
+          }
+        }
+      }
+      break;
+    }
+    case "refresh": {
+      if (typeof value !== "object" || value === null || Array.isArray(value)) {
+        return `${path}: expected an object`;
+      }
+      break;
+    }
+    default:
+      return `${path}.tag: expected one of "notify", "refresh"`;
+  }
+  return null;
+}
+
+function checkLog(value: unknown, path: string): string | null {
+  if (typeof value !== "string") {
+    return `${path}: expected a string`;
+  }
+  return null;
+}
+
+function checkNotificationRef(value: unknown, path: string): string | null {
+  if (typeof value !== "object" || value === null || Array.isArray(value)) {
+    return `${path}: expected an object`;
+  }
+  const object0 = value as Record<string, unknown>;
+  if (!("priority" in object0)) {
+    return `${path}: missing "priority"`;
+  }
+  {
+    const value1 = object0["priority"];
+    if (
+      typeof value1 !== "number" ||
+      !Number.isInteger(value1) ||
+      value1 < 0 ||
+      value1 > 255
+    ) {
+      return `${path}.priority: expected an integer between 0 and 255`;
+    }
+  }
+  if (!("sentAt" in object0)) {
+    return `${path}: missing "sentAt"`;
+  }
+  {
+    const value1 = object0["sentAt"];
+    if (typeof value1 !== "string" || Number.isNaN(Date.parse(value1))) {
+      return `${path}.sentAt: expected a timestamp`;
+    }
+  }
+  if (!("title" in object0)) {
+    return `${path}: missing "title"`;
+  }
+  {
+    const value1 = object0["title"];
+    if (typeof value1 !== "string") {
+      return `${path}.title: expected a string`;
+    }
+  }
+  if (object0["counts"] !== undefined) {
+    const value1 = object0["counts"];
+    if (value1 !== null) {
+      if (
+        typeof value1 !== "object" ||
+        value1 === null ||
+        Array.isArray(value1)
+      ) {
+        return `${path}.counts: expected an object`;
+      }
+      for (const [key1, value2] of Object.entries(
+        value1 as Record<string, unknown>,
+      )) {
+        if (typeof value2 !== "number") {
+          return `${path}.counts.${key1}: expected a number`;
+        }
+      }
+    }
+  }
+  if (object0["tags"] !== undefined) {
+    const value1 = object0["tags"];
+    if (!Array.isArray(value1)) {
+      return `${path}.tags: expected an array`;
+    }
+    for (let i1 = 0; i1 < value1.length; i1++) {
+      const value2: unknown = value1[i1];
+      if (typeof value2 !== "string") {
+        return `${path}.tags[${i1}]: expected a string`;
+      }
+    }
+  }
+  return null;
+}
+
+function checkNotify(value: unknown, path: string): string | null {
+  {
+    const problem = checkNotificationRef(value, `${path}`);
+    if (problem !== null) {
+      return problem;
+    }
+  }
+  return null;
+}
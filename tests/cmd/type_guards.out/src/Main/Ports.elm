@@ -0,0 +1,199 @@
+port module Main.Ports exposing (..)
+
+{-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema 5d5c3ebb98ac2cd7, content b020757f5e2ec1a8
+-}
+
+import Dict exposing (Dict)
+import Json.Decode
+import Json.Decode.Pipeline
+import Json.Encode
+
+
+type alias Notification =
+    { counts : Maybe (Maybe (Dict String Float))
+    , priority : Int
+    , sentAt : String
+    , tags : Maybe (List String)
+    , title : String
+    }
+
+
+notificationDecoder : Json.Decode.Decoder Notification
+notificationDecoder =
+    Json.Decode.succeed Notification
+        |> Json.Decode.Pipeline.optional "counts" (Json.Decode.nullable (Json.Decode.nullable (Json.Decode.dict Json.Decode.float))) Nothing
+        |> Json.Decode.Pipeline.required "priority" Json.Decode.int
+        |> Json.Decode.Pipeline.required "sentAt" Json.Decode.string
+        |> Json.Decode.Pipeline.optional "tags" (Json.Decode.nullable (Json.Decode.list Json.Decode.string)) Nothing
+        |> Json.Decode.Pipeline.required "title" Json.Decode.string
+
+
+encodeNotification : Notification -> Json.Encode.Value
+encodeNotification notification_ =
+    List.filterMap identity
+        [ Maybe.map
+            (/counts_ ->
+                ( "counts"
+                , case counts_ of
+                    Just value ->
+                        Json.Encode.dict identity (/value -> Json.Encode.float value) value
+
+                    Nothing ->
+                        Json.Encode.null
+                )
+            )
+            notification_.counts
+        , Just ( "priority", Json.Encode.int notification_.priority )
+        , Just ( "sentAt", Json.Encode.string notification_.sentAt )
+        , Maybe.map (/tags_ -> ( "tags", Json.Encode.list (/value -> Json.Encode.string value) tags_ )) notification_.tags
+        , Just ( "title", Json.Encode.string notification_.title )
+        ]
+        |> Json.Encode.object
+
+
+type alias Notify =
+    { notification : Notification
+    }
+
+
+notifyDecoder : Json.Decode.Decoder Notify
+notifyDecoder =
+    Json.Decode.succeed Notify
+        |> Json.Decode.Pipeline.required "notification" notificationDecoder
+
+
+encodeNotify : Notify -> Json.Encode.Value
+encodeNotify notify_ =
+    Json.Encode.object
+        [ ( "notification", encodeNotification notify_.notification )
+        , ( "tag", Json.Encode.string "notify" )
+        ]
+
+
+type alias Refresh =
+    {}
+
+
+refreshDecoder : Json.Decode.Decoder Refresh
+refreshDecoder =
+    Json.Decode.succeed Refresh
+
+
+encodeRefresh : Refresh -> Json.Encode.Value
+encodeRefresh refresh_ =
+    Json.Encode.object
+        [ ( "tag", Json.Encode.string "refresh" )
+        ]
+
+
+type FromWorld
+    = FromWorldNotify Notify
+    | FromWorldRefresh Refresh
+
+
+fromWorldDecoder : Json.Decode.Decoder FromWorld
+fromWorldDecoder =
+    Json.Decode.andThen
+        (/tag ->
+            case tag of
+                "notify" ->
+                    Json.Decode.map FromWorldNotify notifyDecoder
+
+                "refresh" ->
+                    Json.Decode.map FromWorldRefresh refreshDecoder
+
+                unknown ->
+                    Json.Decode.fail ("Unknown value `" ++ unknown ++ "`")
+        )
+        (Json.Decode.field "tag" Json.Decode.string)
+
+
+encodeFromWorld : FromWorld -> Json.Encode.Value
+encodeFromWorld fromWorld_ =
+    case fromWorld_ of
+        FromWorldNotify fromWorldNotify ->
+            encodeNotify fromWorldNotify
+
+        FromWorldRefresh fromWorldRefresh ->
+            encodeRefresh fromWorldRefresh
+
+
+type alias Log =
+    String
+
+
+logDecoder : Json.Decode.Decoder Log
+logDecoder =
+    Json.Decode.string
+
+
+encodeLog : Log -> Json.Encode.Value
+encodeLog log_ =
+    Json.Encode.string log_
+
+
+type alias Notify =
+    { counts : Maybe (Maybe (Dict String Float))
+    , priority : Int
+    , sentAt : String
+    , tags : Maybe (List String)
+    , title : String
+    }
+
+
+notifyDecoder : Json.Decode.Decoder Notify
+notifyDecoder =
+    Json.Decode.succeed Notify
+        |> Json.Decode.Pipeline.optional "counts" (Json.Decode.nullable (Json.Decode.nullable (Json.Decode.dict Json.Decode.float))) Nothing
+        |> Json.Decode.Pipeline.required "priority" Json.Decode.int
+        |> Json.Decode.Pipeline.required "sentAt" Json.Decode.string
+        |> Json.Decode.Pipeline.optional "tags" (Json.Decode.nullable (Json.Decode.list Json.Decode.string)) Nothing
+        |> Json.Decode.Pipeline.required "title" Json.Decode.string
+
+
+encodeNotify : Notify -> Json.Encode.Value
+encodeNotify notify_ =
+    List.filterMap identity
+        [ Maybe.map
+            (/counts_ ->
+                ( "counts"
+                , case counts_ of
+                    Just value ->
+                        Json.Encode.dict identity (/value -> Json.Encode.float value) value
+
+                    Nothing ->
+                        Json.Encode.null
+                )
+            )
+            notify_.counts
+        , Just ( "priority", Json.Encode.int notify_.priority )
+        , Just ( "sentAt", Json.Encode.string notify_.sentAt )
+        , Maybe.map (/tags_ -> ( "tags", Json.Encode.list (/value -> Json.Encode.string value) tags_ )) notify_.tags
+        , Just ( "title", Json.Encode.string notify_.title )
+        ]
+        |> Json.Encode.object
+
+
+port fromWorld : (Json.Decode.Value -> msg) -> Sub msg
+
+
+subscribeToFromWorld : (Result Json.Decode.Error FromWorld -> msg) -> Sub msg
+subscribeToFromWorld toMsg =
+    fromWorld (Json.Decode.decodeValue fromWorldDecoder >> toMsg)
+
+
+port log : Json.Decode.Value -> Cmd msg
+
+
+sendLog : Log -> Cmd msg
+sendLog =
+    encodeLog >> log
+
+
+port notify : (Json.Decode.Value -> msg) -> Sub msg
+
+
+subscribeToNotify : (Result Json.Decode.Error Notify -> msg) -> Sub msg
+subscribeToNotify toMsg =
+    notify (Json.Decode.decodeValue notifyDecoder >> toMsg)
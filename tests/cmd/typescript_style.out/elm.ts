@@ -0,0 +1,32 @@
+// Warning: this file is automatically generated. Don't edit by hand!
+// elm-duet 0.1.0, schema d6cfed1204ad9688, content 213739bc9e01b1c4
+
+declare module Elm {
+  type NotificationPermission = "default" | "denied" | "granted";
+
+  namespace Main {
+    interface Flags {
+      readonly counts: Readonly<Record<string, number>>;
+      readonly permission: Elm.NotificationPermission;
+      readonly tags: readonly string[];
+      readonly theme: "dark" | "light";
+    }
+
+    interface Ports {
+      notify?: {
+        subscribe: (
+          callback: (value: {
+            readonly options: readonly {
+              readonly label: string;
+            }[];
+            readonly title: string;
+          }) => void,
+        ) => void;
+      };
+    }
+
+    function init(config: { flags: Flags; node: HTMLElement }): {
+      ports?: Ports;
+    };
+  }
+}
@@ -0,0 +1,215 @@
+// Warning: this file is automatically generated. Don't edit by hand!
+// elm-duet 0.1.0, schema d6cfed1204ad9688, content 01034686baf7e83d
+
+export const allNotificationPermission = [
+  "default",
+  "denied",
+  "granted",
+] as const;
+
+export enum NotificationPermission {
+  NotAsked = "default",
+  Denied = "denied",
+  Granted = "granted",
+}
+
+export const allTheme = ["dark", "light"] as const;
+
+export const Theme = {
+  Dark: "dark",
+  Light: "light",
+} as const;
+export type Theme = (typeof Theme)[keyof typeof Theme];
+
+export function isFlags(value: unknown): value is Elm.Main.Flags {
+  return checkFlags(value, "$") === null;
+}
+
+export function assertFlags(value: unknown): asserts value is Elm.Main.Flags {
+  const problem = checkFlags(value, "$");
+  if (problem !== null) {
+    throw new TypeError(problem);
+  }
+}
+
+export function isNotify(
+  value: unknown,
+): value is Parameters<
+  Parameters<NonNullable<Elm.Main.Ports["notify"]>["subscribe"]>[0]
+>[0] {
+  return checkNotify(value, "$") === null;
+}
+
+export function assertNotify(
+  value: unknown,
+): asserts value is Parameters<
+  Parameters<NonNullable<Elm.Main.Ports["notify"]>["subscribe"]>[0]
+>[0] {
+  const problem = checkNotify(value, "$");
+  if (problem !== null) {
+    throw new TypeError(problem);
+  }
+}
+
+/**
+ * Subscribe to every port Elm sends values through, and get functions for sending values to
+ * Elm. Elm removes ports that are never used, so we warn about missing ports (or throw, or
+ * ignore them, depending on `options.missingPorts`.)
+ */
+export function connectMain(
+  app: { ports?: Elm.Main.Ports },
+  handlers: {
+    notify: (
+      value: Parameters<
+        Parameters<NonNullable<Elm.Main.Ports["notify"]>["subscribe"]>[0]
+      >[0],
+    ) => void;
+  },
+  options: { missingPorts?: "throw" | "warn" | "ignore" } = {},
+): {} {
+  const ports: Elm.Main.Ports = app.ports ?? {};
+  const missing = (port: string) => {
+    const message = `the Main Elm module does not have a "${port}" port. Is it used in Elm?`;
+    if (options.missingPorts === "throw") {
+      throw new Error(message);
+    } else if (options.missingPorts !== "ignore") {
+      console.warn(message);
+    }
+  };
+
+  if (ports.notify) {
+    ports.notify.subscribe(handlers.notify);
+  } else {
+    missing("notify");
+  }
+
+  return {};
+}
+
+function checkFlags(value: unknown, path: string): string | null {
+  if (typeof value !== "object" || value === null || Array.isArray(value)) {
+    return `${path}: expected an object`;
+  }
+  const object0 = value as Record<string, unknown>;
+  if (!("counts" in object0)) {
+    return `${path}: missing "counts"`;
+  }
+  {
+    const value1 = object0["counts"];
+    if (
+      typeof value1 !== "object" ||
+      value1 === null ||
+      Array.isArray(value1)
+    ) {
+      return `${path}.counts: expected an object`;
+    }
+    for (const [key1, value2] of Object.entries(
+      value1 as Record<string, unknown>,
+    )) {
+      if (
+        typeof value2 !== "number" ||
+        !Number.isInteger(value2) ||
+        value2 < -2147483648 ||
+        value2 > 2147483647
+      ) {
+        return `${path}.counts.${key1}: expected an integer between -2147483648 and 2147483647`;
+      }
+    }
+  }
+  if (!("permission" in object0)) {
+    return `${path}: missing "permission"`;
+  }
+  {
+    const value1 = object0["permission"];
+    {
+      const problem = checkNotificationPermissionRef(
+        value1,
+        `${path}.permission`,
+      );
+      if (problem !== null) {
+        return problem;
+      }
+    }
+  }
+  if (!("tags" in object0)) {
+    return `${path}: missing "tags"`;
+  }
+  {
+    const value1 = object0["tags"];
+    if (!Array.isArray(value1)) {
+      return `${path}.tags: expected an array`;
+    }
+    for (let i1 = 0; i1 < value1.length; i1++) {
+      const value2: unknown = value1[i1];
+      if (typeof value2 !== "string") {
+        return `${path}.tags[${i1}]: expected a string`;
+      }
+    }
+  }
+  if (!("theme" in object0)) {
+    return `${path}: missing "theme"`;
+  }
+  {
+    const value1 = object0["theme"];
+    if (!["dark", "light"].includes(value1 as string)) {
+      return `${path}.theme: expected one of "dark", "light"`;
+    }
+  }
+  return null;
+}
+
+function checkNotificationPermissionRef(
+  value: unknown,
+  path: string,
+): string | null {
+  if (!["default", "denied", "granted"].includes(value as string)) {
+    return `${path}: expected one of "default", "denied", "granted"`;
+  }
+  return null;
+}
+
+function checkNotify(value: unknown, path: string): string | null {
+  if (typeof value !== "object" || value === null || Array.isArray(value)) {
+    return `${path}: expected an object`;
+  }
+  const object0 = value as Record<string, unknown>;
+  if (!("options" in object0)) {
+    return `${path}: missing "options"`;
+  }
+  {
+    const value1 = object0["options"];
+    if (!Array.isArray(value1)) {
+      return `${path}.options: expected an array`;
+    }
+    for (let i1 = 0; i1 < value1.length; i1++) {
+      const value2: unknown = value1[i1];
+      if (
+        typeof value2 !== "object" ||
+        value2 === null ||
+        Array.isArray(value2)
+      ) {
+        return `${path}.options[${i1}]: expected an object`;
+      }
+      const object2 = value2 as Record<string, unknown>;
+      if (!("label" in object2)) {
+        return `${path}.options[${i1}]: missing "label"`;
+      }
+      {
+        const value3 = object2["label"];
+        if (typeof value3 !== "string") {
+          return `${path}.options[${i1}].label: expected a string`;
+        }
+      }
+    }
+  }
+  if (!("title" in object0)) {
+    return `${path}: missing "title"`;
+  }
+  {
+    const value1 = object0["title"];
+    if (typeof value1 !== "string") {
+      return `${path}.title: expected a string`;
+    }
+  }
+  return null;
+}
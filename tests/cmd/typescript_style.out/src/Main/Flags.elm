@@ -0,0 +1,175 @@
+module Main.Flags exposing (..)
+
+{-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema d6cfed1204ad9688, content 07cfad5b979cd293
+-}
+
+import Dict exposing (Dict)
+import Json.Decode
+import Json.Decode.Pipeline
+import Json.Encode
+
+
+type NotificationPermission
+    = NotAsked
+    | NotificationPermissionDenied
+    | NotificationPermissionGranted
+
+
+notificationPermissionDecoder : Json.Decode.Decoder NotificationPermission
+notificationPermissionDecoder =
+    Json.Decode.andThen
+        (/tag ->
+            case tag of
+                "default" ->
+                    Json.Decode.succeed NotAsked
+
+                "denied" ->
+                    Json.Decode.succeed NotificationPermissionDenied
+
+                "granted" ->
+                    Json.Decode.succeed NotificationPermissionGranted
+
+                unknown ->
+                    Json.Decode.fail ("Unknown value `" ++ unknown ++ "`")
+        )
+        Json.Decode.string
+
+
+encodeNotificationPermission : NotificationPermission -> Json.Encode.Value
+encodeNotificationPermission notificationPermission_ =
+    case notificationPermission_ of
+        NotAsked ->
+            Json.Encode.string "default"
+
+        NotificationPermissionDenied ->
+            Json.Encode.string "denied"
+
+        NotificationPermissionGranted ->
+            Json.Encode.string "granted"
+
+
+allNotificationPermission : List NotificationPermission
+allNotificationPermission =
+    [ NotAsked
+    , NotificationPermissionDenied
+    , NotificationPermissionGranted
+    ]
+
+
+notificationPermissionToString : NotificationPermission -> String
+notificationPermissionToString notificationPermission_ =
+    case notificationPermission_ of
+        NotAsked ->
+            "default"
+
+        NotificationPermissionDenied ->
+            "denied"
+
+        NotificationPermissionGranted ->
+            "granted"
+
+
+notificationPermissionFromString : String -> Maybe NotificationPermission
+notificationPermissionFromString string_ =
+    case string_ of
+        "default" ->
+            Just NotAsked
+
+        "denied" ->
+            Just NotificationPermissionDenied
+
+        "granted" ->
+            Just NotificationPermissionGranted
+
+        _ ->
+            Nothing
+
+
+type Theme
+    = ThemeDark
+    | ThemeLight
+
+
+themeDecoder : Json.Decode.Decoder Theme
+themeDecoder =
+    Json.Decode.andThen
+        (/tag ->
+            case tag of
+                "dark" ->
+                    Json.Decode.succeed ThemeDark
+
+                "light" ->
+                    Json.Decode.succeed ThemeLight
+
+                unknown ->
+                    Json.Decode.fail ("Unknown value `" ++ unknown ++ "`")
+        )
+        Json.Decode.string
+
+
+encodeTheme : Theme -> Json.Encode.Value
+encodeTheme theme_ =
+    case theme_ of
+        ThemeDark ->
+            Json.Encode.string "dark"
+
+        ThemeLight ->
+            Json.Encode.string "light"
+
+
+allTheme : List Theme
+allTheme =
+    [ ThemeDark
+    , ThemeLight
+    ]
+
+
+themeToString : Theme -> String
+themeToString theme_ =
+    case theme_ of
+        ThemeDark ->
+            "dark"
+
+        ThemeLight ->
+            "light"
+
+
+themeFromString : String -> Maybe Theme
+themeFromString string_ =
+    case string_ of
+        "dark" ->
+            Just ThemeDark
+
+        "light" ->
+            Just ThemeLight
+
+        _ ->
+            Nothing
+
+
+type alias Flags =
+    { counts : Dict String Int
+    , permission : NotificationPermission
+    , tags : List String
+    , theme : Theme
+    }
+
+
+flagsDecoder : Json.Decode.Decoder Flags
+flagsDecoder =
+    Json.Decode.succeed Flags
+        |> Json.Decode.Pipeline.required "counts" (Json.Decode.dict Json.Decode.int)
+        |> Json.Decode.Pipeline.required "permission" notificationPermissionDecoder
+        |> Json.Decode.Pipeline.required "tags" (Json.Decode.list Json.Decode.string)
+        |> Json.Decode.Pipeline.required "theme" themeDecoder
+
+
+encodeFlags : Flags -> Json.Encode.Value
+encodeFlags flags_ =
+    Json.Encode.object
+        [ ( "counts", Json.Encode.dict identity (/value -> Json.Encode.int value) flags_.counts )
+        , ( "permission", encodeNotificationPermission flags_.permission )
+        , ( "tags", Json.Encode.list (/value -> Json.Encode.string value) flags_.tags )
+        , ( "theme", encodeTheme flags_.theme )
+        ]
@@ -0,0 +1,57 @@
+port module Main.Ports exposing (..)
+
+{-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema d6cfed1204ad9688, content 2d7af5e8f033e8cd
+-}
+
+import Dict exposing (Dict)
+import Json.Decode
+import Json.Decode.Pipeline
+import Json.Encode
+
+
+type alias OptionsElements =
+    { label : String
+    }
+
+
+optionsElementsDecoder : Json.Decode.Decoder OptionsElements
+optionsElementsDecoder =
+    Json.Decode.succeed OptionsElements
+        |> Json.Decode.Pipeline.required "label" Json.Decode.string
+
+
+encodeOptionsElements : OptionsElements -> Json.Encode.Value
+encodeOptionsElements optionsElements_ =
+    Json.Encode.object
+        [ ( "label", Json.Encode.string optionsElements_.label )
+        ]
+
+
+type alias Notify =
+    { options : List OptionsElements
+    , title : String
+    }
+
+
+notifyDecoder : Json.Decode.Decoder Notify
+notifyDecoder =
+    Json.Decode.succeed Notify
+        |> Json.Decode.Pipeline.required "options" (Json.Decode.list optionsElementsDecoder)
+        |> Json.Decode.Pipeline.required "title" Json.Decode.string
+
+
+encodeNotify : Notify -> Json.Encode.Value
+encodeNotify notify_ =
+    Json.Encode.object
+        [ ( "options", Json.Encode.list (/value -> encodeOptionsElements value) notify_.options )
+        , ( "title", Json.Encode.string notify_.title )
+        ]
+
+
+port notify : Json.Decode.Value -> Cmd msg
+
+
+sendNotify : Notify -> Cmd msg
+sendNotify =
+    encodeNotify >> notify
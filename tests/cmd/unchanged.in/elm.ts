@@ -0,0 +1,20 @@
+// Warning: this file is automatically generated. Don't edit by hand!
+// elm-duet 0.1.0, schema 825443d6c3f7e1c5, content a29ae401dfce59ff
+
+declare module Elm {
+  namespace Main {
+    type Flags = {
+      name: string;
+    };
+
+    type Ports = {
+      greet?: {
+        subscribe: (callback: (value: string) => void) => void;
+      };
+    };
+
+    function init(config: { flags: Flags; node: HTMLElement }): {
+      ports?: Ports;
+    };
+  }
+}
@@ -0,0 +1,3 @@
+module Main.Flags exposing (..)
+
+-- an old version of the flags
@@ -0,0 +1,32 @@
+port module Main.Ports exposing (..)
+
+{-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema 825443d6c3f7e1c5, content 92c0797155ed2d0a
+-}
+
+import Dict exposing (Dict)
+import Json.Decode
+import Json.Decode.Pipeline
+import Json.Encode
+
+
+type alias Greet =
+    String
+
+
+greetDecoder : Json.Decode.Decoder Greet
+greetDecoder =
+    Json.Decode.string
+
+
+encodeGreet : Greet -> Json.Encode.Value
+encodeGreet greet_ =
+    Json.Encode.string greet_
+
+
+port greet : Json.Decode.Value -> Cmd msg
+
+
+sendGreet : Greet -> Cmd msg
+sendGreet =
+    encodeGreet >> greet
@@ -0,0 +1,55 @@
+// Warning: this file is automatically generated. Don't edit by hand!
+// elm-duet 0.1.0, schema 9783d90154cda1a9, content 3ceebebbbf42cf4e
+
+declare module Elm {
+  type Notification = {
+    counts?: Record<string, number> | null;
+    level?: Elm.NotificationLevel;
+    priority: number;
+    sentAt: string;
+    tags?: string[];
+    title: string;
+  };
+
+  type NotificationLevel = "error" | "info" | "warning" | (string & {});
+
+  type Notify = {
+    counts?: Record<string, number> | null;
+    level?: Elm.NotificationLevel;
+    priority: number;
+    sentAt: string;
+    tags?: string[];
+    title: string;
+  };
+
+  namespace Main {
+    type Flags = {
+      retries: number | null;
+    };
+
+    type Ports = {
+      fromWorld?: {
+        send: (
+          value:
+            | {
+                notification: Elm.Notification;
+                tag: "notify";
+              }
+            | {
+                tag: "refresh";
+              },
+        ) => void;
+      };
+      log?: {
+        subscribe: (callback: (value: string) => void) => void;
+      };
+      notify?: {
+        send: (value: Elm.Notify) => void;
+      };
+    };
+
+    function init(config: { flags: Flags; node: HTMLElement }): {
+      ports?: Ports;
+    };
+  }
+}
@@ -0,0 +1,35 @@
+module Main.Flags exposing (..)
+
+{-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema 9783d90154cda1a9, content 387be8a25d5d62e6
+-}
+
+import Dict exposing (Dict)
+import Json.Decode
+import Json.Decode.Pipeline
+import Json.Encode
+
+
+type alias Flags =
+    { retries : Maybe Int
+    }
+
+
+flagsDecoder : Json.Decode.Decoder Flags
+flagsDecoder =
+    Json.Decode.succeed Flags
+        |> Json.Decode.Pipeline.required "retries" (Json.Decode.nullable Json.Decode.int)
+
+
+encodeFlags : Flags -> Json.Encode.Value
+encodeFlags flags_ =
+    Json.Encode.object
+        [ ( "retries"
+          , case flags_.retries of
+                Just value ->
+                    Json.Encode.int value
+
+                Nothing ->
+                    Json.Encode.null
+          )
+        ]
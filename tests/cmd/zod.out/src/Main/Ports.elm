@@ -0,0 +1,288 @@
+port module Main.Ports exposing (..)
+
+{-| Warning: this file is automatically generated. Don't edit by hand!
+elm-duet 0.1.0, schema 9783d90154cda1a9, content 97122327d6cffad8
+-}
+
+import Dict exposing (Dict)
+import Json.Decode
+import Json.Decode.Pipeline
+import Json.Encode
+
+
+type NotificationLevel
+    = NotificationLevelError
+    | NotificationLevelInfo
+    | NotificationLevelWarning
+    | NotificationLevelUnknown String
+
+
+notificationLevelDecoder : Json.Decode.Decoder NotificationLevel
+notificationLevelDecoder =
+    Json.Decode.andThen
+        (/tag ->
+            case tag of
+                "error" ->
+                    Json.Decode.succeed NotificationLevelError
+
+                "info" ->
+                    Json.Decode.succeed NotificationLevelInfo
+
+                "warning" ->
+                    Json.Decode.succeed NotificationLevelWarning
+
+                unknown ->
+                    Json.Decode.succeed (NotificationLevelUnknown unknown)
+        )
+        Json.Decode.string
+
+
+encodeNotificationLevel : NotificationLevel -> Json.Encode.Value
+encodeNotificationLevel notificationLevel_ =
+    case notificationLevel_ of
+        NotificationLevelError ->
+            Json.Encode.string "error"
+
+        NotificationLevelInfo ->
+            Json.Encode.string "info"
+
+        NotificationLevelWarning ->
+            Json.Encode.string "warning"
+
+        NotificationLevelUnknown unknown ->
+            Json.Encode.string unknown
+
+
+allNotificationLevel : List NotificationLevel
+allNotificationLevel =
+    [ NotificationLevelError
+    , NotificationLevelInfo
+    , NotificationLevelWarning
+    ]
+
+
+notificationLevelToString : NotificationLevel -> String
+notificationLevelToString notificationLevel_ =
+    case notificationLevel_ of
+        NotificationLevelError ->
+            "error"
+
+        NotificationLevelInfo ->
+            "info"
+
+        NotificationLevelWarning ->
+            "warning"
+
+        NotificationLevelUnknown unknown ->
+            unknown
+
+
+notificationLevelFromString : String -> NotificationLevel
+notificationLevelFromString string_ =
+    case string_ of
+        "error" ->
+            NotificationLevelError
+
+        "info" ->
+            NotificationLevelInfo
+
+        "warning" ->
+            NotificationLevelWarning
+
+        unknown ->
+            NotificationLevelUnknown unknown
+
+
+type alias Notification =
+    { counts : Maybe (Maybe (Dict String Float))
+    , level : Maybe NotificationLevel
+    , priority : Int
+    , sentAt : String
+    , tags : Maybe (List String)
+    , title : String
+    }
+
+
+notificationDecoder : Json.Decode.Decoder Notification
+notificationDecoder =
+    Json.Decode.succeed Notification
+        |> Json.Decode.Pipeline.optional "counts" (Json.Decode.nullable (Json.Decode.nullable (Json.Decode.dict Json.Decode.float))) Nothing
+        |> Json.Decode.Pipeline.optional "level" (Json.Decode.nullable notificationLevelDecoder) Nothing
+        |> Json.Decode.Pipeline.required "priority" Json.Decode.int
+        |> Json.Decode.Pipeline.required "sentAt" Json.Decode.string
+        |> Json.Decode.Pipeline.optional "tags" (Json.Decode.nullable (Json.Decode.list Json.Decode.string)) Nothing
+        |> Json.Decode.Pipeline.required "title" Json.Decode.string
+
+
+encodeNotification : Notification -> Json.Encode.Value
+encodeNotification notification_ =
+    List.filterMap identity
+        [ Maybe.map
+            (/counts_ ->
+                ( "counts"
+                , case counts_ of
+                    Just value ->
+                        Json.Encode.dict identity (/value -> Json.Encode.float value) value
+
+                    Nothing ->
+                        Json.Encode.null
+                )
+            )
+            notification_.counts
+        , Maybe.map (/level_ -> ( "level", encodeNotificationLevel level_ )) notification_.level
+        , Just ( "priority", Json.Encode.int notification_.priority )
+        , Just ( "sentAt", Json.Encode.string notification_.sentAt )
+        , Maybe.map (/tags_ -> ( "tags", Json.Encode.list (/value -> Json.Encode.string value) tags_ )) notification_.tags
+        , Just ( "title", Json.Encode.string notification_.title )
+        ]
+        |> Json.Encode.object
+
+
+type alias Notify =
+    { notification : Notification
+    }
+
+
+notifyDecoder : Json.Decode.Decoder Notify
+notifyDecoder =
+    Json.Decode.succeed Notify
+        |> Json.Decode.Pipeline.required "notification" notificationDecoder
+
+
+encodeNotify : Notify -> Json.Encode.Value
+encodeNotify notify_ =
+    Json.Encode.object
+        [ ( "notification", encodeNotification notify_.notification )
+        , ( "tag", Json.Encode.string "notify" )
+        ]
+
+
+type alias Refresh =
+    {}
+
+
+refreshDecoder : Json.Decode.Decoder Refresh
+refreshDecoder =
+    Json.Decode.succeed Refresh
+
+
+encodeRefresh : Refresh -> Json.Encode.Value
+encodeRefresh refresh_ =
+    Json.Encode.object
+        [ ( "tag", Json.Encode.string "refresh" )
+        ]
+
+
+type FromWorld
+    = FromWorldNotify Notify
+    | FromWorldRefresh Refresh
+
+
+fromWorldDecoder : Json.Decode.Decoder FromWorld
+fromWorldDecoder =
+    Json.Decode.andThen
+        (/tag ->
+            case tag of
+                "notify" ->
+                    Json.Decode.map FromWorldNotify notifyDecoder
+
+                "refresh" ->
+                    Json.Decode.map FromWorldRefresh refreshDecoder
+
+                unknown ->
+                    Json.Decode.fail ("Unknown value `" ++ unknown ++ "`")
+        )
+        (Json.Decode.field "tag" Json.Decode.string)
+
+
+encodeFromWorld : FromWorld -> Json.Encode.Value
+encodeFromWorld fromWorld_ =
+    case fromWorld_ of
+        FromWorldNotify fromWorldNotify ->
+            encodeNotify fromWorldNotify
+
+        FromWorldRefresh fromWorldRefresh ->
+            encodeRefresh fromWorldRefresh
+
+
+type alias Log =
+    String
+
+
+logDecoder : Json.Decode.Decoder Log
+logDecoder =
+    Json.Decode.string
+
+
+encodeLog : Log -> Json.Encode.Value
+encodeLog log_ =
+    Json.Encode.string log_
+
+
+type alias Notify =
+    { counts : Maybe (Maybe (Dict String Float))
+    , level : Maybe NotificationLevel
+    , priority : Int
+    , sentAt : String
+    , tags : Maybe (List String)
+    , title : String
+    }
+
+
+notifyDecoder : Json.Decode.Decoder Notify
+notifyDecoder =
+    Json.Decode.succeed Notify
+        |> Json.Decode.Pipeline.optional "counts" (Json.Decode.nullable (Json.Decode.nullable (Json.Decode.dict Json.Decode.float))) Nothing
+        |> Json.Decode.Pipeline.optional "level" (Json.Decode.nullable notificationLevelDecoder) Nothing
+        |> Json.Decode.Pipeline.required "priority" Json.Decode.int
+        |> Json.Decode.Pipeline.required "sentAt" Json.Decode.string
+        |> Json.Decode.Pipeline.optional "tags" (Json.Decode.nullable (Json.Decode.list Json.Decode.string)) Nothing
+        |> Json.Decode.Pipeline.required "title" Json.Decode.string
+
+
+encodeNotify : Notify -> Json.Encode.Value
+encodeNotify notify_ =
+    List.filterMap identity
+        [ Maybe.map
+            (/counts_ ->
+                ( "counts"
+                , case counts_ of
+                    Just value ->
+                        Json.Encode.dict identity (/value -> Json.Encode.float value) value
+
+                    Nothing ->
+                        Json.Encode.null
+                )
+            )
+            notify_.counts
+        , Maybe.map (/level_ -> ( "level", encodeNotificationLevel level_ )) notify_.level
+        , Just ( "priority", Json.Encode.int notify_.priority )
+        , Just ( "sentAt", Json.Encode.string notify_.sentAt )
+        , Maybe.map (/tags_ -> ( "tags", Json.Encode.list (/value -> Json.Encode.string value) tags_ )) notify_.tags
+        , Just ( "title", Json.Encode.string notify_.title )
+        ]
+        |> Json.Encode.object
+
+
+port fromWorld : (Json.Decode.Value -> msg) -> Sub msg
+
+
+subscribeToFromWorld : (Result Json.Decode.Error FromWorld -> msg) -> Sub msg
+subscribeToFromWorld toMsg =
+    fromWorld (Json.Decode.decodeValue fromWorldDecoder >> toMsg)
+
+
+port log : Json.Decode.Value -> Cmd msg
+
+
+sendLog : Log -> Cmd msg
+sendLog =
+    encodeLog >> log
+
+
+port notify : (Json.Decode.Value -> msg) -> Sub msg
+
+
+subscribeToNotify : (Result Json.Decode.Error Notify -> msg) -> Sub msg
+subscribeToNotify toMsg =
+    notify (Json.Decode.decodeValue notifyDecoder >> toMsg)
@@ -0,0 +1,52 @@
+// Warning: this file is automatically generated. Don't edit by hand!
+// elm-duet 0.1.0, schema 9783d90154cda1a9, content 9dbb2530429c090f
+
+import { z } from "zod";
+
+export const NotificationLevelSchema = z.union([
+  z.enum(["error", "info", "warning"]),
+  z.string(),
+]);
+export type NotificationLevel = z.infer<typeof NotificationLevelSchema>;
+
+export const NotificationSchema = z.object({
+  counts: z.record(z.string(), z.number()).nullable().optional(),
+  level: NotificationLevelSchema.optional(),
+  priority: z.number().int().min(0).max(255),
+  sentAt: z.string().datetime({ offset: true }),
+  tags: z.array(z.string()).optional(),
+  title: z.string(),
+});
+export type Notification = z.infer<typeof NotificationSchema>;
+
+export const MainFlagsSchema = z.object({
+  retries: z.number().int().min(-128).max(127).nullable(),
+});
+export type MainFlags = z.infer<typeof MainFlagsSchema>;
+
+export const MainFromWorldSchema = z.discriminatedUnion("tag", [
+  z.object({
+    notification: NotificationSchema,
+    tag: z.literal("notify"),
+  }),
+  z.object({
+    tag: z.literal("refresh"),
+  }),
+]);
+export type MainFromWorld = z.infer<typeof MainFromWorldSchema>;
+
+export const MainLogSchema = z.string();
+export type MainLog = z.infer<typeof MainLogSchema>;
+
+export const NotifySchema = z.object({
+  counts: z.record(z.string(), z.number()).nullable().optional(),
+  level: NotificationLevelSchema.optional(),
+  priority: z.number().int().min(0).max(255),
+  sentAt: z.string().datetime({ offset: true }),
+  tags: z.array(z.string()).optional(),
+  title: z.string(),
+});
+export type Notify = z.infer<typeof NotifySchema>;
+
+export const MainNotifySchema = NotifySchema;
+export type MainNotify = z.infer<typeof MainNotifySchema>;